@@ -3,31 +3,133 @@ use std::time::Duration;
 
 use criterion::{BatchSize, Bencher, black_box, Criterion, criterion_group, criterion_main};
 
-use find_words::{generate_random_string, get_constructable_words, get_letters_count, read_words_from_file};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use find_words::corpus::{evaluate, read_corpus};
+use find_words::merge::kmerge;
+use find_words::rack::word_score;
+use find_words::top::{find_top_k, Ranking};
+use find_words::trie::TrieDictionary;
+use find_words::{Dictionary, get_constructable_words, get_letters_count, read_words_from_file, Rack, RackSpec};
+
+/// Number of words in each of the benchmark word lists.
+const NO_OF_WORDS: [u16; 3] = [100, 1000, 10000];
+
+/// Lengths of the random strings of letters.
+const VARIOUS_LENGTHS: [u8; 6] = [4, 8, 12, 50, 100, 200];
 
 fn bench_find_words_in_letter_string(b: &mut Bencher, list_length: u8, filename: &str) {
     let words = black_box(read_words_from_file(filename).unwrap());
-    let list = black_box(get_letters_count(&generate_random_string(Some(list_length))));
+    let list = black_box(get_letters_count(&random_list(list_length)));
     b.iter_batched(|| words.to_vec(),
                    |words| get_constructable_words(words, &list),
                    BatchSize::SmallInput,
     )
 }
 
-fn find_words_benchmark(c: &mut Criterion) {
-    let mut group = c.benchmark_group("Find words");
+fn bench_dictionary_find(b: &mut Bencher, list_length: u8, filename: &str) {
+    let dictionary = black_box(Dictionary::from_file(filename).unwrap());
+    let list = black_box(random_list(list_length));
+    b.iter(|| dictionary.find(&list))
+}
+
+fn bench_trie_find(b: &mut Bencher, list_length: u8, filename: &str) {
+    let dictionary = black_box(TrieDictionary::new(read_words_from_file(filename).unwrap()));
+    let list = black_box(random_list(list_length));
+    b.iter(|| dictionary.find(&list))
+}
+
+/// Benchmarks one engine. Each engine gets its own group, so that its results are stored
+/// (and compared against baselines) under `target/criterion/<engine>`.
+fn bench_engine(c: &mut Criterion, engine: &str, bench: fn(&mut Bencher, u8, &str)) {
+    let mut group = c.benchmark_group(engine);
     group.measurement_time(Duration::new(6, 0));
-    let no_of_words = [100u16, 1000u16, 10000u16];
-    let various_lengths = [4, 8, 12, 50, 100, 200];
-    for curr_no in no_of_words {
+    for curr_no in NO_OF_WORDS {
         let path = get_file_path(curr_no);
-        various_lengths.iter().for_each(|&length| {
+        VARIOUS_LENGTHS.iter().for_each(|&length| {
             group.bench_function(
                 format!("bench find words in {} letter string from {} words", length, curr_no),
-                |b| bench_find_words_in_letter_string(b, length, &path),
+                |b| bench(b, length, &path),
             );
         });
     }
+    group.finish();
+}
+
+/// Runs every engine on the corpus of realistic racks, after checking that each engine finds
+/// the expected number of solutions for every rack.
+fn bench_corpus(c: &mut Criterion) {
+    let dictionary_path = get_file_path(10000);
+    let corpus = read_corpus(&format!("{}/benches/racks_10000_words.txt", env::current_dir().unwrap().display())).unwrap();
+    let words = read_words_from_file(&dictionary_path).unwrap();
+    let dictionary = Dictionary::new(words.clone());
+    let trie = TrieDictionary::new(words.clone());
+    let linear = |rack: &str| get_constructable_words(words.clone(), &get_letters_count(rack)).len();
+    let indexed = |rack: &str| dictionary.find(rack).len();
+    let pruned = |rack: &str| trie.find(rack).len();
+
+    let mut group = c.benchmark_group("corpus");
+    for (engine, count) in [("linear", &linear as &dyn Fn(&str) -> usize), ("dictionary", &indexed), ("trie", &pruned)] {
+        let report = evaluate(&corpus, count);
+        assert!(report.mismatches.is_empty(), "{} engine disagrees with the corpus: {:?}", engine, report.mismatches);
+        group.bench_function(format!("{} on {} racks", engine, corpus.len()), |b| b.iter(|| evaluate(&corpus, count)));
+    }
+    group.finish();
+}
+
+/// Compares merging sorted runs (one per first letter of the 10000 word list) with sorting their
+/// concatenation, for the whole result and for its first 10 words.
+fn bench_merge(c: &mut Criterion) {
+    let words = read_words_from_file(&get_file_path(10000)).unwrap();
+    let mut runs: Vec<Vec<String>> = vec![vec![]; 27];
+    for word in words {
+        let run = word.value.bytes().next().filter(u8::is_ascii_lowercase).map_or(26, |b| (b - b'a') as usize);
+        runs[run].push(word.value);
+    }
+    runs.iter_mut().for_each(|run| run.sort_unstable());
+
+    let mut group = c.benchmark_group("merge");
+    group.bench_function("sort concatenated runs", |b| b.iter(|| {
+        let mut words: Vec<&String> = runs.iter().flatten().collect();
+        words.sort_unstable();
+        words
+    }));
+    group.bench_function("k-way merge", |b| b.iter(|| kmerge(runs.iter().map(|run| run.iter())).collect::<Vec<_>>()));
+    group.bench_function("k-way merge, first 10", |b| b.iter(|| kmerge(runs.iter().map(|run| run.iter())).take(10).collect::<Vec<_>>()));
+    group.finish();
+}
+
+/// Compares keeping the 10 best scoring words of a long list of letters with scoring and sorting
+/// every word found.
+fn bench_top_k(c: &mut Criterion) {
+    let words = read_words_from_file(&get_file_path(10000)).unwrap();
+    let list = get_letters_count(&random_list(100));
+
+    let mut group = c.benchmark_group("top_k");
+    group.bench_function("sort every word found, first 10", |b| b.iter(|| {
+        let mut found = get_constructable_words(words.clone(), &list);
+        found.sort_by_key(|word| std::cmp::Reverse(word_score(word)));
+        found.truncate(10);
+        found
+    }));
+    group.bench_function("bounded heaps, 10", |b| b.iter(|| find_top_k(&words, &list, 10, Ranking::Score)));
+    group.finish();
+}
+
+fn find_words_benchmark(c: &mut Criterion) {
+    bench_engine(c, "linear", bench_find_words_in_letter_string);
+    bench_engine(c, "dictionary", bench_dictionary_find);
+    bench_engine(c, "trie", bench_trie_find);
+    bench_corpus(c);
+    bench_merge(c);
+    bench_top_k(c);
+}
+
+/// A random list of `len` letters, the same on every run so that runs can be compared with a
+/// baseline.
+fn random_list(len: u8) -> String {
+    Rack::random(RackSpec::letters(len), &mut StdRng::seed_from_u64(len as u64)).to_string()
 }
 
 fn get_file_path(no_of_words: u16) -> String {
@@ -37,4 +139,4 @@ fn get_file_path(no_of_words: u16) -> String {
 }
 
 criterion_group!(benches, find_words_benchmark);
-criterion_main!(benches);
\ No newline at end of file
+criterion_main!(benches);
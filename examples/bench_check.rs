@@ -0,0 +1,113 @@
+//! Compares the latest benchmark results against a saved criterion baseline and exits with a
+//! non-zero status if any benchmark got slower by more than the allowed threshold.
+//!
+//! ```sh
+//! cargo bench --bench find_words_benchmark -- --save-baseline main   # on the reference commit
+//! cargo bench --bench find_words_benchmark                          # on your branch
+//! cargo bench-check --baseline main --threshold 10
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use serde_json::Value;
+
+const USAGE: &str = "usage: bench_check --baseline <name> [--threshold <percent>] [--dir <criterion dir>] [--engine <name>]";
+
+struct Options {
+    baseline: String,
+    threshold: f64,
+    dir: PathBuf,
+    engine: Option<String>,
+}
+
+struct Comparison {
+    name: String,
+    baseline: f64,
+    current: f64,
+}
+
+impl Comparison {
+    fn change(&self) -> f64 {
+        (self.current - self.baseline) / self.baseline * 100.0
+    }
+}
+
+fn main() {
+    let options = parse_args().unwrap_or_else(|err| {
+        eprintln!("{}\n{}", err, USAGE);
+        process::exit(2);
+    });
+    let root = match &options.engine {
+        Some(engine) => options.dir.join(engine),
+        None => options.dir.clone(),
+    };
+
+    let mut comparisons = vec![];
+    collect(&root, &root, &options.baseline, &mut comparisons);
+    if comparisons.is_empty() {
+        eprintln!("no results with baseline {:?} found in {}", options.baseline, root.display());
+        process::exit(2);
+    }
+    comparisons.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut regressions = 0;
+    for comparison in &comparisons {
+        let change = comparison.change();
+        let regressed = change > options.threshold;
+        if regressed {
+            regressions += 1;
+        }
+        println!("{:>8.2}% {:>12.0}ns -> {:>12.0}ns {}{}",
+                 change, comparison.baseline, comparison.current, comparison.name,
+                 if regressed { "  REGRESSION" } else { "" });
+    }
+    println!("{} of {} benchmarks regressed by more than {}%", regressions, comparisons.len(), options.threshold);
+    if regressions > 0 {
+        process::exit(1);
+    }
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut baseline = None;
+    let mut threshold = 5.0;
+    let mut dir = PathBuf::from("target/criterion");
+    let mut engine = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+        match arg.as_str() {
+            "--baseline" => baseline = Some(value()?),
+            "--threshold" => threshold = value()?.parse().map_err(|_| "threshold must be a number".to_string())?,
+            "--dir" => dir = PathBuf::from(value()?),
+            "--engine" => engine = Some(value()?),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let baseline = baseline.ok_or("missing --baseline")?;
+    Ok(Options { baseline, threshold, dir, engine })
+}
+
+/// Finds every benchmark directory holding both the baseline and the latest (`new`) estimates.
+fn collect(root: &Path, dir: &Path, baseline: &str, comparisons: &mut Vec<Comparison>) {
+    let (Some(baseline_mean), Some(current_mean)) = (mean(&dir.join(baseline)), mean(&dir.join("new"))) else {
+        let Ok(entries) = fs::read_dir(dir) else { return };
+        for entry in entries.flatten() {
+            if entry.path().is_dir() {
+                collect(root, &entry.path(), baseline, comparisons);
+            }
+        }
+        return;
+    };
+    let name = dir.strip_prefix(root).unwrap_or(dir).display().to_string();
+    comparisons.push(Comparison { name, baseline: baseline_mean, current: current_mean });
+}
+
+/// Mean time in nanoseconds stored in a criterion `estimates.json`.
+fn mean(dir: &Path) -> Option<f64> {
+    let estimates: Value = serde_json::from_str(&fs::read_to_string(dir.join("estimates.json")).ok()?).ok()?;
+    estimates["mean"]["point_estimate"].as_f64()
+}
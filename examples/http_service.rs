@@ -0,0 +1,142 @@
+//! A minimal HTTP microservice answering word searches with JSON, built on the standard library
+//! only. Queries come from untrusted users, so they are checked against [`QueryLimits`] first.
+//!
+//! ```sh
+//! cargo run --example http_service -- --dict tests/golden/lexicons/tiny.txt --port 8080
+//! curl 'http://127.0.0.1:8080/words?letters=tca'
+//! curl 'http://127.0.0.1:8080/words?letters=tc%3F'      # %3F is a ? wildcard
+//! curl 'http://127.0.0.1:8080/health'
+//! ```
+
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process;
+use std::sync::Arc;
+use std::thread;
+
+use find_words::limits::QueryLimits;
+use find_words::Dictionary;
+
+const USAGE: &str = "usage: http_service [--dict <path>] [--port <port>]";
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let (mut path, mut port) = ("words.txt".to_string(), 8080u16);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dict" => path = args.next().unwrap_or_else(|| exit(USAGE)),
+            "--port" => port = args.next().and_then(|port| port.parse().ok()).unwrap_or_else(|| exit(USAGE)),
+            _ => exit(USAGE),
+        }
+    }
+    let dictionary = Dictionary::from_file(&path).unwrap_or_else(|error| exit(&format!("cannot read {}: {}", path, error)));
+    let dictionary = Arc::new(dictionary);
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|error| exit(&format!("cannot listen on port {}: {}", port, error)));
+    println!("Listening on http://127.0.0.1:{} with {} words", port, dictionary.len());
+
+    for stream in listener.incoming().flatten() {
+        let dictionary = Arc::clone(&dictionary);
+        thread::spawn(move || {
+            if let Err(error) = handle(stream, &dictionary) {
+                eprintln!("connection failed: {}", error);
+            }
+        });
+    }
+}
+
+/// Answers a single request, closing the connection afterwards.
+fn handle(mut stream: TcpStream, dictionary: &Dictionary) -> std::io::Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let (status, body) = match request_line.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["GET", target, _] => route(target, dictionary),
+        _ => ("400 Bad Request", error_json("malformed request")),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body,
+    )
+}
+
+fn route(target: &str, dictionary: &Dictionary) -> (&'static str, String) {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    match path {
+        "/health" => ("200 OK", format!("{{\"status\":\"ok\",\"words\":{}}}", dictionary.len())),
+        "/words" => {
+            let letters = query.split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == "letters")
+                .map(|(_, value)| percent_decode(value));
+            match letters {
+                Some(Some(letters)) => search(&letters, dictionary),
+                Some(None) => ("400 Bad Request", error_json("letters are not correctly encoded")),
+                None => ("400 Bad Request", error_json("missing letters parameter")),
+            }
+        }
+        _ => ("404 Not Found", error_json("not found")),
+    }
+}
+
+fn search(letters: &str, dictionary: &Dictionary) -> (&'static str, String) {
+    let limits = QueryLimits::default();
+    if let Err(error) = limits.check_letters(letters) {
+        return ("400 Bad Request", error_json(&error.to_string()));
+    }
+    let words = dictionary.find_with_wildcards(letters, limits.max_wildcards);
+    let truncated = words.len() > limits.max_results;
+    let words: Vec<String> = words.iter().take(limits.max_results).map(|word| json_string(word)).collect();
+    let body = format!("{{\"letters\":{},\"words\":[{}],\"truncated\":{}}}", json_string(letters), words.join(","), truncated);
+    ("200 OK", body)
+}
+
+/// Decodes `%XX` escapes and `+` for spaces, or `None` if the result is not UTF-8.
+fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                let hex = std::str::from_utf8(bytes.get(index + 1..index + 3)?).ok()?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                index += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn error_json(message: &str) -> String {
+    format!("{{\"error\":{}}}", json_string(message))
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
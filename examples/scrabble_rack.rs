@@ -0,0 +1,65 @@
+//! Helps with a Scrabble rack: the best words it can make, scored with the tiles they use
+//! (blanks score nothing), and the best exchanges if the player would rather draw new tiles.
+//! Without a rack, one is drawn from an English bag.
+//!
+//! ```sh
+//! cargo run --example scrabble_rack -- aert?sq
+//! cargo run --example scrabble_rack -- --dict tests/golden/lexicons/tiny.txt
+//! ```
+
+use std::env;
+use std::process;
+
+use find_words::game::{TileBag, RACK_SIZE};
+use find_words::rack::{tile_score, word_score};
+use find_words::simulation::{advise_exchange, SimulationConfig};
+use find_words::{Dictionary, Rack};
+
+const USAGE: &str = "usage: scrabble_rack [--dict <path>] [rack, blanks written ?]";
+
+/// Number of words and exchanges shown.
+const TOP: usize = 10;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let (mut path, mut rack) = ("words.txt".to_string(), None);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dict" => path = args.next().unwrap_or_else(|| exit(USAGE)),
+            _ if rack.is_none() => rack = Some(arg.parse::<Rack>().unwrap_or_else(|error| exit(&format!("invalid rack: {}", error)))),
+            _ => exit(USAGE),
+        }
+    }
+    let dictionary = Dictionary::from_file(&path).unwrap_or_else(|error| exit(&format!("cannot read {}: {}", path, error)));
+    let bag = TileBag::english().with_blanks(2);
+    let rack = rack.unwrap_or_else(|| bag.clone().draw(&mut rand::thread_rng(), RACK_SIZE));
+    println!("Rack: {}", rack);
+
+    let mut plays: Vec<(u32, String)> = dictionary.find_rack(&rack)
+        .into_iter()
+        .filter_map(|word| {
+            let tiles = rack.clone().remove_word(word)?;
+            let played: String = tiles.iter().map(ToString::to_string).collect();
+            Some((tile_score(&tiles), played))
+        })
+        .collect();
+    plays.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    println!("\nBest words (blanks in uppercase):");
+    for (score, word) in plays.iter().take(TOP) {
+        println!("{:>4}  {}", score, word);
+    }
+    if plays.is_empty() {
+        println!("      none");
+    }
+
+    let config = SimulationConfig { trials: 100, ..SimulationConfig::default() };
+    println!("\nBest exchanges, with the expected score of the best word after drawing:");
+    for advice in advise_exchange(&dictionary, &rack, &bag, &word_score, &config).iter().take(TOP) {
+        println!("{:>7.2}  exchange {:<7}  keep {}", advice.expected_score, advice.exchange.to_string(), advice.leave);
+    }
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
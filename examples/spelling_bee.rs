@@ -0,0 +1,52 @@
+//! Solves a Spelling Bee puzzle: every word of at least four letters made from the letters of the
+//! puzzle and containing the required letter, with its score, pangrams marked, and the score
+//! needed for every rank.
+//!
+//! ```sh
+//! cargo run --example spelling_bee -- tcaerdn a
+//! cargo run --example spelling_bee -- tcaerdn a --dict tests/golden/lexicons/tiny.txt
+//! ```
+
+use std::env;
+use std::process;
+
+use find_words::puzzle::Puzzle;
+use find_words::Dictionary;
+
+const USAGE: &str = "usage: spelling_bee <letters> <required letter> [--dict <path>]";
+
+/// Minimum length of an answer, as in the newspaper puzzle.
+const MIN_LEN: usize = 4;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (letters, required, path) = match args.as_slice() {
+        [letters, required] => (letters, required, "words.txt"),
+        [letters, required, option, path] if option == "--dict" => (letters, required, path.as_str()),
+        _ => exit(USAGE),
+    };
+    let mut required_letters = required.chars();
+    let required = match (required_letters.next(), required_letters.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => c,
+        _ => exit("the required letter must be a single letter"),
+    };
+    let dictionary = Dictionary::from_file(path).unwrap_or_else(|error| exit(&format!("cannot read {}: {}", path, error)));
+
+    let puzzle = Puzzle::from_dictionary(&dictionary, letters, Some(required), MIN_LEN);
+    let mut answers: Vec<&String> = puzzle.answers().iter().collect();
+    answers.sort_by(|a, b| puzzle.word_score(b).cmp(&puzzle.word_score(a)).then(a.cmp(b)));
+    println!("{} answers, {} points", answers.len(), puzzle.max_score());
+    for answer in answers {
+        let pangram = if puzzle.is_pangram(answer) { "  (pangram)" } else { "" };
+        println!("{:>4}  {}{}", puzzle.word_score(answer), answer, pangram);
+    }
+    println!();
+    for (rank, score) in puzzle.rank_thresholds() {
+        println!("{:>4}  {:?}", score, rank);
+    }
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
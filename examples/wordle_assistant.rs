@@ -0,0 +1,62 @@
+//! Suggests Wordle guesses: give the guesses played so far with their feedback (`g` for green,
+//! `y` for yellow, `b` for grey), and get the remaining candidates and the guesses that narrow
+//! them down the most.
+//!
+//! ```sh
+//! cargo run --example wordle_assistant
+//! cargo run --example wordle_assistant -- crate=yggyg --dict tests/golden/lexicons/tiny.txt
+//! ```
+
+use std::env;
+use std::process;
+
+use find_words::wordle::{parse_feedback, WordleSolver};
+use find_words::Dictionary;
+
+const USAGE: &str = "usage: wordle_assistant [--dict <path>] [--len <letters>] [guess=feedback]...";
+
+/// Number of candidates and guesses shown.
+const TOP: usize = 10;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let (mut path, mut len, mut feedback) = ("words.txt".to_string(), 5, vec![]);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dict" => path = args.next().unwrap_or_else(|| exit(USAGE)),
+            "--len" => len = args.next().and_then(|len| len.parse().ok()).unwrap_or_else(|| exit(USAGE)),
+            _ => match arg.split_once('=') {
+                Some((guess, pattern)) => match parse_feedback(pattern) {
+                    Some(marks) if marks.len() == guess.len() => feedback.push((guess.to_ascii_lowercase(), marks)),
+                    _ => exit(&format!("invalid feedback {:?} for {:?}", pattern, guess)),
+                },
+                None => exit(USAGE),
+            },
+        }
+    }
+    let dictionary = Dictionary::from_file(&path).unwrap_or_else(|error| exit(&format!("cannot read {}: {}", path, error)));
+
+    let mut solver = WordleSolver::new(&dictionary, len);
+    for (guess, marks) in &feedback {
+        solver.apply(guess, marks);
+    }
+    let candidates = solver.candidates();
+    println!("{} candidate{}:", candidates.len(), if candidates.len() == 1 { "" } else { "s" });
+    for candidate in candidates.iter().take(TOP) {
+        println!("  {}", candidate);
+    }
+    if candidates.len() > TOP {
+        println!("  ...");
+    }
+    if candidates.len() > 1 {
+        println!("\nBest guesses, with the bits of information they give:");
+        for (guess, entropy) in solver.rank_guesses(None, TOP) {
+            println!("  {}  {:.2}", guess, entropy);
+        }
+    }
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
@@ -0,0 +1,51 @@
+/* C ABI of find_words, built with `cargo rustc --release --lib --features ffi --crate-type cdylib`.
+ * Strings are NUL-terminated UTF-8. See src/ffi.rs for the details of every function. */
+
+#ifndef FIND_WORDS_H
+#define FIND_WORDS_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Error codes returned by fw_last_error. */
+#define FW_OK 0
+#define FW_NULL_POINTER 1
+#define FW_INVALID_UTF8 2
+#define FW_IO_ERROR 3
+#define FW_MALFORMED_DICTIONARY 4
+#define FW_EMPTY_DICTIONARY 5
+#define FW_PANIC 6
+
+typedef struct FwDictionary FwDictionary;
+
+/* Code of the error of the last call on this thread, FW_OK if it succeeded. */
+int32_t fw_last_error(void);
+
+/* Static description of an error code, not to be freed. */
+const char *fw_error_message(int32_t code);
+
+/* Loads the word list at path, one word per line. Returns NULL on error. */
+FwDictionary *fw_dictionary_load(const char *path);
+
+/* Number of words of the dictionary. */
+size_t fw_dictionary_len(const FwDictionary *dictionary);
+
+/* Frees a dictionary. Does nothing with NULL. */
+void fw_dictionary_free(FwDictionary *dictionary);
+
+/* Words that can be constructed from letters, in dictionary order, *out_len of them, to free with
+ * fw_free_results. Returns NULL when no word is found or on error, which fw_last_error tells apart. */
+char **fw_find(const FwDictionary *dictionary, const char *letters, size_t *out_len);
+
+/* Frees the words returned by fw_find. Does nothing with NULL. */
+void fw_free_results(char **results, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,108 @@
+//! Allocation counting, enabled by the `alloc-count` feature, to check how much loading and
+//! searching allocate, e.g. that [`can_be_constructed`](crate::can_be_constructed) does not.
+//!
+//! [`CountingAllocator`] wraps another global allocator (the system one by default, or any
+//! other such as a bump or arena allocator) and counts what goes through it. It only sees
+//! allocations once installed as the global allocator of the program:
+//!
+//! ```ignore
+//! use find_words::allocation::{count, CountingAllocator};
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator::system();
+//!
+//! let (dictionary, load) = count(|| Dictionary::from_file("words.txt"));
+//! let (words, query) = count(|| dictionary.find("aeinrst"));
+//! println!("{} allocations to load, {} to search", load.allocations, query.allocations);
+//! ```
+//!
+//! Counters are shared by every thread, so allocations made by other threads during a
+//! measurement, including the ones of a parallel search, are counted too.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static DEALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Global allocator counting the allocations made through another allocator.
+#[derive(Debug, Default)]
+pub struct CountingAllocator<A: GlobalAlloc = System> {
+    inner: A,
+}
+
+/// Allocations counted since the program started, or during a measurement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations included.
+    pub allocations: u64,
+    pub deallocations: u64,
+    /// Number of bytes allocated, without subtracting the bytes freed.
+    pub bytes: u64,
+}
+
+impl CountingAllocator<System> {
+    /// Counts the allocations of the system allocator.
+    pub const fn system() -> Self {
+        CountingAllocator { inner: System }
+    }
+}
+
+impl<A: GlobalAlloc> CountingAllocator<A> {
+    /// Counts the allocations of `inner`.
+    pub const fn new(inner: A) -> Self {
+        CountingAllocator { inner }
+    }
+}
+
+// SAFETY: every call is forwarded unchanged to the inner allocator; counting only touches atomics.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_allocation(layout.size());
+        self.inner.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record_allocation(layout.size());
+        self.inner.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        DEALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        self.inner.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_allocation(new_size);
+        self.inner.realloc(ptr, layout, new_size)
+    }
+}
+
+fn record_allocation(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+}
+
+/// Allocations counted since the program started. Always zero unless a [`CountingAllocator`]
+/// is the global allocator.
+pub fn stats() -> AllocStats {
+    AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        deallocations: DEALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+    }
+}
+
+/// Runs `f`, returning its result along with the allocations counted while it ran.
+pub fn count<T, F: FnOnce() -> T>(f: F) -> (T, AllocStats) {
+    let before = stats();
+    let result = f();
+    let after = stats();
+    let counted = AllocStats {
+        allocations: after.allocations - before.allocations,
+        deallocations: after.deallocations - before.deallocations,
+        bytes: after.bytes - before.bytes,
+    };
+    (result, counted)
+}
@@ -0,0 +1,171 @@
+//! Alphabets map letters to the indexes of a [`LetterCounts`](crate::LetterCounts) array.
+//!
+//! Every alphabet is case-insensitive: upper and lower case forms of a letter share an index.
+
+/// Maps the `N` letters of an alphabet to the indexes `0..N` and back.
+pub trait Alphabet<const N: usize> {
+    /// Index of a letter, or `None` if the character is not part of the alphabet.
+    fn index(c: char) -> Option<usize>;
+
+    /// Lowercase letter at an index. Panics if `index >= N`.
+    fn letter(index: usize) -> char;
+}
+
+/// The 26 letters of the basic Latin alphabet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct English;
+
+/// The 26 letters of the basic Latin alphabet, Latin letters with diacritics counting as the
+/// letter they are written on, see [`fold_diacritic`]: "élève" has three `e`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Folded;
+
+/// The 24 letters of the modern Greek alphabet. Final sigma is counted as sigma.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Greek;
+
+/// The 33 letters of the Russian Cyrillic alphabet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cyrillic;
+
+impl Alphabet<26> for English {
+    fn index(c: char) -> Option<usize> {
+        if c.is_ascii_alphabetic() { Some((c.to_ascii_lowercase() as u8 - b'a') as usize) } else { None }
+    }
+
+    fn letter(index: usize) -> char {
+        assert!(index < 26, "index {} out of range for English", index);
+        (b'a' + index as u8) as char
+    }
+}
+
+impl Alphabet<26> for Folded {
+    fn index(c: char) -> Option<usize> {
+        English::index(fold_diacritic(c))
+    }
+
+    fn letter(index: usize) -> char {
+        English::letter(index)
+    }
+}
+
+impl Alphabet<24> for Greek {
+    fn index(c: char) -> Option<usize> {
+        match to_lowercase(c) {
+            'ς' => Some(17),
+            c @ 'α'..='ρ' => Some(c as usize - 'α' as usize),
+            c @ 'σ'..='ω' => Some(c as usize - 'σ' as usize + 17),
+            _ => None,
+        }
+    }
+
+    fn letter(index: usize) -> char {
+        match index {
+            0..=16 => char::from_u32('α' as u32 + index as u32).unwrap(),
+            17..=23 => char::from_u32('σ' as u32 + index as u32 - 17).unwrap(),
+            _ => panic!("index {} out of range for Greek", index),
+        }
+    }
+}
+
+impl Alphabet<33> for Cyrillic {
+    fn index(c: char) -> Option<usize> {
+        match to_lowercase(c) {
+            c @ 'а'..='е' => Some(c as usize - 'а' as usize),
+            'ё' => Some(6),
+            c @ 'ж'..='я' => Some(c as usize - 'ж' as usize + 7),
+            _ => None,
+        }
+    }
+
+    fn letter(index: usize) -> char {
+        match index {
+            0..=5 => char::from_u32('а' as u32 + index as u32).unwrap(),
+            6 => 'ё',
+            7..=32 => char::from_u32('ж' as u32 + index as u32 - 7).unwrap(),
+            _ => panic!("index {} out of range for Cyrillic", index),
+        }
+    }
+}
+
+/// The basic Latin letter, in lowercase, that a Latin letter with diacritics (from the Latin-1
+/// and Latin Extended-A blocks) is written on, e.g. `e` for `É`. Other characters are returned
+/// unchanged, including ligatures such as `æ` and `ß`, which stand for two letters.
+pub fn fold_diacritic(c: char) -> char {
+    match to_lowercase(c) {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ĥ' | 'ħ' => 'h',
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'ĵ' => 'j',
+        'ķ' => 'k',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'ś' | 'ŝ' | 'ş' | 'š' => 's',
+        'ţ' | 'ť' | 'ŧ' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ŵ' => 'w',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        _ => c,
+    }
+}
+
+fn to_lowercase(c: char) -> char {
+    let mut lowercase = c.to_lowercase();
+    match (lowercase.next(), lowercase.next()) {
+        (Some(lower), None) => lower,
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod alphabet_tests {
+    use super::{fold_diacritic, Alphabet, Cyrillic, English, Folded, Greek};
+
+    fn round_trips<A: Alphabet<N>, const N: usize>() -> bool {
+        (0..N).all(|index| A::index(A::letter(index)) == Some(index))
+    }
+
+    #[test]
+    fn english() {
+        assert!(round_trips::<English, 26>());
+        assert_eq!(English::index('a'), Some(0));
+        assert_eq!(English::index('Z'), Some(25));
+        assert_eq!(English::index('é'), None);
+        assert_eq!(English::index('1'), None);
+    }
+
+    #[test]
+    fn folded() {
+        assert!(round_trips::<Folded, 26>());
+        assert_eq!(Folded::index('é'), Some(4));
+        assert_eq!(Folded::index('Ç'), Some(2));
+        assert_eq!(Folded::index('Q'), Some(16));
+        assert_eq!(Folded::index('æ'), None);
+        assert_eq!((fold_diacritic('Ø'), fold_diacritic('ß'), fold_diacritic('-')), ('o', 'ß', '-'));
+    }
+
+    #[test]
+    fn greek() {
+        assert!(round_trips::<Greek, 24>());
+        assert_eq!(Greek::index('α'), Some(0));
+        assert_eq!(Greek::index('Ω'), Some(23));
+        assert_eq!(Greek::index('ς'), Greek::index('σ'));
+        assert_eq!(Greek::index('a'), None);
+    }
+
+    #[test]
+    fn cyrillic() {
+        assert!(round_trips::<Cyrillic, 33>());
+        assert_eq!(Cyrillic::index('а'), Some(0));
+        assert_eq!(Cyrillic::index('Ё'), Some(6));
+        assert_eq!(Cyrillic::index('Я'), Some(32));
+        assert_eq!(Cyrillic::index('a'), None);
+    }
+}
@@ -0,0 +1,158 @@
+//! Standard workloads that can be timed from inside an application, enabled by the `bench`
+//! feature.
+//!
+//! The criterion benchmarks of this crate only measure it built with their own settings.
+//! Applications that use another allocator, another number of threads or their own word list
+//! can run the same workloads with these functions and get the timings back as [`Timing`]s.
+
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::corpus::RackCase;
+use crate::{get_constructable_words, get_letters_count, random_string, Dictionary, Word};
+
+/// How a workload is run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Number of timed runs of the workload.
+    pub iterations: u32,
+    /// Number of untimed runs before the timed ones.
+    pub warmup: u32,
+    /// Length of the random lists of letters.
+    pub list_len: u8,
+    /// Seed of the random lists of letters, so that runs can be compared.
+    pub seed: u64,
+    /// Number of threads of the pool the workload runs in, `None` for the global pool.
+    pub threads: Option<usize>,
+}
+
+/// Timings of the runs of a workload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timing {
+    pub workload: String,
+    /// Duration of every timed run, in order.
+    pub runs: Vec<Duration>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig { iterations: 100, warmup: 10, list_len: 12, seed: 0, threads: None }
+    }
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.runs.iter().sum()
+    }
+
+    /// Average duration of a run, zero without runs.
+    pub fn mean(&self) -> Duration {
+        if self.runs.is_empty() { return Duration::ZERO; }
+        self.total() / self.runs.len() as u32
+    }
+
+    pub fn min(&self) -> Duration {
+        self.runs.iter().copied().min().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.runs.iter().copied().max().unwrap_or_default()
+    }
+}
+
+/// Times [`Dictionary::find`] on a new random list of letters every run.
+pub fn dictionary_find(dictionary: &Dictionary, config: &BenchConfig) -> Timing {
+    let lists = random_lists(config);
+    let mut lists = lists.iter().cycle();
+    time("dictionary find", config, || dictionary.find(lists.next().unwrap()).len())
+}
+
+/// Times [`get_constructable_words`] on a new random list of letters every run. Copying the
+/// words, which the function consumes, is part of the timing.
+pub fn linear_find(words: &[Word], config: &BenchConfig) -> Timing {
+    let lists: Vec<_> = random_lists(config).iter().map(|list| get_letters_count(list)).collect();
+    let mut lists = lists.iter().cycle();
+    time("linear find", config, || get_constructable_words(words.to_vec(), lists.next().unwrap()).len())
+}
+
+/// Times [`Dictionary::find`] on every rack of a corpus, each run searching the whole corpus.
+pub fn corpus_find(dictionary: &Dictionary, cases: &[RackCase], config: &BenchConfig) -> Timing {
+    time("corpus find", config, || cases.iter().map(|case| dictionary.find(&case.rack).len()).sum())
+}
+
+/// Runs the dictionary and linear workloads on the words of `dictionary`.
+pub fn run_standard(dictionary: &Dictionary, config: &BenchConfig) -> Vec<Timing> {
+    vec![dictionary_find(dictionary, config), linear_find(dictionary.words(), config)]
+}
+
+/// One random list of letters per run, warmup included.
+fn random_lists(config: &BenchConfig) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    (0..(config.warmup + config.iterations).max(1)).map(|_| random_string(&mut rng, config.list_len)).collect()
+}
+
+/// Runs the workload in the configured pool, returning the duration of every timed run. The
+/// workload returns the number of words it found so that the search cannot be optimized away.
+fn time<F: FnMut() -> usize + Send>(workload: &str, config: &BenchConfig, mut run: F) -> Timing {
+    let mut measure = || {
+        for _ in 0..config.warmup {
+            std::hint::black_box(run());
+        }
+        (0..config.iterations)
+            .map(|_| {
+                let start = Instant::now();
+                std::hint::black_box(run());
+                start.elapsed()
+            })
+            .collect()
+    };
+    let runs = match config.threads {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("cannot start the thread pool")
+            .install(measure),
+        None => measure(),
+    };
+    Timing { workload: workload.to_string(), runs }
+}
+
+#[cfg(test)]
+mod bench_tests {
+    use std::time::Duration;
+
+    use crate::corpus::RackCase;
+    use crate::{test_words, Dictionary};
+
+    use super::{corpus_find, run_standard, BenchConfig, Timing};
+
+    fn config() -> BenchConfig {
+        BenchConfig { iterations: 5, warmup: 1, threads: Some(2), ..BenchConfig::default() }
+    }
+
+    #[test]
+    fn standard_workloads() {
+        let dictionary: Dictionary = test_words(&["a", "at", "cat", "act", "tact", "dog"]).into();
+        let timings = run_standard(&dictionary, &config());
+        let workloads: Vec<&str> = timings.iter().map(|timing| timing.workload.as_str()).collect();
+        assert_eq!(workloads, vec!["dictionary find", "linear find"]);
+        assert!(timings.iter().all(|timing| timing.runs.len() == 5));
+    }
+
+    #[test]
+    fn corpus_workload() {
+        let dictionary: Dictionary = test_words(&["a", "at", "cat"]).into();
+        let cases = vec![RackCase { rack: "tca".to_string(), solutions: 3 }];
+        assert_eq!(corpus_find(&dictionary, &cases, &config()).runs.len(), 5);
+    }
+
+    #[test]
+    fn statistics() {
+        let timing = Timing { workload: "test".to_string(), runs: vec![Duration::from_millis(1), Duration::from_millis(3)] };
+        assert_eq!((timing.total(), timing.mean()), (Duration::from_millis(4), Duration::from_millis(2)));
+        assert_eq!((timing.min(), timing.max()), (Duration::from_millis(1), Duration::from_millis(3)));
+        assert_eq!(Timing { workload: "test".to_string(), runs: vec![] }.mean(), Duration::ZERO);
+    }
+}
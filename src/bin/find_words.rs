@@ -1,12 +1,617 @@
-use find_words::{generate_random_string, get_constructable_words, get_letters_count, read_words_from_file};
-
-fn main() -> Result<(), std::io::Error> {
-    let words = read_words_from_file("words.txt")?;
-    let list = generate_random_string(Some(20));
-    println!("List of letters: {}", list);
-    println!("Words that can be constructed");
-    for word  in get_constructable_words(words, &get_letters_count(&list)) {
-        println!("{}", word)
-    }
-    Ok(())
-}
\ No newline at end of file
+use std::cmp::Reverse;
+use std::io::BufRead;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+use std::{env, fmt, io, process};
+
+use find_words::alphabet::English;
+use find_words::families;
+use find_words::letters::{parse_letters_with_report, CharPolicy, ParsePolicy, ParseReport};
+use find_words::load::read_words_from_file_with_warnings;
+use find_words::matches::json_string;
+use find_words::pattern::{Pattern, PatternError};
+use find_words::phrase::{find_phrases, PhraseOptions};
+use find_words::rack::word_score;
+use find_words::scoring::{Scorer, Scrabble};
+use find_words::soak::{run_soak, SoakConfig, SoakReport};
+use find_words::{get_constructable_words, get_constructable_words_min_len, get_letters_count, letter_count, read_words_from_file, Dictionary, Grouping, Matches, ParseError, Rack, RackSpec, Word, DEFAULT_MIN_WORD_LEN};
+
+const USAGE: &str = "usage: find_words [--dict PATH] [--letters LETTERS | --random LEN] [--min-len N] [--max-len N] \
+[--sort score|length|alpha] [--pattern PATTERN] [--output plain|json|csv] [--errors text|json] [--families csv|json] [--strip] [LETTERS]
+       find_words doctor [--dict PATH] [--errors text|json]
+       find_words repl [--dict PATH] [--min-len N] [--max-len N] [--sort score|length|alpha] [--output plain|json|csv] [--strip]
+       find_words anagram [--dict PATH] [--min-len N] [--max-words N] [--timeout DURATION] PHRASE
+       find_words soak [--dict PATH] [--qps N] [--duration DURATION] [--threads N] [--reload-every DURATION]";
+
+/// Commands of the REPL, besides lists of letters.
+const REPL_HELP: &str = ":minlen N, :maxlen N, :sort dict|score|length|alpha, :dict PATH, :help, :quit";
+
+/// Word list read when `--dict` is not given, without the `embedded-dict` feature.
+const DEFAULT_DICTIONARY: &str = "words.txt";
+
+/// Length of the random list of letters searched when none is given.
+const DEFAULT_RANDOM_LEN: u8 = 20;
+
+/// Letters of the query `doctor` runs, common enough to find words in any English list.
+const REFERENCE_LETTERS: &str = "etaoinshrdlu";
+
+/// Exit codes, so that scripts can tell whether the list of letters had any solutions.
+const EXIT_FOUND: i32 = 0;
+const EXIT_NOT_FOUND: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_IO: i32 = 3;
+const EXIT_CHECK_FAILED: i32 = 4;
+
+/// What the program does.
+#[derive(Clone, Copy, PartialEq)]
+enum Command {
+    /// Searches one list of letters, or exports the anagram families.
+    Search,
+    /// Checks the dictionary and the search.
+    Doctor,
+    /// Loads the dictionary once, then searches the lists of letters read from stdin.
+    Repl,
+    /// Prints the phrases using every letter of a phrase as they are found.
+    Anagram,
+    /// Searches generated racks at a steady rate, then reports the latencies and memory use.
+    Soak,
+}
+
+/// How failures are reported on stderr.
+#[derive(Clone, Copy, PartialEq)]
+enum ErrorFormat {
+    Text,
+    Json,
+}
+
+/// Format of the anagram families export.
+#[derive(Clone, Copy, PartialEq)]
+enum ExportFormat {
+    Csv,
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+/// Order of the words found.
+#[derive(Clone, Copy, PartialEq)]
+enum SortKey {
+    /// The order of the dictionary.
+    Dictionary,
+    /// Highest English tile score first, in dictionary order among equal scores.
+    Score,
+    /// Most letters first, alphabetically among words of the same length.
+    Length,
+    Alpha,
+}
+
+/// Format of the words found.
+#[derive(Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Plain,
+    Json,
+    /// A `value,length,score,letters_used` record per word.
+    Csv,
+}
+
+struct Options {
+    command: Command,
+    /// Word list to read, `words.txt` (or the built-in list with the `embedded-dict` feature)
+    /// if `None`.
+    dictionary: Option<String>,
+    errors: ErrorFormat,
+    /// Export every anagram family of the dictionary instead of searching a list of letters.
+    families: Option<ExportFormat>,
+    /// Drop digits and symbols from the list of letters instead of rejecting it.
+    strip: bool,
+    list: Option<String>,
+    /// Length of the random list searched when no list is given.
+    random_len: u8,
+    min_len: usize,
+    max_len: usize,
+    sort: SortKey,
+    /// Board pattern the words must fit, e.g. `c_t`, its empty squares filled from the list of
+    /// letters.
+    pattern: Option<String>,
+    output: OutputFormat,
+    /// Most words in a phrase found by `anagram`.
+    max_words: usize,
+    /// Time after which `anagram` stops searching.
+    timeout: Option<Duration>,
+    /// Load of `soak`.
+    soak: SoakConfig,
+}
+
+enum CliError {
+    Usage(String),
+    Dictionary { path: String, error: io::Error },
+    /// The lists of letters of `repl` cannot be read from stdin.
+    Input(io::Error),
+    InvalidLetters(ParseError),
+    InvalidPattern(PatternError),
+    /// A check of `doctor` failed.
+    CheckFailed(String),
+}
+
+impl CliError {
+    /// Stable identifier of the kind of failure, for scripts parsing JSON errors.
+    fn code(&self) -> &'static str {
+        match self {
+            CliError::Usage(_) => "usage",
+            CliError::Dictionary { .. } => "dictionary_unreadable",
+            CliError::Input(_) => "input_unreadable",
+            CliError::InvalidLetters(_) => "invalid_letters",
+            CliError::InvalidPattern(_) => "invalid_pattern",
+            CliError::CheckFailed(_) => "check_failed",
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) | CliError::InvalidLetters(_) | CliError::InvalidPattern(_) => EXIT_USAGE,
+            CliError::Dictionary { .. } | CliError::Input(_) => EXIT_IO,
+            CliError::CheckFailed(_) => EXIT_CHECK_FAILED,
+        }
+    }
+
+    fn report(&self, format: ErrorFormat) {
+        match format {
+            ErrorFormat::Text => eprintln!("{}", self),
+            ErrorFormat::Json => eprintln!(
+                "{{\"error\":{{\"code\":\"{}\",\"message\":{}}}}}",
+                self.code(),
+                json_string(&self.to_string()),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{}\n{}", message, USAGE),
+            CliError::Dictionary { path, error } => write!(f, "Cannot read dictionary {}: {}", path, error),
+            CliError::Input(error) => write!(f, "Cannot read input: {}", error),
+            CliError::InvalidLetters(error) => write!(f, "Invalid list of letters: {}", error),
+            CliError::InvalidPattern(error) => write!(f, "Invalid pattern: {}", error),
+            CliError::CheckFailed(message) => write!(f, "Check failed: {}", message),
+        }
+    }
+}
+
+/// Parses a duration such as `2s`, `1.5s`, `500ms`, `10m` or `1h`, in seconds without a unit.
+fn parse_duration(option: &str, value: Option<String>) -> Result<Duration, CliError> {
+    let error = || CliError::Usage(format!("{} requires a duration such as 2s, 500ms or 10m", option));
+    let value = value.ok_or_else(error)?;
+    let (number, scale) = match value.strip_suffix("ms") {
+        Some(millis) => (millis, 0.001),
+        None => match value.strip_suffix('m') {
+            Some(minutes) => (minutes, 60.0),
+            None => match value.strip_suffix('h') {
+                Some(hours) => (hours, 3600.0),
+                None => (value.strip_suffix('s').unwrap_or(&value), 1.0),
+            },
+        },
+    };
+    number.parse::<f64>().ok()
+        .and_then(|number| Duration::try_from_secs_f64(number * scale).ok())
+        .ok_or_else(error)
+}
+
+/// Parses the value of a numeric option.
+fn parse_number<T: std::str::FromStr>(option: &str, value: Option<String>) -> Result<T, CliError> {
+    value.as_deref()
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| CliError::Usage(format!("{} requires a number", option)))
+}
+
+fn parse_args<I: Iterator<Item=String>>(args: I) -> (Options, Result<(), CliError>) {
+    let mut args = args.peekable();
+    let mut options = Options {
+        command: match args.next_if(|arg| ["doctor", "repl", "anagram", "soak"].contains(&arg.as_str())).as_deref() {
+            Some("doctor") => Command::Doctor,
+            Some("repl") => Command::Repl,
+            Some("anagram") => Command::Anagram,
+            Some(_) => Command::Soak,
+            None => Command::Search,
+        },
+        dictionary: None,
+        errors: ErrorFormat::Text,
+        families: None,
+        strip: false,
+        list: None,
+        random_len: DEFAULT_RANDOM_LEN,
+        min_len: DEFAULT_MIN_WORD_LEN,
+        max_len: usize::MAX,
+        sort: SortKey::Dictionary,
+        pattern: None,
+        output: OutputFormat::Plain,
+        max_words: PhraseOptions::default().max_words,
+        timeout: None,
+        soak: SoakConfig::default(),
+    };
+    let mut random = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dictionary" | "--dict" => match args.next() {
+                Some(path) => options.dictionary = Some(path),
+                None => return (options, Err(CliError::Usage(format!("{} requires a path", arg)))),
+            },
+            "--letters" => match args.next() {
+                Some(list) if options.list.is_none() => options.list = Some(list),
+                Some(_) => return (options, Err(CliError::Usage("only one list of letters can be given".to_string()))),
+                None => return (options, Err(CliError::Usage("--letters requires a list of letters".to_string()))),
+            },
+            "--random" => match parse_number(&arg, args.next()) {
+                Ok(len) => (options.random_len, random) = (len, true),
+                Err(error) => return (options, Err(error)),
+            },
+            "--min-len" => match parse_number(&arg, args.next()) {
+                Ok(len) => options.min_len = len,
+                Err(error) => return (options, Err(error)),
+            },
+            "--max-len" => match parse_number(&arg, args.next()) {
+                Ok(len) => options.max_len = len,
+                Err(error) => return (options, Err(error)),
+            },
+            "--max-words" => match parse_number(&arg, args.next()) {
+                Ok(count) => options.max_words = count,
+                Err(error) => return (options, Err(error)),
+            },
+            "--timeout" => match parse_duration(&arg, args.next()) {
+                Ok(timeout) => options.timeout = Some(timeout),
+                Err(error) => return (options, Err(error)),
+            },
+            "--qps" => match parse_number(&arg, args.next()) {
+                Ok(qps) if qps > 0 => options.soak.qps = qps,
+                Ok(_) => return (options, Err(CliError::Usage("--qps must be at least 1".to_string()))),
+                Err(error) => return (options, Err(error)),
+            },
+            "--duration" => match parse_duration(&arg, args.next()) {
+                Ok(duration) => options.soak.duration = duration,
+                Err(error) => return (options, Err(error)),
+            },
+            "--threads" => match parse_number(&arg, args.next()) {
+                Ok(threads) => options.soak.threads = threads,
+                Err(error) => return (options, Err(error)),
+            },
+            "--reload-every" => match parse_duration(&arg, args.next()) {
+                Ok(every) => options.soak.reload_every = Some(every),
+                Err(error) => return (options, Err(error)),
+            },
+            "--sort" => match args.next().as_deref() {
+                Some("score") => options.sort = SortKey::Score,
+                Some("length") => options.sort = SortKey::Length,
+                Some("alpha") => options.sort = SortKey::Alpha,
+                _ => return (options, Err(CliError::Usage("--sort must be score, length or alpha".to_string()))),
+            },
+            "--pattern" => match args.next() {
+                Some(pattern) => options.pattern = Some(pattern),
+                None => return (options, Err(CliError::Usage("--pattern requires a pattern such as c_t".to_string()))),
+            },
+            "--output" => match args.next().as_deref() {
+                Some("plain") => options.output = OutputFormat::Plain,
+                Some("json") => options.output = OutputFormat::Json,
+                Some("csv") => options.output = OutputFormat::Csv,
+                _ => return (options, Err(CliError::Usage("--output must be plain, json or csv".to_string()))),
+            },
+            "--errors" => match args.next().as_deref() {
+                Some("text") => options.errors = ErrorFormat::Text,
+                Some("json") => options.errors = ErrorFormat::Json,
+                _ => return (options, Err(CliError::Usage("--errors must be text or json".to_string()))),
+            },
+            "--families" => match args.next().as_deref() {
+                Some("csv") => options.families = Some(ExportFormat::Csv),
+                #[cfg(feature = "serde")]
+                Some("json") => options.families = Some(ExportFormat::Json),
+                #[cfg(not(feature = "serde"))]
+                Some("json") => return (options, Err(CliError::Usage("--families json requires the serde feature".to_string()))),
+                _ => return (options, Err(CliError::Usage("--families must be csv or json".to_string()))),
+            },
+            "--strip" => options.strip = true,
+            _ if arg.starts_with("--") => return (options, Err(CliError::Usage(format!("unknown option {}", arg)))),
+            _ if options.list.is_none() => options.list = Some(arg),
+            _ => return (options, Err(CliError::Usage(format!("unexpected argument {}", arg)))),
+        }
+    }
+    if matches!(options.command, Command::Doctor | Command::Repl | Command::Soak) && (random || options.list.is_some() || options.families.is_some()) {
+        return (options, Err(CliError::Usage("doctor, repl and soak do not take a list of letters".to_string())));
+    }
+    if options.command == Command::Anagram && (random || options.list.is_none() || options.families.is_some()) {
+        return (options, Err(CliError::Usage("anagram requires a phrase".to_string())));
+    }
+    if random && options.list.is_some() {
+        return (options, Err(CliError::Usage("--random cannot be used with a list of letters".to_string())));
+    }
+    if options.min_len > options.max_len {
+        return (options, Err(CliError::Usage("--min-len cannot be greater than --max-len".to_string())));
+    }
+    (options, Ok(()))
+}
+
+/// Returns the number of words found.
+fn run(options: &Options) -> Result<usize, CliError> {
+    match options.command {
+        Command::Search => {}
+        Command::Doctor => return doctor(options),
+        Command::Repl => return repl(options),
+        Command::Anagram => return anagram(options),
+        Command::Soak => return soak(options),
+    }
+    let mut words = load_words(options)?;
+    if let Some(format) = options.families {
+        return Ok(export_families(words, format));
+    }
+    let list = options.list.clone().unwrap_or_else(|| Rack::random(RackSpec::letters(options.random_len), &mut rand::thread_rng()).to_string());
+    let report = parse_letters_with_report(&list, &parse_policy(options.strip)).map_err(CliError::InvalidLetters)?;
+    let pattern: Option<Pattern> = options.pattern.as_deref().map(str::parse).transpose().map_err(CliError::InvalidPattern)?;
+    words.retain(|word| (options.min_len..=options.max_len).contains(&letter_count(&word.value)));
+    let matches = Matches::new(match pattern {
+        Some(pattern) => search_pattern(words, &report, &pattern, options.sort),
+        None => search(words, &report, options.min_len, options.sort),
+    });
+    match options.output {
+        OutputFormat::Plain => {
+            println!("List of letters: {}", list);
+            print_normalization(&report);
+            println!("Words that can be constructed");
+            print!("{}", matches.to_plaintext(Grouping::None));
+        }
+        OutputFormat::Json => println!("{}", matches.to_json_with_letters(&list)),
+        OutputFormat::Csv => print!("{}", matches.to_csv()),
+    }
+    Ok(matches.len())
+}
+
+/// The words of `--dict`, or of the default word list.
+fn load_words(options: &Options) -> Result<Vec<Word>, CliError> {
+    #[cfg(feature = "embedded-dict")]
+    if options.dictionary.is_none() { return Ok(find_words::builtin_words()); }
+    let path = options.dictionary.as_deref().unwrap_or(DEFAULT_DICTIONARY);
+    read_words_from_file(path).map_err(|error| CliError::Dictionary { path: path.to_string(), error })
+}
+
+/// Loads the dictionary, its data-quality warnings with it, reports what it holds and checks that an indexed search finds the same
+/// words as a scan of every word. Returns the number of words of the dictionary.
+fn doctor(options: &Options) -> Result<usize, CliError> {
+    println!("Dictionary: {}", options.dictionary.as_deref().unwrap_or(if cfg!(feature = "embedded-dict") { "built-in" } else { DEFAULT_DICTIONARY }));
+    #[cfg(feature = "embedded-dict")]
+    let path = options.dictionary.as_deref();
+    #[cfg(not(feature = "embedded-dict"))]
+    let path = Some(options.dictionary.as_deref().unwrap_or(DEFAULT_DICTIONARY));
+    let start = Instant::now();
+    let (words, warnings) = match path {
+        Some(path) => {
+            let loaded = read_words_from_file_with_warnings(path).map_err(|error| CliError::Dictionary { path: path.to_string(), error })?;
+            (loaded.words, Some(loaded.warnings))
+        }
+        None => (load_words(options)?, None),
+    };
+    println!("Loaded {} words in {}ms", words.len(), start.elapsed().as_millis());
+    if words.is_empty() { return Err(CliError::CheckFailed("the dictionary has no words".to_string())); }
+    if let Some(warnings) = warnings {
+        match warnings.first() {
+            Some(first) => println!("Data-quality warnings: {} (first: {})", warnings.len(), first),
+            None => println!("Data-quality warnings: none"),
+        }
+    }
+
+    let start = Instant::now();
+    let dictionary = Dictionary::new(words.clone());
+    dictionary.build_indexes();
+    println!("Indexed in {}ms, fingerprint {:016x}", start.elapsed().as_millis(), dictionary.fingerprint().0);
+    let lengths = words.iter().map(|word| letter_count(&word.value));
+    println!("Word lengths: {} to {} letters", lengths.clone().min().unwrap_or(0), lengths.max().unwrap_or(0));
+
+    let (found, trace) = dictionary.find_with_trace(REFERENCE_LETTERS);
+    println!("Reference query {:?}: {}", REFERENCE_LETTERS, trace);
+    let expected = get_constructable_words(words, &get_letters_count(REFERENCE_LETTERS));
+    if found != expected {
+        return Err(CliError::CheckFailed(format!("the reference query found {} words, a full scan {}", found.len(), expected.len())));
+    }
+    println!("OK: the reference query found the {} words of a full scan", expected.len());
+    Ok(dictionary.len())
+}
+
+/// Searches every list of letters read from stdin, one per line, in the dictionary loaded once, and
+/// runs the commands of [`REPL_HELP`]. Errors are reported on stderr without stopping. Returns the
+/// number of lines searched.
+fn repl(options: &Options) -> Result<usize, CliError> {
+    let mut dictionary = Dictionary::new(load_words(options)?);
+    let (mut min_len, mut max_len, mut sort) = (options.min_len, options.max_len, options.sort);
+    let mut searched = 0;
+    for line in io::stdin().lock().lines() {
+        let line = line.map_err(CliError::Input)?;
+        let line = line.trim();
+        let (command, argument) = line.split_once(char::is_whitespace).map_or((line, ""), |(command, argument)| (command, argument.trim()));
+        let outcome = match command {
+            "" => Ok(()),
+            ":quit" | ":q" => break,
+            ":help" => {
+                println!("{}", REPL_HELP);
+                Ok(())
+            }
+            ":minlen" => argument.parse().map(|len| min_len = len).map_err(|_| ":minlen requires a number".to_string()),
+            ":maxlen" => argument.parse().map(|len| max_len = len).map_err(|_| ":maxlen requires a number".to_string()),
+            ":sort" => {
+                let key = match argument {
+                    "dict" => Some(SortKey::Dictionary),
+                    "score" => Some(SortKey::Score),
+                    "length" => Some(SortKey::Length),
+                    "alpha" => Some(SortKey::Alpha),
+                    _ => None,
+                };
+                key.map(|key| sort = key).ok_or_else(|| ":sort must be dict, score, length or alpha".to_string())
+            }
+            ":dict" => read_words_from_file(argument)
+                .map(|words| {
+                    dictionary = Dictionary::new(words);
+                    println!("Loaded {} words from {}", dictionary.len(), argument);
+                })
+                .map_err(|error| CliError::Dictionary { path: argument.to_string(), error }.to_string()),
+            _ if command.starts_with(':') => Err(format!("unknown command {}, expected one of {}", command, REPL_HELP)),
+            _ => {
+                searched += 1;
+                parse_letters_with_report(line, &parse_policy(options.strip)).map(|report| {
+                    let mut found = dictionary.view().lengths(min_len..=max_len).find_counts(&report.counts);
+                    sort_words(&mut found, sort);
+                    let matches = Matches::new(found.into_iter().map(str::to_string).collect());
+                    match options.output {
+                        OutputFormat::Plain => print!("{}", matches.to_plaintext(Grouping::None)),
+                        OutputFormat::Json => println!("{}", matches.to_json_with_letters(line)),
+                        OutputFormat::Csv => print!("{}", matches.to_csv()),
+                    }
+                }).map_err(|error| CliError::InvalidLetters(error).to_string())
+            }
+        };
+        if let Err(message) = outcome {
+            eprintln!("{}", message);
+        }
+    }
+    Ok(searched)
+}
+
+/// Prints the phrases of the dictionary using every letter of the phrase, one per line as they
+/// are found, then how much of the search space was covered, before the deadline of
+/// `--timeout`. Returns the number of phrases found.
+fn anagram(options: &Options) -> Result<usize, CliError> {
+    let phrase = options.list.as_deref().expect("anagram requires a phrase");
+    let dictionary = Dictionary::new(load_words(options)?);
+    let view = dictionary.view().lengths(options.min_len..=options.max_len);
+    let phrase_options = PhraseOptions { max_words: options.max_words, timeout: options.timeout };
+    let coverage = find_phrases(&view, phrase, &phrase_options, |words| {
+        println!("{}", words.join(" "));
+        ControlFlow::Continue(())
+    });
+    let status = if coverage.timed_out { "stopped at the deadline" } else { "complete" };
+    eprintln!(
+        "Found {} phrases in {}ms, {}: searched {} of {} first words ({:.0}%), {} partial phrases",
+        coverage.phrases,
+        coverage.elapsed.as_millis(),
+        status,
+        coverage.first_words_searched,
+        coverage.first_words,
+        coverage.fraction() * 100.0,
+        coverage.nodes,
+    );
+    Ok(coverage.phrases)
+}
+
+/// Searches generated racks in the dictionary at the rate of `--qps` for `--duration`, then
+/// prints the latency percentiles and the growth of the memory. Returns the number of searches.
+fn soak(options: &Options) -> Result<usize, CliError> {
+    let config = &options.soak;
+    let dictionary = Dictionary::new(load_words(options)?);
+    println!(
+        "Soak: {} searches per second for {:.1}s on {} thread{}, {} racks of {} tiles",
+        config.qps,
+        config.duration.as_secs_f64(),
+        config.threads,
+        if config.threads == 1 { "" } else { "s" },
+        config.rack_pool,
+        config.rack_len,
+    );
+    let report = run_soak(dictionary, config);
+    println!(
+        "Ran {} searches in {:.1}s, {:.1} per second, {} from the caches, {} reloads",
+        report.queries(),
+        report.elapsed.as_secs_f64(),
+        report.achieved_qps(),
+        report.cache_hits,
+        report.reloads,
+    );
+    let latency = |percentile| format!("{:.3}ms", report.percentile(percentile).as_secs_f64() * 1000.0);
+    println!("Latency: p50 {}, p90 {}, p99 {}, p99.9 {}, max {}", latency(50.0), latency(90.0), latency(99.0), latency(99.9), latency(100.0));
+    print_memory(&report);
+    Ok(report.queries())
+}
+
+/// Prints the resident memory of a soak test, if it could be read.
+fn print_memory(report: &SoakReport) {
+    let (Some(start), Some(end), Some(peak), Some(growth)) = (report.memory_start, report.memory_end, report.memory_peak, report.memory_growth()) else {
+        println!("Memory: not available on this system");
+        return;
+    };
+    let mib = |bytes: f64| bytes / (1024.0 * 1024.0);
+    println!(
+        "Memory: {:.1} MiB at the start, {:.1} MiB at the end ({:+.1} MiB), {:.1} MiB at the peak",
+        mib(start as f64),
+        mib(end as f64),
+        mib(growth as f64),
+        mib(peak as f64),
+    );
+}
+
+/// How the list of letters is parsed, dropping digits and symbols with `--strip`.
+fn parse_policy(strip: bool) -> ParsePolicy {
+    if strip {
+        ParsePolicy { digits: CharPolicy::Strip, other: CharPolicy::Strip, ..ParsePolicy::default() }
+    } else {
+        ParsePolicy::default()
+    }
+}
+
+/// Sorts words found in dictionary order in `sort` order.
+fn sort_words<W: AsRef<str>>(found: &mut [W], sort: SortKey) {
+    match sort {
+        SortKey::Dictionary => {}
+        SortKey::Score => found.sort_by_key(|word| Reverse(Scrabble.score(word.as_ref()))),
+        SortKey::Length => found.sort_by(|a, b| letter_count(b.as_ref()).cmp(&letter_count(a.as_ref())).then(a.as_ref().cmp(b.as_ref()))),
+        SortKey::Alpha => found.sort_by(|a, b| a.as_ref().cmp(b.as_ref())),
+    }
+}
+
+/// The words fitting `pattern` with the parsed letters on its empty squares, in `sort` order.
+fn search_pattern(words: Vec<Word>, report: &ParseReport, pattern: &Pattern, sort: SortKey) -> Vec<String> {
+    let mut found: Vec<String> = words.into_iter()
+        .filter(|word| pattern.matches(&word.value, &report.counts))
+        .map(|word| word.value)
+        .collect();
+    sort_words(&mut found, sort);
+    found
+}
+
+/// The words of at least `min_len` letters that can be constructed from the parsed letters, in
+/// `sort` order.
+fn search(words: Vec<Word>, report: &ParseReport, min_len: usize, sort: SortKey) -> Vec<String> {
+    let letters = report.counts.to_map::<English>();
+    let mut found = get_constructable_words_min_len(words, &letters, min_len);
+    sort_words(&mut found, sort);
+    found
+}
+
+/// Tells which characters were stripped from the list and which letters were searched, if any
+/// character other than whitespace was stripped.
+fn print_normalization(report: &ParseReport) {
+    let removed: Vec<String> = report.removed.iter()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(position, c)| format!("{:?} at position {}", c, position))
+        .collect();
+    if removed.is_empty() { return; }
+    println!("Removed {} character{}: {}", removed.len(), if removed.len() == 1 { "" } else { "s" }, removed.join(", "));
+    println!("Letters used: {}", report.normalized);
+}
+
+/// Prints every anagram family of the words, scored with English tile values, and returns the
+/// number of families.
+fn export_families(words: Vec<Word>, format: ExportFormat) -> usize {
+    let families = families::families(&words.into(), &word_score);
+    match format {
+        ExportFormat::Csv => print!("{}", families::to_csv(&families)),
+        #[cfg(feature = "serde")]
+        ExportFormat::Json => println!("{}", families::to_json(&families)),
+    }
+    families.len()
+}
+
+fn main() {
+    let (options, parsed) = parse_args(env::args().skip(1));
+    let code = match parsed.and_then(|()| run(&options)) {
+        Ok(0) if options.command != Command::Repl => EXIT_NOT_FOUND,
+        Ok(_) => EXIT_FOUND,
+        Err(error) => {
+            error.report(options.errors);
+            error.exit_code()
+        }
+    };
+    process::exit(code);
+}
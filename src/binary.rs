@@ -0,0 +1,237 @@
+//! Compact binary encoding of word lists, so that a dictionary parsed once can be loaded again
+//! without parsing its words, see [`Dictionary::save_cache`](crate::Dictionary::save_cache).
+//!
+//! An encoding starts with the magic bytes `FWDC`, a format version (`u16`), the
+//! [`Fingerprint`] of the words (`u64`) and the number of words (`u32`). Every word follows, in order: its length in bytes and its UTF-8 bytes, its rank
+//! plus one (0 without a rank), then its number of distinct letters and each letter, as a length
+//! and UTF-8 bytes, with its count as a byte. Lengths and ranks are LEB128 variable-length
+//! integers. The encoding ends with a 64-bit FNV-1a checksum of everything before it. Integers
+//! of fixed size are little-endian. Decoding checks the checksum and the fingerprint, so a
+//! truncated or corrupted file, or one whose words are not those it was written with, is
+//! rejected rather than loaded with wrong words.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::dictionary::Fingerprint;
+use crate::Word;
+
+const MAGIC: &[u8; 4] = b"FWDC";
+
+/// Version of the format written by [`encode`].
+pub const FORMAT_VERSION: u16 = 2;
+
+/// Bytes of the magic bytes, the version, the fingerprint and the number of words.
+const HEADER_LEN: usize = 18;
+
+/// Error returned when bytes are not a valid encoding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The bytes do not start with the magic bytes of the format.
+    NotAnEncoding,
+    /// The bytes were written by another version of the format.
+    UnsupportedVersion(u16),
+    /// The bytes end in the middle of a word or of the header.
+    Truncated,
+    /// The checksum does not match the bytes.
+    Corrupted,
+    /// A word, or one of its letters, is not valid UTF-8.
+    InvalidUtf8,
+    /// Bytes are left after the last word.
+    TrailingBytes,
+    /// The words decoded do not have the fingerprint of the header.
+    FingerprintMismatch { expected: Fingerprint, found: Fingerprint },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::NotAnEncoding => write!(f, "not an encoded word list"),
+            DecodeError::UnsupportedVersion(version) =>
+                write!(f, "format version {} is not supported, expected {}", version, FORMAT_VERSION),
+            DecodeError::Truncated => write!(f, "encoded word list is truncated"),
+            DecodeError::Corrupted => write!(f, "encoded word list is corrupted"),
+            DecodeError::InvalidUtf8 => write!(f, "encoded word is not valid UTF-8"),
+            DecodeError::TrailingBytes => write!(f, "unexpected bytes after the last encoded word"),
+            DecodeError::FingerprintMismatch { expected, found } =>
+                write!(f, "encoded words have fingerprint {}, expected {}", found, expected),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// Encodes the words, keeping their order, ranks and letter counts.
+pub fn encode(words: &[Word]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + 8 + words.iter().map(|word| 3 * word.value.len() + 3).sum::<usize>());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&Fingerprint::of(words).0.to_le_bytes());
+    bytes.extend_from_slice(&(words.len() as u32).to_le_bytes());
+    for word in words {
+        write_varint(&mut bytes, word.value.len() as u64);
+        bytes.extend_from_slice(word.value.as_bytes());
+        write_varint(&mut bytes, word.rank.map_or(0, |rank| rank as u64 + 1));
+        let mut letters: Vec<(char, u8)> = word.letters.iter().map(|(&letter, &count)| (letter, count)).collect();
+        letters.sort_unstable();
+        write_varint(&mut bytes, letters.len() as u64);
+        for (letter, count) in letters {
+            let mut buffer = [0; 4];
+            let letter = letter.encode_utf8(&mut buffer);
+            write_varint(&mut bytes, letter.len() as u64);
+            bytes.extend_from_slice(letter.as_bytes());
+            bytes.push(count);
+        }
+    }
+    let checksum = checksum(&bytes);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+    bytes
+}
+
+/// The fingerprint recorded in the header of an encoding, read without decoding the words, e.g.
+/// to check that a cache holds the expected lexicon.
+pub fn fingerprint(bytes: &[u8]) -> Result<Fingerprint, DecodeError> {
+    let mut reader = header(bytes)?;
+    Ok(Fingerprint(u64::from_le_bytes(reader.array()?)))
+}
+
+/// Decodes words encoded by [`encode`].
+pub fn decode(bytes: &[u8]) -> Result<Vec<Word>, DecodeError> {
+    let (body, sum) = bytes.split_at(bytes.len().saturating_sub(8));
+    let mut reader = header(bytes)?;
+    if checksum(body) != u64::from_le_bytes(sum.try_into().expect("split 8 bytes off")) { return Err(DecodeError::Corrupted); }
+    let expected = Fingerprint(u64::from_le_bytes(reader.array()?));
+    let len = u32::from_le_bytes(reader.array()?) as usize;
+    // Every word takes at least 3 bytes, which bounds what a wrong length can allocate.
+    let mut words = Vec::with_capacity(len.min(bytes.len() / 3));
+    for _ in 0..len {
+        let value = reader.string()?;
+        let rank = reader.varint()?.checked_sub(1).map(|rank| rank as u32);
+        let distinct = reader.varint()?;
+        let mut letters = HashMap::new();
+        for _ in 0..distinct {
+            let letter = reader.string()?;
+            let mut chars = letter.chars();
+            let (Some(letter), None) = (chars.next(), chars.next()) else { return Err(DecodeError::InvalidUtf8); };
+            letters.insert(letter, reader.byte()?);
+        }
+        words.push(Word { value, letters, rank });
+    }
+    if !reader.bytes.is_empty() { return Err(DecodeError::TrailingBytes); }
+    let found = Fingerprint::of(&words);
+    if found != expected { return Err(DecodeError::FingerprintMismatch { expected, found }); }
+    Ok(words)
+}
+
+/// The bytes of an encoding after its magic bytes and version, once both are checked.
+fn header(bytes: &[u8]) -> Result<Reader<'_>, DecodeError> {
+    if bytes.get(..4) != Some(MAGIC.as_slice()) { return Err(DecodeError::NotAnEncoding); }
+    if bytes.len() < HEADER_LEN + 8 { return Err(DecodeError::Truncated); }
+    let mut reader = Reader { bytes: &bytes[4..bytes.len() - 8] };
+    let version = u16::from_le_bytes(reader.array()?);
+    if version != FORMAT_VERSION { return Err(DecodeError::UnsupportedVersion(version)); }
+    Ok(reader)
+}
+
+/// 64-bit FNV-1a hash of the bytes.
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Bytes left to decode.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.bytes.len() < len { return Err(DecodeError::Truncated); }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        Ok(self.take(N)?.try_into().expect("took exactly N bytes"))
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, DecodeError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 { return Ok(value); }
+        }
+        Err(DecodeError::Truncated)
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let len = self.varint()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+#[cfg(test)]
+mod binary_tests {
+    use crate::{test_words, Word};
+
+    use crate::Dictionary;
+
+    use super::{checksum, decode, encode, fingerprint, DecodeError, FORMAT_VERSION};
+
+    fn words() -> Vec<Word> {
+        let mut words = test_words(&["cat", "Éclair", "", "a-b", "zzz"]);
+        words[1].rank = Some(300);
+        words
+    }
+
+    #[test]
+    fn round_trip() {
+        let words = words();
+        assert_eq!(decode(&encode(&words)), Ok(words));
+        assert_eq!(decode(&encode(&[])), Ok(vec![]));
+    }
+
+    #[test]
+    fn fingerprints() {
+        let words = words();
+        let bytes = encode(&words);
+        let expected = Dictionary::new(words).fingerprint();
+        assert_eq!(fingerprint(&bytes), Ok(expected));
+        assert_eq!(fingerprint(b"words.txt"), Err(DecodeError::NotAnEncoding));
+        let mut other = bytes[..bytes.len() - 8].to_vec();
+        other[6] ^= 1;
+        other.extend_from_slice(&checksum(&other).to_le_bytes());
+        let recorded = fingerprint(&other).unwrap();
+        assert_eq!(decode(&other), Err(DecodeError::FingerprintMismatch { expected: recorded, found: expected }));
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let bytes = encode(&words());
+        assert_eq!(decode(b"words.txt"), Err(DecodeError::NotAnEncoding));
+        assert_eq!(decode(&bytes[..10]), Err(DecodeError::Truncated));
+        assert_eq!(decode(&bytes[..bytes.len() - 1]), Err(DecodeError::Corrupted));
+        let mut newer = bytes.clone();
+        newer[4] = FORMAT_VERSION as u8 + 1;
+        assert_eq!(decode(&newer), Err(DecodeError::UnsupportedVersion(FORMAT_VERSION + 1)));
+        for position in [10, bytes.len() - 10, bytes.len() - 1] {
+            let mut corrupted = bytes.clone();
+            corrupted[position] ^= 1;
+            assert_eq!(decode(&corrupted), Err(DecodeError::Corrupted), "byte {}", position);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! A Bloom filter: a compact set that can tell for sure that a word is absent, and only
+//! probably that it is present.
+//!
+//! The filter takes about `1.44 * log2(1 / rate)` bits per word for a false positive rate of
+//! `rate`, i.e. under 10 bits per word for 1%, much less than an exact index. Used as a
+//! prefilter in front of an exact check, it answers most queries for absent words on its own.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Bloom filter over strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    bit_count: usize,
+    hash_count: u32,
+    len: usize,
+}
+
+impl BloomFilter {
+    /// An empty filter sized for `capacity` items with a false positive rate of `rate`
+    /// (between 0 and 1, exclusive).
+    pub fn with_rate(capacity: usize, rate: f64) -> BloomFilter {
+        assert!(rate > 0.0 && rate < 1.0, "false positive rate {} must be between 0 and 1", rate);
+        let bit_count = Self::bits_for(capacity, rate).max(64);
+        let hash_count = ((bit_count as f64 / capacity.max(1) as f64) * std::f64::consts::LN_2).round().max(1.0) as u32;
+        BloomFilter { bits: vec![0; bit_count.div_ceil(64)], bit_count, hash_count, len: 0 }
+    }
+
+    /// Number of bits a filter of `capacity` items needs for a false positive rate of `rate`.
+    pub fn bits_for(capacity: usize, rate: f64) -> usize {
+        (-(capacity as f64) * rate.ln() / (std::f64::consts::LN_2 * std::f64::consts::LN_2)).ceil() as usize
+    }
+
+    pub fn insert(&mut self, item: &str) {
+        let bits: Vec<usize> = self.bit_indexes(item).collect();
+        for bit in bits {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+        self.len += 1;
+    }
+
+    /// Returns `false` if the item was never inserted, and `true` if it probably was.
+    pub fn might_contain(&self, item: &str) -> bool {
+        self.bit_indexes(item).all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Number of items inserted.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Size of the bit array, in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        self.bits.len() * 8
+    }
+
+    /// Expected false positive rate given the items inserted so far.
+    pub fn false_positive_rate(&self) -> f64 {
+        let filled = 1.0 - (-(self.hash_count as f64) * self.len as f64 / self.bit_count as f64).exp();
+        filled.powi(self.hash_count as i32)
+    }
+
+    /// Bits of an item, by double hashing.
+    fn bit_indexes(&self, item: &str) -> impl Iterator<Item=usize> + '_ {
+        let first = hash(item, 0);
+        let second = hash(item, 1) | 1;
+        (0..self.hash_count as u64).map(move |i| (first.wrapping_add(i.wrapping_mul(second)) % self.bit_count as u64) as usize)
+    }
+}
+
+fn hash(item: &str, seed: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    item.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod bloom_filter_tests {
+    use super::BloomFilter;
+
+    #[test]
+    fn no_false_negatives() {
+        let mut filter = BloomFilter::with_rate(1000, 0.01);
+        let words: Vec<String> = (0..1000).map(|i| format!("word{}", i)).collect();
+        for word in &words {
+            filter.insert(word);
+        }
+        assert_eq!(filter.len(), 1000);
+        assert!(words.iter().all(|word| filter.might_contain(word)));
+    }
+
+    #[test]
+    fn false_positive_rate_is_close_to_target() {
+        let mut filter = BloomFilter::with_rate(1000, 0.01);
+        for i in 0..1000 {
+            filter.insert(&format!("word{}", i));
+        }
+        let false_positives = (0..10000).filter(|i| filter.might_contain(&format!("other{}", i))).count();
+        assert!(false_positives < 300, "{} false positives", false_positives);
+        assert!((filter.false_positive_rate() - 0.01).abs() < 0.005);
+    }
+
+    #[test]
+    fn memory_tradeoff() {
+        assert!(BloomFilter::bits_for(1000, 0.001) > BloomFilter::bits_for(1000, 0.01));
+        assert_eq!(BloomFilter::with_rate(1000, 0.01).memory_bytes(), BloomFilter::bits_for(1000, 0.01).div_ceil(64) * 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_rate() {
+        BloomFilter::with_rate(10, 1.5);
+    }
+}
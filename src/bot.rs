@@ -0,0 +1,163 @@
+//! Computer opponents choosing a word from a rack.
+//!
+//! A [`Bot`] finds the words a rack can make in its dictionary, scores them and picks one
+//! according to its [`Difficulty`]: always the best word, one of the best few, or a word a player
+//! with a limited vocabulary would know. There is no board yet, so plays are single words.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use rand::prelude::*;
+
+use crate::matches::{Matches, Weights};
+use crate::{letter_count, Dictionary, Rack};
+
+/// How well a [`Bot`] plays.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    /// Always the highest-scoring word.
+    MaxScore,
+    /// One of the `k` highest-scoring words, uniformly at random.
+    TopK(usize),
+    /// A word among the `max_rank` most frequent words of the bot's frequencies, at random with a
+    /// probability proportional to its score. Without frequencies, every word is known.
+    Vocabulary { max_rank: usize },
+}
+
+/// A computer opponent.
+pub struct Bot<'a> {
+    dictionary: &'a Dictionary,
+    difficulty: Difficulty,
+    scorer: &'a dyn Fn(&str) -> u32,
+    frequencies: Option<&'a HashMap<String, u64>>,
+}
+
+impl<'a> Bot<'a> {
+    /// A bot playing words of `dictionary`, scoring one point per letter.
+    pub fn new(dictionary: &'a Dictionary, difficulty: Difficulty) -> Self {
+        Bot { dictionary, difficulty, scorer: &letter_score, frequencies: None }
+    }
+
+    /// Scores words with `scorer` instead, e.g. with a puzzle's
+    /// [`word_score`](crate::puzzle::Puzzle::word_score).
+    pub fn with_scorer(mut self, scorer: &'a dyn Fn(&str) -> u32) -> Self {
+        self.scorer = scorer;
+        self
+    }
+
+    /// How often each word is used, for [`Difficulty::Vocabulary`].
+    pub fn with_frequencies(mut self, frequencies: &'a HashMap<String, u64>) -> Self {
+        self.frequencies = Some(frequencies);
+        self
+    }
+
+    /// Words the rack can make, highest score first and in dictionary order among equal scores.
+    pub fn ranked_plays(&self, rack: &Rack) -> Vec<(&'a str, u32)> {
+        let mut plays: Vec<(&'a str, u32)> = self.dictionary.find_rack(rack)
+            .into_iter()
+            .map(|word| (word, (self.scorer)(word)))
+            .collect();
+        plays.sort_by_key(|&(_, score)| Reverse(score));
+        plays
+    }
+
+    /// Chooses the word to play from a rack, or `None` if the bot has no word to play.
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R, rack: &Rack) -> Option<&'a str> {
+        let plays = self.ranked_plays(rack);
+        match self.difficulty {
+            Difficulty::MaxScore => plays.first().map(|&(word, _)| word),
+            Difficulty::TopK(k) => plays[..k.min(plays.len())].choose(rng).map(|&(word, _)| word),
+            Difficulty::Vocabulary { max_rank } => {
+                let known: Vec<&str> = match self.frequencies {
+                    None => plays.iter().map(|&(word, _)| word).collect(),
+                    Some(frequencies) => {
+                        let vocabulary = vocabulary(frequencies, max_rank);
+                        plays.iter().map(|&(word, _)| word).filter(|word| vocabulary.contains(word)).collect()
+                    }
+                };
+                let known = Matches::from(known);
+                let word = known.sample_weighted(rng, Weights::Score(self.scorer))?;
+                plays.iter().map(|&(play, _)| play).find(|&play| play == word)
+            }
+        }
+    }
+}
+
+/// The `max_rank` most frequent words, ties broken alphabetically.
+fn vocabulary(frequencies: &HashMap<String, u64>, max_rank: usize) -> Vec<&str> {
+    let mut words: Vec<(&str, u64)> = frequencies.iter().map(|(word, &count)| (word.as_str(), count)).collect();
+    words.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    words.into_iter().take(max_rank).map(|(word, _)| word).collect()
+}
+
+fn letter_score(word: &str) -> u32 {
+    letter_count(word) as u32
+}
+
+#[cfg(test)]
+mod bot_tests {
+    use std::collections::{HashMap, HashSet};
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::{test_words, Dictionary, Rack};
+
+    use super::{Bot, Difficulty};
+
+    fn dictionary() -> Dictionary {
+        test_words(&["a", "at", "cat", "act", "tact", "dog"]).into()
+    }
+
+    fn rack(tiles: &str) -> Rack {
+        tiles.parse().unwrap()
+    }
+
+    #[test]
+    fn ranked_plays() {
+        let dictionary = dictionary();
+        let bot = Bot::new(&dictionary, Difficulty::MaxScore);
+        assert_eq!(bot.ranked_plays(&rack("ttac")), vec![("tact", 4), ("cat", 3), ("act", 3), ("at", 2)]);
+    }
+
+    #[test]
+    fn max_score() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(3);
+        let bot = Bot::new(&dictionary, Difficulty::MaxScore);
+        assert_eq!(bot.choose(&mut rng, &rack("ttac")), Some("tact"));
+        assert_eq!(bot.choose(&mut rng, &rack("xyz")), None);
+    }
+
+    #[test]
+    fn custom_scorer() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(3);
+        let scorer = |word: &str| if word == "act" { 10 } else { 1 };
+        let bot = Bot::new(&dictionary, Difficulty::MaxScore).with_scorer(&scorer);
+        assert_eq!(bot.choose(&mut rng, &rack("ttac")), Some("act"));
+    }
+
+    #[test]
+    fn top_k() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(3);
+        let bot = Bot::new(&dictionary, Difficulty::TopK(2));
+        let chosen: HashSet<&str> = (0..100).filter_map(|_| bot.choose(&mut rng, &rack("ttac"))).collect();
+        assert_eq!(chosen, HashSet::from(["tact", "cat"]));
+    }
+
+    #[test]
+    fn vocabulary() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(3);
+        let frequencies = HashMap::from([
+            ("a".to_string(), 1000), ("at".to_string(), 500), ("cat".to_string(), 100), ("tact".to_string(), 1),
+        ]);
+        let bot = Bot::new(&dictionary, Difficulty::Vocabulary { max_rank: 3 }).with_frequencies(&frequencies);
+        let chosen: HashSet<&str> = (0..100).filter_map(|_| bot.choose(&mut rng, &rack("ttac"))).collect();
+        assert_eq!(chosen, HashSet::from(["at", "cat"]));
+        let bot = Bot::new(&dictionary, Difficulty::Vocabulary { max_rank: 2 });
+        assert!((0..100).filter_map(|_| bot.choose(&mut rng, &rack("ttac"))).any(|word| word == "tact"));
+    }
+}
@@ -0,0 +1,304 @@
+//! Caching of search results.
+//!
+//! A [`QueryCache`] keys results by the letters of the rack (in any order) and the
+//! [`QueryOptions`] of the search, and remembers the [`generation`](Dictionary::generation) of
+//! the dictionary they came from: as soon as it is used with another generation, because the
+//! dictionary changed or another dictionary is searched, every entry is dropped.
+//!
+//! A [`PreparedQuery`] caches the work done before a search instead: the letters are counted
+//! and the options resolved once, so that a server retrying a search or paging through its
+//! results runs it again without parsing anything.
+
+use std::collections::{HashMap, VecDeque};
+use std::ops::RangeInclusive;
+
+use crate::dictionary::Normalization;
+use crate::{Dictionary, DictionaryView, LetterCounts, LetterKey, DEFAULT_MIN_WORD_LEN};
+
+/// Restrictions of a search that can be part of a cache key.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct QueryOptions {
+    pub min_len: Option<usize>,
+    pub max_len: Option<usize>,
+    pub first_letter: Option<char>,
+    /// Normalization of the search, the dictionary's if `None`.
+    pub normalization: Option<Normalization>,
+}
+
+/// Letters and options of a search, normalized once to be searched any number of times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreparedQuery {
+    counts: LetterCounts,
+    min_len: usize,
+    max_len: usize,
+    first_letter: Option<char>,
+    normalization: Option<Normalization>,
+}
+
+/// Results of recent searches, oldest evicted first once `capacity` entries are cached.
+#[derive(Clone, Debug)]
+pub struct QueryCache {
+    capacity: usize,
+    generation: Option<u64>,
+    entries: HashMap<(LetterKey, QueryOptions), Vec<String>>,
+    order: VecDeque<(LetterKey, QueryOptions)>,
+    hits: u64,
+    misses: u64,
+}
+
+impl QueryOptions {
+    /// The view of `dictionary` searched with these options. Without `min_len`, searches skip
+    /// the words shorter than [`DEFAULT_MIN_WORD_LEN`].
+    pub fn view<'a>(&self, dictionary: &'a Dictionary) -> DictionaryView<'a> {
+        let mut view = match (self.min_len, self.max_len) {
+            (Some(min_len), Some(max_len)) => dictionary.view().lengths(min_len..=max_len),
+            (Some(min_len), None) => dictionary.view().lengths(min_len..),
+            (None, Some(max_len)) => dictionary.view().lengths(..=max_len),
+            (None, None) => dictionary.view(),
+        };
+        if let Some(letter) = self.first_letter {
+            view = view.starting_with(letter);
+        }
+        if let Some(normalization) = self.normalization {
+            view = view.normalization(normalization);
+        }
+        view
+    }
+}
+
+impl PreparedQuery {
+    /// The search of `list` with `options` in `dictionary`. The minimum length defaults to
+    /// [`DEFAULT_MIN_WORD_LEN`], and the maximum length to the number of letters of the list. The
+    /// letters are counted with the normalization of the options, the dictionary's without one, so
+    /// the query is only meant for `dictionary` and its copies.
+    pub fn new(dictionary: &Dictionary, list: &str, options: &QueryOptions) -> PreparedQuery {
+        let counts = options.normalization.unwrap_or_else(|| dictionary.normalization()).counts(list);
+        let max_len = options.max_len.map_or(counts.len(), |max_len| max_len.min(counts.len()));
+        PreparedQuery {
+            counts,
+            min_len: options.min_len.unwrap_or(DEFAULT_MIN_WORD_LEN),
+            max_len,
+            first_letter: options.first_letter.map(|letter| letter.to_ascii_lowercase()),
+            normalization: options.normalization,
+        }
+    }
+
+    /// Counts of the letters of the list.
+    pub fn counts(&self) -> &LetterCounts {
+        &self.counts
+    }
+
+    /// Bitmask of the letters of the list, see [`LetterCounts::mask`].
+    pub fn mask(&self) -> u64 {
+        self.counts.mask()
+    }
+
+    /// Lengths of the words the search can find.
+    pub fn lengths(&self) -> RangeInclusive<usize> {
+        self.min_len..=self.max_len
+    }
+
+    pub fn first_letter(&self) -> Option<char> {
+        self.first_letter
+    }
+
+    /// Returns `true` if no word can be found, the lengths being empty.
+    pub fn is_empty(&self) -> bool {
+        self.min_len > self.max_len
+    }
+
+    /// Words of `dictionary` found by the search, the same as [`QueryOptions::view`] finds for the
+    /// list, in dictionary order.
+    pub fn find<'a>(&self, dictionary: &'a Dictionary) -> Vec<&'a str> {
+        if self.is_empty() { return vec![]; }
+        let mut view = dictionary.view().lengths(self.lengths());
+        if let Some(letter) = self.first_letter {
+            view = view.starting_with(letter);
+        }
+        if let Some(normalization) = self.normalization {
+            view = view.normalization(normalization);
+        }
+        view.find_counts(&self.counts)
+    }
+
+    /// Up to `len` words of the search, skipping the first `offset`, for paging through results.
+    pub fn page<'a>(&self, dictionary: &'a Dictionary, offset: usize, len: usize) -> Vec<&'a str> {
+        self.find(dictionary).into_iter().skip(offset).take(len).collect()
+    }
+}
+
+impl QueryCache {
+    pub fn new(capacity: usize) -> QueryCache {
+        QueryCache {
+            capacity,
+            generation: None,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Words of `dictionary` that can be constructed from `rack` with `options`, from the cache
+    /// when the same letters were searched with the same options in the same generation. Without
+    /// `min_len`, the key holds [`DEFAULT_MIN_WORD_LEN`], so that asking for the default minimum
+    /// or for none shares an entry.
+    pub fn find(&mut self, dictionary: &Dictionary, rack: &str, options: &QueryOptions) -> Vec<String> {
+        if self.generation != Some(dictionary.generation()) {
+            self.clear();
+            self.generation = Some(dictionary.generation());
+        }
+        let normalization = options.normalization.unwrap_or_else(|| dictionary.normalization());
+        let options = QueryOptions { min_len: Some(options.min_len.unwrap_or(DEFAULT_MIN_WORD_LEN)), ..options.clone() };
+        let key = (LetterKey::from(&normalization.counts(rack)), options.clone());
+        if let Some(words) = self.entries.get(&key) {
+            self.hits += 1;
+            dictionary.record_query(None);
+            return words.clone();
+        }
+        self.misses += 1;
+        let words: Vec<String> = options.view(dictionary).find(rack).into_iter().map(str::to_string).collect();
+        if self.capacity > 0 {
+            if self.entries.len() >= self.capacity {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.entries.remove(&oldest);
+                }
+            }
+            self.order.push_back(key.clone());
+            self.entries.insert(key, words.clone());
+        }
+        words
+    }
+
+    /// Generation of the dictionary the cached results came from, `None` before the first search.
+    pub fn generation(&self) -> Option<u64> {
+        self.generation
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of searches answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of searches that had to run.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+#[cfg(test)]
+mod query_cache_tests {
+    use crate::{test_words, Dictionary, Normalization};
+
+    use super::{PreparedQuery, QueryCache, QueryOptions};
+
+    fn dictionary() -> Dictionary {
+        test_words(&["a", "at", "cat", "act", "tact", "dog"]).into()
+    }
+
+    #[test]
+    fn same_letters_hit() {
+        let dictionary = dictionary();
+        let mut cache = QueryCache::new(10);
+        let options = QueryOptions::default();
+        assert_eq!(cache.find(&dictionary, "tac", &options), vec!["at", "cat", "act"]);
+        assert_eq!(cache.find(&dictionary, "C A T", &options), vec!["at", "cat", "act"]);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        assert_eq!(cache.generation(), Some(dictionary.generation()));
+    }
+
+    #[test]
+    fn hits_are_counted_by_the_dictionary() {
+        let mut dictionary = dictionary();
+        dictionary.set_usage_tracking(true);
+        let mut cache = QueryCache::new(10);
+        cache.find(&dictionary, "tac", &QueryOptions::default());
+        cache.find(&dictionary, "cat", &QueryOptions::default());
+        let stats = dictionary.stats_snapshot().unwrap();
+        assert_eq!((stats.queries, stats.cache_hits), (2, 1));
+    }
+
+    #[test]
+    fn options_are_part_of_the_key() {
+        let dictionary = dictionary();
+        let mut cache = QueryCache::new(10);
+        let options = QueryOptions { min_len: Some(3), max_len: None, first_letter: Some('a'), normalization: None };
+        assert_eq!(cache.find(&dictionary, "tac", &QueryOptions::default()).len(), 3);
+        assert_eq!(cache.find(&dictionary, "tac", &options), vec!["act"]);
+        assert_eq!(cache.misses(), 2);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn dictionary_changes_invalidate() {
+        let mut dictionary = dictionary();
+        let mut cache = QueryCache::new(10);
+        let options = QueryOptions::default();
+        cache.find(&dictionary, "tac", &options);
+        dictionary.retain(|word| word.value != "cat");
+        assert_eq!(cache.find(&dictionary, "tac", &options), vec!["at", "act"]);
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn oldest_entries_are_evicted() {
+        let dictionary = dictionary();
+        let mut cache = QueryCache::new(2);
+        let options = QueryOptions::default();
+        for rack in ["a", "at", "cat", "a"] {
+            cache.find(&dictionary, rack, &options);
+        }
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.hits(), 0);
+    }
+
+    #[test]
+    fn prepared_queries() {
+        let dictionary = dictionary();
+        let query = PreparedQuery::new(&dictionary, "T-a-c", &QueryOptions::default());
+        assert_eq!((query.lengths(), query.counts().len()), (2..=3, 3));
+        assert_eq!(query.mask(), 1 << 19 | 1 << 2 | 1);
+        assert_eq!(query.find(&dictionary), dictionary.find("tac"));
+        assert_eq!(query.page(&dictionary, 1, 5), vec!["cat", "act"]);
+
+        let options = QueryOptions { min_len: Some(1), max_len: Some(10), first_letter: Some('A'), normalization: None };
+        let query = PreparedQuery::new(&dictionary, "tac", &options);
+        assert_eq!((query.lengths(), query.first_letter()), (1..=3, Some('a')));
+        assert_eq!(query.find(&dictionary), options.view(&dictionary).find("tac"));
+        assert!(PreparedQuery::new(&dictionary, "tac", &QueryOptions { min_len: Some(4), ..options }).is_empty());
+    }
+
+    #[test]
+    fn normalization_overrides() {
+        let dictionary: Dictionary = test_words(&["élève", "lev"]).into();
+        let folded = QueryOptions { normalization: Some(Normalization::FoldDiacritics), ..QueryOptions::default() };
+        let mut cache = QueryCache::new(10);
+        assert_eq!(cache.find(&dictionary, "elv", &QueryOptions::default()), vec!["élève", "lev"]);
+        assert_eq!(cache.find(&dictionary, "elv", &folded), vec!["lev"]);
+        assert_eq!(cache.find(&dictionary, "eeelv", &folded), vec!["élève", "lev"]);
+        assert_eq!(cache.find(&dictionary, "éeelv", &folded), vec!["élève", "lev"]);
+        assert_eq!((cache.hits(), cache.misses()), (1, 3));
+        assert_eq!(PreparedQuery::new(&dictionary, "ÉÈelv", &folded).find(&dictionary), vec!["élève", "lev"]);
+
+        let mut folding: Dictionary = test_words(&["élève", "lev"]).into();
+        folding.set_fold_diacritics(true);
+        let default = QueryOptions::default();
+        assert_eq!(default.view(&folding).find("éeelv"), vec!["élève", "lev"]);
+        let query = PreparedQuery::new(&folding, "éeelv", &default);
+        assert_eq!((query.find(&folding), query.lengths()), (vec!["élève", "lev"], 2..=5));
+    }
+}
@@ -0,0 +1,94 @@
+//! Compound words: two words of a dictionary written together, for lenient game modes.
+//!
+//! [`Dictionary::find_with_compounds`](crate::Dictionary::find_with_compounds) returns the words
+//! a list of letters can make, followed by the concatenations of two of those words that the list
+//! can make at once and that are not words of the dictionary themselves, e.g. "cupboard" from
+//! "cup" and "board" with a word list lacking it. Compounds are flagged as
+//! [`Candidate::Compound`], so that games can score or confirm them differently.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::alphabet::English;
+use crate::parallel::*;
+use crate::{can_be_constructed, LetterCounts};
+
+/// A word found for a list of letters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Candidate<'a> {
+    /// A word of the dictionary.
+    Word(&'a str),
+    /// Two words of the dictionary written together, which is not a word of the dictionary.
+    Compound(&'a str, &'a str),
+}
+
+impl Candidate<'_> {
+    /// The word, compounds written without separator.
+    pub fn value(&self) -> String {
+        self.to_string()
+    }
+
+    /// Returns `true` for a compound, which is not a word of the dictionary.
+    pub fn is_synthesized(&self) -> bool {
+        matches!(self, Candidate::Compound(..))
+    }
+}
+
+impl fmt::Display for Candidate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Candidate::Word(word) => write!(f, "{}", word),
+            Candidate::Compound(first, second) => write!(f, "{}{}", first, second),
+        }
+    }
+}
+
+/// Pairs of `words` that `list` can make together, for which `is_word` is `false`, ordered by
+/// their first then their second word in the order of `words`. Words with characters other than
+/// ASCII letters are not used, and each compound is only returned once, ignoring case.
+pub(crate) fn compounds<'a>(words: &[&'a str], list: &LetterCounts, is_word: impl Fn(&str) -> bool + Sync) -> Vec<Candidate<'a>> {
+    let parts: Vec<(&str, LetterCounts)> = words.iter()
+        .filter(|word| word.chars().all(|c| c.is_ascii_alphabetic()))
+        .map(|&word| (word, LetterCounts::from_word::<English>(word)))
+        .collect();
+    let pairs: Vec<Vec<Candidate<'a>>> = parts.par_iter()
+        .map(|(first, counts)| {
+            let Some(left) = list.checked_sub(counts) else { return vec![]; };
+            parts.iter()
+                .filter(|(_, second)| can_be_constructed(second, &left))
+                .filter(|(second, _)| !is_word(&format!("{}{}", first, second)))
+                .map(|(second, _)| Candidate::Compound(first, second))
+                .collect()
+        })
+        .collect();
+    let mut seen = HashSet::new();
+    pairs.into_iter()
+        .flatten()
+        .filter(|compound| seen.insert(compound.value().to_ascii_lowercase()))
+        .collect()
+}
+
+#[cfg(test)]
+mod compound_tests {
+    use crate::{test_words, Dictionary};
+
+    use super::Candidate;
+
+    #[test]
+    fn compounds_follow_the_words() {
+        let dictionary: Dictionary = test_words(&["cup", "board", "cupboard", "dog"]).into();
+        let found = dictionary.find_with_compounds("Cupboard");
+        assert_eq!(found, [Candidate::Word("cup"), Candidate::Word("board"), Candidate::Word("cupboard"), Candidate::Compound("board", "cup")]);
+        assert!(found[3].is_synthesized() && !found[0].is_synthesized());
+        assert_eq!(found[3].value(), "boardcup");
+        assert_eq!(dictionary.find_with_compounds("cupboar"), [Candidate::Word("cup")]);
+    }
+
+    #[test]
+    fn compounds_are_not_repeated() {
+        let dictionary: Dictionary = test_words(&["Up", "up", "on", "it's"]).into();
+        let found = dictionary.find_with_compounds("uponits");
+        let compounds: Vec<String> = found.iter().filter(|candidate| candidate.is_synthesized()).map(Candidate::value).collect();
+        assert_eq!(compounds, ["Upon", "onUp"]);
+    }
+}
@@ -0,0 +1,164 @@
+//! Corpora of realistic racks with known solution counts, to measure and check engines on the
+//! kind of input games produce rather than on uniformly random strings.
+//!
+//! A corpus file has one rack per line, followed by the number of words of the dictionary the
+//! rack can make: `aeinrst 12`. Blank lines and lines starting with `#` are ignored.
+
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "fs")]
+use std::fs;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "random")]
+use rand::rngs::StdRng;
+#[cfg(feature = "random")]
+use rand::SeedableRng;
+
+#[cfg(feature = "random")]
+use crate::game::TileBag;
+#[cfg(feature = "random")]
+use crate::Dictionary;
+
+/// A rack and the number of words it can make.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RackCase {
+    pub rack: String,
+    pub solutions: usize,
+}
+
+/// A rack on which an engine did not find the expected number of solutions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub rack: String,
+    pub expected: usize,
+    pub actual: usize,
+}
+
+/// Results of running an engine on a corpus.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CorpusReport {
+    pub cases: usize,
+    /// Total number of solutions found.
+    pub solutions: usize,
+    pub mismatches: Vec<Mismatch>,
+    pub elapsed: Duration,
+}
+
+/// Error returned when a corpus cannot be read.
+#[derive(Debug)]
+pub enum CorpusError {
+    Io(std::io::Error),
+    /// A line is not a rack followed by a number, counting lines from 1.
+    InvalidLine(usize),
+}
+
+/// Draws `racks_per_size` racks of each size from copies of `bag`, and counts their solutions in
+/// `dictionary`. The same seed always gives the same corpus.
+#[cfg(feature = "random")]
+pub fn generate_corpus(dictionary: &Dictionary, bag: &TileBag, rack_sizes: &[usize], racks_per_size: usize, seed: u64) -> Vec<RackCase> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    rack_sizes.iter()
+        .flat_map(|&size| (0..racks_per_size).map(move |_| size))
+        .map(|size| {
+            let rack = bag.clone().draw(&mut rng, size).to_string();
+            let solutions = dictionary.find(&rack).len();
+            RackCase { rack, solutions }
+        })
+        .collect()
+}
+
+pub fn parse_corpus(text: &str) -> Result<Vec<RackCase>, CorpusError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(number, line)| {
+            let mut fields = line.split_whitespace();
+            match (fields.next(), fields.next().map(str::parse), fields.next()) {
+                (Some(rack), Some(Ok(solutions)), None) => Ok(RackCase { rack: rack.to_string(), solutions }),
+                _ => Err(CorpusError::InvalidLine(number + 1)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "fs")]
+pub fn read_corpus(file: &str) -> Result<Vec<RackCase>, CorpusError> {
+    parse_corpus(&fs::read_to_string(file).map_err(CorpusError::Io)?)
+}
+
+pub fn write_corpus(cases: &[RackCase]) -> String {
+    cases.iter().map(|case| format!("{} {}\n", case.rack, case.solutions)).collect()
+}
+
+/// Runs `engine`, which returns the number of words a rack can make, on every rack of the corpus.
+pub fn evaluate<F: Fn(&str) -> usize>(cases: &[RackCase], engine: F) -> CorpusReport {
+    let start = Instant::now();
+    let mut solutions = 0;
+    let mut mismatches = vec![];
+    for case in cases {
+        let actual = engine(&case.rack);
+        solutions += actual;
+        if actual != case.solutions {
+            mismatches.push(Mismatch { rack: case.rack.clone(), expected: case.solutions, actual });
+        }
+    }
+    CorpusReport { cases: cases.len(), solutions, mismatches, elapsed: start.elapsed() }
+}
+
+impl fmt::Display for CorpusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CorpusError::Io(error) => write!(f, "cannot read corpus: {}", error),
+            CorpusError::InvalidLine(line) => write!(f, "line {}: expected a rack and a number of solutions", line),
+        }
+    }
+}
+
+impl Error for CorpusError {}
+
+#[cfg(test)]
+mod corpus_tests {
+    #[cfg(feature = "random")]
+    use crate::game::TileBag;
+    use crate::{test_words, Dictionary};
+
+    #[cfg(feature = "random")]
+    use super::generate_corpus;
+    use super::{evaluate, parse_corpus, write_corpus, CorpusError, Mismatch, RackCase};
+
+    fn dictionary() -> Dictionary {
+        test_words(&["a", "at", "cat", "act", "tact", "dog"]).into()
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn generated_corpus() {
+        let dictionary = dictionary();
+        let bag = TileBag::from_letters("aacttdog");
+        let corpus = generate_corpus(&dictionary, &bag, &[3, 8], 4, 9);
+        assert_eq!(corpus.len(), 8);
+        assert!(corpus[..4].iter().all(|case| case.rack.len() == 3));
+        assert_eq!(corpus[7], RackCase { rack: corpus[7].rack.clone(), solutions: 5 });
+        assert_eq!(corpus, generate_corpus(&dictionary, &bag, &[3, 8], 4, 9));
+    }
+
+    #[test]
+    fn round_trip() {
+        let cases = vec![RackCase { rack: "tca".to_string(), solutions: 4 }, RackCase { rack: "xyz".to_string(), solutions: 0 }];
+        let text = write_corpus(&cases);
+        assert_eq!(text, "tca 4\nxyz 0\n");
+        assert_eq!(parse_corpus(&format!("# racks\n\n{}", text)).unwrap(), cases);
+        assert!(matches!(parse_corpus("tca 4\ntca four\n"), Err(CorpusError::InvalidLine(2))));
+    }
+
+    #[test]
+    fn evaluation() {
+        let dictionary = dictionary();
+        let cases = parse_corpus("tca 3\nttac 4\ndog 0\n").unwrap();
+        let report = evaluate(&cases, |rack| dictionary.find(rack).len());
+        assert_eq!(report.cases, 3);
+        assert_eq!(report.solutions, 8);
+        assert_eq!(report.mismatches, vec![Mismatch { rack: "dog".to_string(), expected: 0, actual: 1 }]);
+    }
+}
@@ -0,0 +1,155 @@
+//! Deny-lists of words that must never be shown to players, and random rack generation that
+//! avoids them.
+//!
+//! No list is bundled with the crate: what counts as offensive depends on the product and the
+//! audience, so applications load their own, one word per line.
+
+use std::collections::HashSet;
+#[cfg(feature = "fs")]
+use std::io::Error;
+
+#[cfg(feature = "random")]
+use rand::Rng;
+
+#[cfg(feature = "random")]
+use crate::random_string;
+#[cfg(feature = "fs")]
+use crate::read_words_from_file;
+use crate::Dictionary;
+
+/// Maximum number of racks drawn by [`generate_safe_random_string`] before giving up.
+pub const MAX_SAFE_RACK_ATTEMPTS: usize = 1000;
+
+/// Set of denied words, compared on their lowercase letters.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DenyList {
+    words: HashSet<String>,
+}
+
+impl DenyList {
+    /// Builds a deny-list from words. Words without letters are ignored.
+    pub fn new<I: IntoIterator<Item=S>, S: AsRef<str>>(words: I) -> DenyList {
+        DenyList {
+            words: words.into_iter()
+                .map(|word| letters_of(word.as_ref()))
+                .filter(|word| !word.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Reads a deny-list from a file containing one word per line.
+    #[cfg(feature = "fs")]
+    pub fn from_file(file: &str) -> Result<DenyList, Error> {
+        Ok(DenyList::new(read_words_from_file(file)?.into_iter().map(|word| word.value)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns `true` if the word is denied, ignoring case and anything but letters.
+    pub fn is_denied(&self, word: &str) -> bool {
+        self.words.contains(&letters_of(word))
+    }
+
+    /// Returns `true` if a denied word can be read in `text`, as a run of consecutive letters.
+    pub fn spells_denied(&self, text: &str) -> bool {
+        let text = letters_of(text);
+        self.words.iter().any(|word| text.contains(word.as_str()))
+    }
+
+    /// Returns `true` if a rack is safe to show: it does not spell a denied word, and unless it
+    /// has no solution at all, some of its solutions in `dictionary` are not denied.
+    pub fn is_safe_rack(&self, rack: &str, dictionary: &Dictionary) -> bool {
+        if self.spells_denied(rack) { return false; }
+        let solutions = dictionary.find(rack);
+        solutions.is_empty() || solutions.iter().any(|word| !self.is_denied(word))
+    }
+}
+
+/// Like [`generate_random_string`](crate::generate_random_string), but only returns racks that
+/// are safe according to [`DenyList::is_safe_rack`]. Returns `None` if no safe rack was drawn
+/// in [`MAX_SAFE_RACK_ATTEMPTS`] attempts.
+#[cfg(feature = "random")]
+pub fn generate_safe_random_string<R: Rng + ?Sized>(rng: &mut R, length: u8, dictionary: &Dictionary, deny_list: &DenyList) -> Option<String> {
+    (0..MAX_SAFE_RACK_ATTEMPTS)
+        .map(|_| random_string(rng, length))
+        .find(|rack| deny_list.is_safe_rack(rack, dictionary))
+}
+
+fn letters_of(word: &str) -> String {
+    word.chars().filter(|c| c.is_alphabetic()).flat_map(char::to_lowercase).collect()
+}
+
+#[cfg(test)]
+mod deny_list_tests {
+    #[cfg(feature = "random")]
+    use rand::rngs::StdRng;
+    #[cfg(feature = "random")]
+    use rand::SeedableRng;
+
+    use crate::{test_words, Dictionary};
+
+    #[cfg(feature = "random")]
+    use super::generate_safe_random_string;
+    use super::DenyList;
+
+    fn deny_list() -> DenyList {
+        DenyList::new(["Rat", "bad word", ""])
+    }
+
+    #[test]
+    fn denied_words() {
+        let deny_list = deny_list();
+        assert_eq!(deny_list.len(), 2);
+        assert!(deny_list.is_denied("RAT"));
+        assert!(deny_list.is_denied("bad-word"));
+        assert!(!deny_list.is_denied("rate"));
+    }
+
+    #[test]
+    fn spelled_in_rack() {
+        let deny_list = deny_list();
+        assert!(deny_list.spells_denied("xxratxx"));
+        assert!(deny_list.spells_denied("xbadwordx"));
+        assert!(!deny_list.spells_denied("tarxx"));
+    }
+
+    #[test]
+    fn safe_racks() {
+        let dictionary: Dictionary = test_words(&["rat", "tar", "art", "at"]).into();
+        let deny_list = DenyList::new(["rat", "tar", "art"]);
+        assert!(deny_list.is_safe_rack("rta", &dictionary));
+        assert!(deny_list.is_safe_rack("rtx", &dictionary));
+        assert!(!deny_list.is_safe_rack("ratx", &dictionary));
+        let deny_list = DenyList::new(["rat", "tar", "art", "at"]);
+        assert!(!deny_list.is_safe_rack("rta", &dictionary));
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn generated_racks_are_safe() {
+        let dictionary: Dictionary = test_words(&["cat", "tac", "act"]).into();
+        let deny_list = DenyList::new(["cat", "tac", "act", "zz"]);
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            let rack = generate_safe_random_string(&mut rng, 10, &dictionary, &deny_list).unwrap();
+            assert_eq!(rack.len(), 10);
+            assert!(!rack.contains("zz"));
+            assert!(dictionary.find(&rack).is_empty());
+        }
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn gives_up_when_every_rack_is_unsafe() {
+        let dictionary = Dictionary::default();
+        let deny_list = DenyList::new(('a'..='z').map(|c| c.to_string()));
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(generate_safe_random_string(&mut rng, 3, &dictionary, &deny_list), None);
+    }
+}
@@ -0,0 +1,1944 @@
+//! A reusable, indexed list of words.
+//!
+//! Unlike [`get_constructable_words`](crate::get_constructable_words), which consumes its words,
+//! a [`Dictionary`] is built once and can be queried any number of times. Restricted queries go
+//! through a [`DictionaryView`], which borrows the dictionary instead of copying words out of it.
+//!
+//! Words are bucketed by length and by their rarest letter (the one found in the fewest words of
+//! the dictionary). A word can only be constructed if its rarest letter is in the list, so a
+//! search only has to look at the buckets of the letters present in the list, and only at the
+//! lengths the list is long enough for. [`SearchStats`] reports how many words were looked at,
+//! and [`QueryTrace`] also how long each stage of the search took.
+//!
+//! The other indexes (the [`Trie`], the suffix index and the anagram index, which groups words
+//! by their [`LetterKey`]) are only needed by some queries, so they
+//! are built the first time such a query runs, and dropped whenever the words change. Interactive
+//! applications can also build them ahead of time on a background thread with
+//! [`Dictionary::build_indexes_in_background`], serving simple searches in the meantime.
+//!
+//! ## Sharing a dictionary between processes
+//! Servers that fork workers can load the dictionary once in the parent process and share it
+//! with every worker, relying on the copy-on-write pages of `fork` rather than on a shared memory
+//! segment: a page is only copied into a process once that process writes to it. Searches only
+//! write to a dictionary to build an index or to count usage, so after
+//! [`Dictionary::prepare_for_sharing`] they only read it, and the words and indexes stay in
+//! pages shared by every worker. Changing the dictionary, e.g. with
+//! [`retain`](Dictionary::retain), copies what it changes into the worker that changes it.
+//!
+//! ## Caching parsed dictionaries
+//! [`Dictionary::save_cache`] writes the parsed words in the compact format of the
+//! [`binary`](crate::binary) module, which [`Dictionary::load_cache`] reads back much faster than
+//! a word list can be parsed. With the `serde` feature, a dictionary also serializes as the list
+//! of its words, and deserializes by indexing them again.
+//!
+//! ## Word IDs
+//! [`Dictionary::word_id`] gives every word a [`WordId`], a number made of a tag of the
+//! dictionary's fingerprint and the position of the word, so that services sharing a lexicon can
+//! exchange numbers rather than strings. IDs are the same for the same words in every process,
+//! and [`Dictionary::word_by_id`] rejects the IDs of another version of the lexicon instead of
+//! returning the wrong word.
+//!
+//! ## Accented dictionaries
+//! Only the ASCII letters of a word are counted by default, so "élève" is made of one `e`, one
+//! `l` and one `v`. [`Dictionary::set_fold_diacritics`] counts accented letters as the letter
+//! they are written on instead, in the words and in the lists searched, so that a rack typed
+//! without accents finds "élève", which is still returned with its accents.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+#[cfg(feature = "fs")]
+use std::fs;
+#[cfg(feature = "fs")]
+use std::io::Error;
+use std::ops::{Bound, Range, RangeBounds, RangeInclusive};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::alphabet::{Alphabet, English, Folded};
+#[cfg(feature = "fs")]
+use crate::binary;
+use crate::bloom::BloomFilter;
+use crate::compound::{compounds, Candidate};
+use crate::distribution::LetterFrequencies;
+use crate::limits::WILDCARDS;
+#[cfg(feature = "fs")]
+use crate::load::{LoadCounts, LoadOptions};
+use crate::merge::kmerge;
+use crate::pattern::Pattern;
+use crate::rack::BLANK;
+use crate::sequence::letter_indexes;
+use crate::suffix::{shared_suffix_len, Extension, SuffixIndex};
+use crate::trie::{find_embedded, EmbeddedWord, Trie};
+#[cfg(feature = "fs")]
+use crate::read_words;
+use crate::{can_be_constructed, get_letters_count, letter_count, lowercase_letters, map_can_be_constructed, parse_letters, word_len, FindWordsError, LetterCounts, LetterKey, Rack, Word, DEFAULT_MIN_WORD_LEN};
+
+/// How the letters of the words and of the lists of letters are counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Normalization {
+    /// Only ASCII letters are counted, ignoring case.
+    #[default]
+    Ascii,
+    /// Latin letters with diacritics also count as the letter they are written on, see
+    /// [`Folded`].
+    FoldDiacritics,
+}
+
+/// Source of [`Dictionary::generation`] numbers, shared by every dictionary.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// List of words indexed by word length (the number of letters in the word) and rarest letter.
+#[derive(Clone, Debug)]
+pub struct Dictionary {
+    words: Vec<Word>,
+    generation: u64,
+    fingerprint: Fingerprint,
+    buckets: BTreeMap<usize, BTreeMap<Option<char>, Vec<usize>>>,
+    /// Letter counts of every word, in dictionary order, checked by searches instead of the maps
+    /// of the words.
+    counts: Vec<LetterCounts>,
+    trie: OnceLock<Trie>,
+    suffixes: OnceLock<SuffixIndex>,
+    /// Copy of the words starting with a letter, sorted, and the range of each letter in it.
+    browse: OnceLock<(Vec<Word>, [Range<usize>; 26])>,
+    /// Indexes of every word, sorted alphabetically ignoring case.
+    sorted: OnceLock<Vec<usize>>,
+    /// Indexes of the words with each letter key, in dictionary order.
+    anagrams: OnceLock<HashMap<LetterKey, Vec<usize>>>,
+    /// Indexes of the two-letter and three-letter words, in dictionary order.
+    short_words: OnceLock<[Vec<usize>; 2]>,
+    /// Optional filter checked by `contains` before the trie, with its false positive rate.
+    prefilter: Option<(f64, BloomFilter)>,
+    /// Usage counters, when tracking is on.
+    usage: Option<UsageCounters>,
+    /// Whether the letters of the words and of the lists searched are counted with the
+    /// [`Folded`] alphabet.
+    fold_diacritics: bool,
+    /// Letter counts of every word with the other normalization, for views overriding it.
+    alternate_counts: OnceLock<Vec<LetterCounts>>,
+}
+
+/// Counters behind [`Dictionary::stats_snapshot`], updated by searches through a shared
+/// reference, from any thread.
+#[derive(Debug, Default)]
+struct UsageCounters {
+    queries: AtomicU64,
+    cache_hits: AtomicU64,
+    candidates: AtomicU64,
+}
+
+/// Usage of a dictionary since tracking was turned on, see [`Dictionary::set_usage_tracking`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UsageStats {
+    /// Number of searches served, from a [`QueryCache`](crate::cache::QueryCache) or not.
+    pub queries: u64,
+    /// Number of searches served from a [`QueryCache`](crate::cache::QueryCache).
+    pub cache_hits: u64,
+    /// Number of candidates checked one by one, over every search that was not a cache hit.
+    pub candidates: u64,
+}
+
+/// Handle on the indexes of a dictionary being built on a background thread, see
+/// [`Dictionary::build_indexes_in_background`]. Dropping the handle does not stop the build.
+#[derive(Debug)]
+pub struct IndexBuild {
+    dictionary: Arc<Dictionary>,
+    cancelled: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// How much of the dictionary a search had to look at.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Number of words in the dictionary.
+    pub words: usize,
+    /// Number of words in the buckets that could match, and were checked one by one.
+    pub candidates: usize,
+    /// Number of words found.
+    pub matches: usize,
+}
+
+/// Hash of the words of a dictionary, in order, to check that two programs use the same version
+/// of a lexicon. Unlike [`Dictionary::generation`], it only depends on the words, and is the same
+/// on every platform and in every version of this crate. Displayed as 16 hexadecimal digits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fingerprint(pub u64);
+
+/// Stable identifier of a word of a dictionary: 32 bits derived from the [`Fingerprint`] of the
+/// dictionary, then 32 bits of the position of the word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WordId(pub u64);
+
+/// Detailed account of a search: how many words each stage left, and how long it took.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QueryTrace {
+    /// Number of words in the dictionary.
+    pub words: usize,
+    /// Number of words whose length is within the view and no longer than the list.
+    pub length_candidates: usize,
+    /// Number of those words left by the rarest letter prefilter, checked one by one.
+    pub candidates: usize,
+    /// Number of buckets (words of one length with the same rarest letter) the candidates came from.
+    pub buckets: usize,
+    /// Number of words found.
+    pub matches: usize,
+    /// Time spent counting the letters of the list.
+    pub parse: Duration,
+    /// Time spent selecting and checking the candidates.
+    pub check: Duration,
+    /// Time spent merging the words found in each bucket back into dictionary order.
+    pub merge: Duration,
+    /// Time spent verifying the words found, zero when verification is off.
+    pub verify: Duration,
+}
+
+impl UsageCounters {
+    fn snapshot(&self) -> UsageStats {
+        UsageStats {
+            queries: self.queries.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            candidates: self.candidates.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Clone for UsageCounters {
+    fn clone(&self) -> Self {
+        let stats = self.snapshot();
+        UsageCounters {
+            queries: AtomicU64::new(stats.queries),
+            cache_hits: AtomicU64::new(stats.cache_hits),
+            candidates: AtomicU64::new(stats.candidates),
+        }
+    }
+}
+
+impl UsageStats {
+    /// Average number of candidates checked by the searches that were not cache hits, 0 without
+    /// any such search.
+    pub fn average_candidates(&self) -> f64 {
+        let searches = self.queries - self.cache_hits;
+        if searches == 0 { return 0.0; }
+        self.candidates as f64 / searches as f64
+    }
+}
+
+impl Dictionary {
+    /// Builds a dictionary from a list of words, keeping their order.
+    pub fn new(words: Vec<Word>) -> Dictionary {
+        let mut dictionary = Dictionary {
+            words,
+            generation: 0,
+            fingerprint: Fingerprint::default(),
+            buckets: BTreeMap::new(),
+            counts: vec![],
+            trie: OnceLock::new(),
+            suffixes: OnceLock::new(),
+            anagrams: OnceLock::new(),
+            browse: OnceLock::new(),
+            sorted: OnceLock::new(),
+            short_words: OnceLock::new(),
+            prefilter: None,
+            usage: None,
+            fold_diacritics: false,
+            alternate_counts: OnceLock::new(),
+        };
+        dictionary.rebuild_indexes();
+        dictionary
+    }
+
+    /// Reads a dictionary from a file containing one word per line. Fails with
+    /// [`FindWordsError::EmptyDictionary`] if the file has no lines.
+    #[cfg(feature = "fs")]
+    pub fn from_file(file: &str) -> Result<Dictionary, FindWordsError> {
+        let words = read_words(file)?;
+        if words.is_empty() { return Err(FindWordsError::EmptyDictionary { path: file.to_string() }); }
+        Ok(Dictionary::new(words))
+    }
+
+    /// Reads a dictionary from a file containing one word per line, cleaned up and filtered with
+    /// `options`, and counts the words left out. Fails with [`FindWordsError::EmptyDictionary`]
+    /// if no word is left.
+    #[cfg(feature = "fs")]
+    pub fn from_file_with(file: &str, options: &LoadOptions) -> Result<(Dictionary, LoadCounts), FindWordsError> {
+        let (words, counts) = options.read_file(file)?;
+        if words.is_empty() { return Err(FindWordsError::EmptyDictionary { path: file.to_string() }); }
+        Ok((Dictionary::new(words), counts))
+    }
+
+    /// Writes the words of the dictionary and its [`fingerprint`](Dictionary::fingerprint) to a
+    /// cache file, in the format of [`binary::encode`], to be read back with
+    /// [`Dictionary::load_cache`].
+    #[cfg(feature = "fs")]
+    pub fn save_cache(&self, path: &str) -> Result<(), Error> {
+        fs::write(path, binary::encode(&self.words))
+    }
+
+    /// Reads a dictionary from a cache file written by [`Dictionary::save_cache`]. Fails with
+    /// [`FindWordsError::InvalidCache`] if the file is not a valid cache, e.g. after being
+    /// truncated, or if its words do not have the fingerprint recorded with them.
+    #[cfg(feature = "fs")]
+    pub fn load_cache(path: &str) -> Result<Dictionary, FindWordsError> {
+        let bytes = fs::read(path).map_err(|source| FindWordsError::Io { path: path.to_string(), source })?;
+        let words = binary::decode(&bytes).map_err(|error| FindWordsError::InvalidCache { path: path.to_string(), error })?;
+        Ok(Dictionary::new(words))
+    }
+
+    /// The English word list compiled into the crate with the `embedded-dict` feature: the words
+    /// of `words.txt`, as [`Dictionary::from_file`] would read them.
+    #[cfg(feature = "embedded-dict")]
+    pub fn builtin() -> Dictionary {
+        Dictionary::new(crate::builtin_words())
+    }
+
+    /// Every word in the dictionary.
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Words starting with `letter`, ignoring case, sorted alphabetically ignoring case, for
+    /// browsing the dictionary page by page. The first call copies the words starting with a
+    /// letter into a sorted layout, which later calls slice without copying. Empty for a
+    /// character that is not an ASCII letter.
+    pub fn starting_with(&self, letter: char) -> &[Word] {
+        let Some(index) = English::index(letter) else { return &[]; };
+        let (words, ranges) = self.browse.get_or_init(|| {
+            let mut words: Vec<Word> = self.words.iter()
+                .filter(|word| word.value.starts_with(|c: char| c.is_ascii_alphabetic()))
+                .cloned()
+                .collect();
+            words.sort_by_cached_key(|word| (word.value.to_ascii_lowercase(), word.value.clone()));
+            let ranges = std::array::from_fn(|index| {
+                let first = |word: &Word| English::index(word.value.chars().next().unwrap()).unwrap();
+                words.partition_point(|word| first(word) < index)..words.partition_point(|word| first(word) <= index)
+            });
+            (words, ranges)
+        });
+        &words[ranges[index].clone()]
+    }
+
+    /// Words within a range, e.g. `dictionary.range("car".."cat")`, sorted alphabetically ignoring
+    /// case, as are the bounds. The first call sorts the indexes of the words, after which a range
+    /// is found by binary search, and skipping words to get to a page is free.
+    pub fn range<'r, R: RangeBounds<&'r str>>(&self, range: R) -> impl DoubleEndedIterator<Item=&Word> + ExactSizeIterator {
+        let sorted = self.sorted();
+        // Number of words before `bound`, or before and equal to it.
+        let count_before = |bound: &str, or_equal: bool| sorted.partition_point(|&index| {
+            let key = folded(&self.words[index].value);
+            if or_equal { key.le(folded(bound)) } else { key.lt(folded(bound)) }
+        });
+        let start = match range.start_bound() {
+            Bound::Included(bound) => count_before(bound, false),
+            Bound::Excluded(bound) => count_before(bound, true),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(bound) => count_before(bound, true),
+            Bound::Excluded(bound) => count_before(bound, false),
+            Bound::Unbounded => sorted.len(),
+        }.max(start);
+        sorted[start..end].iter().map(|&index| &self.words[index])
+    }
+
+    /// Words starting with `prefix`, ignoring case, sorted like a [`range`](Dictionary::range).
+    pub fn with_prefix(&self, prefix: &str) -> impl DoubleEndedIterator<Item=&Word> + ExactSizeIterator {
+        let sorted = self.sorted();
+        let prefix = prefix.to_ascii_lowercase();
+        let start = sorted.partition_point(|&index| folded(&self.words[index].value).lt(prefix.bytes()));
+        let len = sorted[start..].partition_point(|&index| self.words[index].value.to_ascii_lowercase().starts_with(&prefix));
+        sorted[start..start + len].iter().map(|&index| &self.words[index])
+    }
+
+    /// Up to `limit` words whose letters start with the letters of `prefix`, the prefix itself
+    /// included, for autocompletion: the most frequent first for words with a rank (see
+    /// [`read_ranked_words`](crate::load::read_ranked_words)), then the words without one,
+    /// shortest first, in dictionary order among words of the same rank and length. Candidates
+    /// are found with the trie, so the minimum word length does not apply.
+    pub fn complete(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        let mut found = self.trie().starting_with(prefix);
+        let key = |&index: &usize| {
+            let word = &self.words[index];
+            (word.rank.is_none(), word.rank, word_len(&word.letters), index)
+        };
+        if limit < found.len() {
+            found.select_nth_unstable_by_key(limit, key);
+            found.truncate(limit);
+        }
+        found.sort_unstable_by_key(key);
+        found.into_iter().map(|index| self.words[index].value.as_str()).collect()
+    }
+
+    fn sorted(&self) -> &[usize] {
+        self.sorted.get_or_init(|| {
+            let mut sorted: Vec<usize> = (0..self.words.len()).collect();
+            sorted.sort_by(|&a, &b| {
+                let (a, b) = (&self.words[a].value, &self.words[b].value);
+                folded(a).cmp(folded(b)).then_with(|| a.cmp(b))
+            });
+            sorted
+        })
+    }
+
+    /// Words made of exactly `len` letters, in dictionary order.
+    pub fn words_of_length(&self, len: usize) -> impl Iterator<Item=&Word> {
+        self.indexes_of_length(len).into_iter().map(|index| &self.words[index])
+    }
+
+    /// Two-letter words, in dictionary order, only those that can be played from `rack` if given.
+    /// The list is computed once, so consulting it is cheap.
+    pub fn two_letter_words(&self, rack: Option<&Rack>) -> Vec<&str> {
+        self.short_words(0, rack)
+    }
+
+    /// Three-letter words, in dictionary order, like [`two_letter_words`](Dictionary::two_letter_words).
+    pub fn three_letter_words(&self, rack: Option<&Rack>) -> Vec<&str> {
+        self.short_words(1, rack)
+    }
+
+    /// For words of `len` letters, how many have each letter (0 for a, 25 for z) at each position.
+    /// Positions count letters only, so "co-op" has `o` at position 2.
+    pub fn position_frequencies(&self, len: usize) -> Vec<[usize; 26]> {
+        let mut frequencies = vec![[0; 26]; len];
+        for word in self.words_of_length(len) {
+            for (position, letter) in letter_indexes(&word.value).enumerate() {
+                frequencies[position][letter] += 1;
+            }
+        }
+        frequencies
+    }
+
+    /// How often each English letter appears in the words, to derive a tile bag from them.
+    pub fn letter_frequencies(&self) -> LetterFrequencies {
+        LetterFrequencies::from_words::<English>(&self.words)
+    }
+
+    /// Words that can be constructed from the list of letters, in dictionary order.
+    pub fn find(&self, list: &str) -> Vec<&str> {
+        self.view().find(list)
+    }
+
+    /// Same as [`find`](Dictionary::find), followed by the compounds of two of the words that the
+    /// list can make and that are not words of the dictionary, see the [`compound`](crate::compound)
+    /// module.
+    pub fn find_with_compounds(&self, list: &str) -> Vec<Candidate<'_>> {
+        self.view().find_with_compounds(list)
+    }
+
+    /// Same as [`find`](Dictionary::find), failing with [`FindWordsError::InvalidLetters`] if
+    /// the list does not satisfy the default [`ParsePolicy`](crate::letters::ParsePolicy)
+    /// instead of ignoring what is not a letter.
+    pub fn try_find(&self, list: &str) -> Result<Vec<&str>, FindWordsError> {
+        self.view().try_find(list)
+    }
+
+    /// Words that can be constructed from the list of letters, each of its [`WILDCARDS`] standing
+    /// for any letter, a word using at most `max_blanks` of them. In dictionary order.
+    pub fn find_with_wildcards(&self, list: &str, max_blanks: usize) -> Vec<&str> {
+        self.view().find_with_wildcards(list, max_blanks)
+    }
+
+    /// Words that can be played from a rack, blanks standing for any letter.
+    pub fn find_rack(&self, rack: &Rack) -> Vec<&str> {
+        self.view().find_rack(rack)
+    }
+
+    /// Same as [`find`](Dictionary::find), also returning how many words had to be checked.
+    pub fn find_with_stats(&self, list: &str) -> (Vec<&str>, SearchStats) {
+        self.view().find_with_stats(list)
+    }
+
+    /// Same as [`find`](Dictionary::find), also returning a trace of the search.
+    pub fn find_with_trace(&self, list: &str) -> (Vec<&str>, QueryTrace) {
+        self.view().find_with_trace(list)
+    }
+
+    /// Words appearing in `text` as runs of at least `min_len` consecutive letters, ignoring
+    /// spaces and punctuation in between, so "the bra tactic" contains "brat". Results are
+    /// sorted by position in the text and then by length.
+    pub fn find_embedded_words(&self, text: &str, min_len: usize) -> Vec<EmbeddedWord> {
+        find_embedded(self.trie(), &self.words, text, min_len)
+    }
+
+    /// Words fitting a board pattern, the letters on its empty squares taken from `letters`, in
+    /// dictionary order. Only the words of the pattern's length are checked.
+    pub fn find_matching_pattern(&self, letters: &str, pattern: &Pattern) -> Vec<&str> {
+        let list = LetterCounts::from_word::<English>(letters);
+        self.view()
+            .lengths(pattern.len()..=pattern.len())
+            .words()
+            .into_iter()
+            .filter(|word| pattern.matches(&word.value, &list))
+            .map(|word| word.value.as_str())
+            .collect()
+    }
+
+    /// Words made of exactly the letters of `letters`, ignoring case and any other character, in
+    /// dictionary order. Looked up in the anagram index, which is built on first use.
+    pub fn anagrams(&self, letters: &str) -> Vec<&str> {
+        let list = self.list_counts(letters);
+        if list.is_empty() { return vec![]; }
+        self.anagram_index().get(&LetterKey::from(&list))
+            .map_or_else(Vec::new, |indexes| indexes.iter().map(|&index| self.words[index].value.as_str()).collect())
+    }
+
+    /// Up to `n` of the longest words that can be constructed from the list of letters, see
+    /// [`DictionaryView::find_longest`].
+    pub fn find_longest(&self, list: &str, n: usize) -> Vec<&str> {
+        self.view().find_longest(list, n)
+    }
+
+    /// Sequences of at most `depth` words that can be played one after the other from the list of
+    /// letters, see [`DictionaryView::find_word_chains`].
+    pub fn find_word_chains(&self, list: &str, depth: usize) -> Vec<Vec<&str>> {
+        self.view().find_word_chains(list, depth)
+    }
+
+    /// Words that can be constructed from the list of letters, grouped by number of letters,
+    /// each group in dictionary order.
+    pub fn subanagrams(&self, list: &str) -> BTreeMap<usize, Vec<&str>> {
+        let mut groups: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+        for word in self.find(list) {
+            groups.entry(letter_count(word)).or_default().push(word);
+        }
+        groups
+    }
+
+    /// Words ending with `suffix`, ignoring case, in dictionary order.
+    pub fn words_ending_with(&self, suffix: &str) -> Vec<&str> {
+        let mut indexes: Vec<usize> = self.suffixes().ending_with(suffix).collect();
+        indexes.sort_unstable();
+        indexes.into_iter().map(|index| self.words[index].value.as_str()).collect()
+    }
+
+    /// Words extending `word` with letters after it (back extensions, found with the trie) or in
+    /// front of it (front extensions, found with the suffix index), so "quest" gives "quests",
+    /// "question" and "conquest". Words are compared on letters only, ignoring case, and `word`
+    /// itself is left out. Back extensions come first, each kind in dictionary order.
+    pub fn extensions_of(&self, word: &str) -> Vec<Extension<'_>> {
+        let letters = letters_key(word);
+        if letters.is_empty() { return vec![]; }
+        let mut back = self.trie().starting_with(&letters);
+        back.sort_unstable();
+        let mut front: Vec<usize> = self.suffixes().ending_with(&letters).collect();
+        front.sort_unstable();
+
+        let backs = back.into_iter().filter_map(|index| {
+            let extra = letters_key(&self.words[index].value).split_off(letters.len());
+            (!extra.is_empty()).then(|| Extension { word: &self.words[index].value, front: String::new(), back: extra })
+        });
+        let fronts = front.into_iter().filter_map(|index| {
+            let mut extra = letters_key(&self.words[index].value);
+            extra.truncate(extra.len() - letters.len());
+            (!extra.is_empty()).then(|| Extension { word: &self.words[index].value, front: extra, back: String::new() })
+        });
+        backs.chain(fronts).collect()
+    }
+
+    /// Rhyme suggestions for `word`: the other words sharing at least its last `min_shared`
+    /// letters, those sharing the longest ending first, then in dictionary order.
+    pub fn rhymes(&self, word: &str, min_shared: usize) -> Vec<&str> {
+        let letters: Vec<char> = word.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+        if letters.len() < min_shared.max(1) {
+            return vec![];
+        }
+        let ending: String = letters[letters.len() - min_shared..].iter().collect();
+        let mut rhymes: Vec<(usize, usize)> = self.suffixes().ending_with(&ending)
+            .filter(|&index| !self.words[index].value.eq_ignore_ascii_case(word))
+            .map(|index| (shared_suffix_len(&self.words[index].value, word), index))
+            .collect();
+        rhymes.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        rhymes.into_iter().map(|(_, index)| self.words[index].value.as_str()).collect()
+    }
+
+    /// Number identifying the current words of the dictionary. It changes whenever the words
+    /// change, and no two dictionaries built or changed separately share one, so caches of search
+    /// results can store it and drop their entries once it no longer matches.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Hash of the words of the dictionary, see [`Fingerprint`].
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.fingerprint
+    }
+
+    /// ID of the first word equal to `word`, case included, or `None` if there is none.
+    pub fn word_id(&self, word: &str) -> Option<WordId> {
+        let trie = self.trie();
+        let node = letter_indexes(word).try_fold(Trie::ROOT, |node, letter| trie.child(node, letter as u8))?;
+        let position = trie.words_at(node).iter().copied().find(|&index| self.words[index].value == word)?;
+        Some(WordId::new(self.fingerprint, position))
+    }
+
+    /// The word with an ID given by [`word_id`](Dictionary::word_id), or `None` if the ID is of
+    /// another dictionary.
+    pub fn word_by_id(&self, id: WordId) -> Option<&Word> {
+        if id.tag() != WordId::new(self.fingerprint, 0).tag() { return None; }
+        self.words.get(id.position())
+    }
+
+    /// Returns `true` once every index has been built.
+    pub fn indexes_ready(&self) -> bool {
+        self.trie.get().is_some() && self.suffixes.get().is_some() && self.anagrams.get().is_some()
+    }
+
+    /// Builds every index that is otherwise built on first use, on the current thread.
+    pub fn build_indexes(&self) {
+        self.trie();
+        self.suffixes();
+        self.anagram_index();
+        self.starting_with('a');
+        self.sorted();
+        self.short_words(0, None);
+        self.alternate_counts();
+    }
+
+    /// Builds every index and turns usage tracking off, so that searches never write to the
+    /// dictionary, which can then be shared by forked processes without being copied into each
+    /// of them, see the [module documentation](self).
+    pub fn prepare_for_sharing(&mut self) {
+        self.build_indexes();
+        self.set_usage_tracking(false);
+    }
+
+    /// Returns `true` if searches only read the dictionary: every index is built and usage is
+    /// not tracked.
+    pub fn is_read_only(&self) -> bool {
+        self.indexes_ready() && self.browse.get().is_some() && self.sorted.get().is_some() && self.short_words.get().is_some() && self.alternate_counts.get().is_some() && self.usage.is_none()
+    }
+
+    /// Starts building every index on a new thread. Searches can run meanwhile: those needing an
+    /// index that is still being built wait for it.
+    pub fn build_indexes_in_background(self: &Arc<Self>) -> IndexBuild {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let dictionary = Arc::clone(self);
+            let cancelled = Arc::clone(&cancelled);
+            thread::spawn(move || {
+                let builds: [&dyn Fn(); 3] = [
+                    &|| { dictionary.trie(); },
+                    &|| { dictionary.suffixes(); },
+                    &|| { dictionary.anagram_index(); },
+                ];
+                for build in builds {
+                    if cancelled.load(Ordering::Relaxed) { return; }
+                    build();
+                }
+            })
+        };
+        IndexBuild { dictionary: Arc::clone(self), cancelled, thread: Some(thread) }
+    }
+
+    /// Returns `true` if some word of the dictionary has exactly the letters of `word`, in order,
+    /// ignoring case. With a prefilter, most absent words are rejected without using the trie.
+    pub fn contains(&self, word: &str) -> bool {
+        if let Some((_, filter)) = &self.prefilter {
+            if !filter.might_contain(&letters_key(word)) { return false; }
+        }
+        self.trie().contains(word)
+    }
+
+    /// Adds a Bloom filter in front of [`contains`](Dictionary::contains), letting through
+    /// a fraction `rate` of the absent words, or removes it with `None`.
+    /// See [`BloomFilter::bits_for`] for the memory used.
+    pub fn set_prefilter(&mut self, rate: Option<f64>) {
+        self.prefilter = rate.map(|rate| (rate, self.build_prefilter(rate)));
+    }
+
+    /// Turns the usage counters of [`stats_snapshot`](Dictionary::stats_snapshot) on, from zero,
+    /// or off. Counting costs a few atomic additions per search, so it is off by default.
+    pub fn set_usage_tracking(&mut self, enabled: bool) {
+        self.usage = enabled.then(UsageCounters::default);
+    }
+
+    /// Usage since tracking was turned on, `None` while it is off.
+    pub fn stats_snapshot(&self) -> Option<UsageStats> {
+        self.usage.as_ref().map(UsageCounters::snapshot)
+    }
+
+    /// Counts a search, `None` standing for a cache hit.
+    pub(crate) fn record_query(&self, candidates: Option<usize>) {
+        let Some(usage) = &self.usage else { return; };
+        usage.queries.fetch_add(1, Ordering::Relaxed);
+        match candidates {
+            Some(candidates) => usage.candidates.fetch_add(candidates as u64, Ordering::Relaxed),
+            None => usage.cache_hits.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+
+    /// The prefilter of [`contains`](Dictionary::contains), if any.
+    pub fn prefilter(&self) -> Option<&BloomFilter> {
+        self.prefilter.as_ref().map(|(_, filter)| filter)
+    }
+
+    /// Prefix tree of the words of the dictionary, built on first use.
+    pub fn trie(&self) -> &Trie {
+        self.trie.get_or_init(|| Trie::new(&self.words))
+    }
+
+    /// A view over the whole dictionary, to be narrowed down with the view's methods.
+    pub fn view(&self) -> DictionaryView<'_> {
+        DictionaryView {
+            dictionary: self,
+            min_len: Bound::Unbounded,
+            max_len: Bound::Unbounded,
+            first_letter: None,
+            filters: vec![],
+            normalization: None,
+            verify: cfg!(debug_assertions),
+        }
+    }
+
+    /// Turns diacritic folding on or off, updating the indexes: with folding, the letters of the
+    /// words and of the lists of letters searched are counted with the [`Folded`] alphabet, so
+    /// that "eleve" finds "élève", which is still returned with its diacritics.
+    pub fn set_fold_diacritics(&mut self, fold: bool) {
+        self.fold_diacritics = fold;
+        for word in &mut self.words {
+            word.letters = letters_of(&word.value, fold);
+        }
+        self.rebuild_indexes();
+    }
+
+    pub fn folds_diacritics(&self) -> bool {
+        self.fold_diacritics
+    }
+
+    /// The normalization of searches that do not override it, see
+    /// [`DictionaryView::normalization`].
+    pub fn normalization(&self) -> Normalization {
+        if self.fold_diacritics { Normalization::FoldDiacritics } else { Normalization::Ascii }
+    }
+
+    /// Counts of a list of letters, folded if the dictionary folds diacritics.
+    fn list_counts(&self, list: &str) -> LetterCounts {
+        self.normalization().counts(list)
+    }
+
+    /// Letter counts of every word with the normalization the dictionary does not use, built on
+    /// first use.
+    fn alternate_counts(&self) -> &[LetterCounts] {
+        self.alternate_counts.get_or_init(|| {
+            let normalization = if self.fold_diacritics { Normalization::Ascii } else { Normalization::FoldDiacritics };
+            self.words.iter().map(|word| normalization.counts(&word.value)).collect()
+        })
+    }
+
+    /// Keeps only the words for which the predicate returns `true`, updating the indexes.
+    pub fn retain<F: FnMut(&Word) -> bool>(&mut self, f: F) {
+        self.words.retain(f);
+        self.rebuild_indexes();
+    }
+
+    fn rebuild_indexes(&mut self) {
+        self.generation = NEXT_GENERATION.fetch_add(1, Ordering::Relaxed);
+        self.fingerprint = Fingerprint::of(&self.words);
+        let mut frequencies = HashMap::new();
+        for word in &self.words {
+            for &letter in word.letters.keys() {
+                *frequencies.entry(letter).or_insert(0usize) += 1;
+            }
+        }
+        self.counts = self.words.iter().map(|word| LetterCounts::from(&word.letters)).collect();
+        self.buckets.clear();
+        for (index, word) in self.words.iter().enumerate() {
+            let rarest = word.letters.keys().copied().min_by_key(|letter| (frequencies[letter], *letter));
+            self.buckets.entry(word_len(&word.letters))
+                .or_default()
+                .entry(rarest)
+                .or_default()
+                .push(index);
+        }
+        self.trie = OnceLock::new();
+        self.suffixes = OnceLock::new();
+        self.anagrams = OnceLock::new();
+        self.browse = OnceLock::new();
+        self.sorted = OnceLock::new();
+        self.short_words = OnceLock::new();
+        self.alternate_counts = OnceLock::new();
+        if let Some((rate, _)) = self.prefilter {
+            self.prefilter = Some((rate, self.build_prefilter(rate)));
+        }
+    }
+
+    fn build_prefilter(&self, rate: f64) -> BloomFilter {
+        let mut filter = BloomFilter::with_rate(self.words.len(), rate);
+        for word in &self.words {
+            filter.insert(&letters_key(&word.value));
+        }
+        filter
+    }
+
+    fn indexes_of_length(&self, len: usize) -> Vec<usize> {
+        let mut indexes: Vec<usize> = self.buckets.get(&len)
+            .into_iter()
+            .flat_map(|by_letter| by_letter.values().flatten())
+            .copied()
+            .collect();
+        indexes.sort_unstable();
+        indexes
+    }
+
+    fn short_words(&self, list: usize, rack: Option<&Rack>) -> Vec<&str> {
+        let lists = self.short_words.get_or_init(|| {
+            [2, 3].map(|len| self.indexes_of_length(len))
+        });
+        lists[list].iter()
+            .map(|&index| self.words[index].value.as_str())
+            .filter(|word| rack.is_none_or(|rack| rack.can_make(word)))
+            .collect()
+    }
+
+    fn suffixes(&self) -> &SuffixIndex {
+        self.suffixes.get_or_init(|| SuffixIndex::new(&self.words))
+    }
+
+    fn anagram_index(&self) -> &HashMap<LetterKey, Vec<usize>> {
+        self.anagrams.get_or_init(|| {
+            let mut index: HashMap<LetterKey, Vec<usize>> = HashMap::new();
+            for (position, counts) in self.counts.iter().enumerate() {
+                index.entry(LetterKey::from(counts)).or_default().push(position);
+            }
+            index
+        })
+    }
+}
+
+/// A subset of a [`Dictionary`] that can be searched directly.
+///
+/// Length restrictions use the dictionary's length index; other restrictions are checked
+/// word by word during the search.
+///
+/// When verification is on (the default in debug builds), every word returned by a search is
+/// checked again against the list of letters and the restrictions of the view, recounting its
+/// letters from scratch, and the search panics on any word that should not have been returned.
+pub struct DictionaryView<'a> {
+    dictionary: &'a Dictionary,
+    min_len: Bound<usize>,
+    max_len: Bound<usize>,
+    first_letter: Option<char>,
+    filters: Vec<WordFilter<'a>>,
+    /// Normalization of the searches, the dictionary's if `None`.
+    normalization: Option<Normalization>,
+    verify: bool,
+}
+
+type WordFilter<'a> = Box<dyn Fn(&Word) -> bool + 'a>;
+
+impl<'a> DictionaryView<'a> {
+    /// Restricts the view to words whose length is within `range`. Without a lower bound,
+    /// searches skip the words shorter than [`DEFAULT_MIN_WORD_LEN`].
+    pub fn lengths<R: RangeBounds<usize>>(mut self, range: R) -> Self {
+        self.min_len = range.start_bound().cloned();
+        self.max_len = range.end_bound().cloned();
+        self
+    }
+
+    /// Restricts the view to words starting with the letter, ignoring case.
+    pub fn starting_with(mut self, letter: char) -> Self {
+        self.first_letter = Some(letter.to_ascii_lowercase());
+        self
+    }
+
+    /// Restricts the view to words for which the predicate returns `true`.
+    /// Calling `filter` several times keeps the words accepted by every predicate.
+    pub fn filter<F: Fn(&Word) -> bool + 'a>(mut self, predicate: F) -> Self {
+        self.filters.push(Box::new(predicate));
+        self
+    }
+
+    /// Counts the letters of the words and of the lists searched with `normalization` instead of
+    /// the dictionary's, without changing the dictionary: searches then check the letter counts
+    /// of every word with that normalization, built on first use, instead of using the length
+    /// and letter buckets.
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    /// Turns verification of search results on or off.
+    pub fn verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Words in the view, in dictionary order.
+    pub fn words(&self) -> Vec<&'a Word> {
+        self.matching(None).0
+    }
+
+    /// Number of words in the view.
+    pub fn len(&self) -> usize {
+        self.words().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Words in the view that can be constructed from the list of letters, in dictionary order.
+    pub fn find(&self, list: &str) -> Vec<&'a str> {
+        self.find_with_stats(list).0
+    }
+
+    /// Words in the view that can be constructed from the list of letters, followed by compounds
+    /// of two of them, see [`Dictionary::find_with_compounds`]. The compounds are not checked
+    /// against the filters of the view.
+    pub fn find_with_compounds(&self, list: &str) -> Vec<Candidate<'a>> {
+        let list = self.effective_normalization().counts(list);
+        let words = self.find_counts(&list);
+        let compounds = compounds(&words, &list, |compound| self.dictionary.contains(compound));
+        words.into_iter().map(Candidate::Word).chain(compounds).collect()
+    }
+
+    /// Same as [`find`](DictionaryView::find), failing on lists that cannot be parsed, see
+    /// [`Dictionary::try_find`].
+    pub fn try_find(&self, list: &str) -> Result<Vec<&'a str>, FindWordsError> {
+        Ok(self.find_counts(&parse_letters(list)?))
+    }
+
+    /// Words in the view that can be constructed from the list of letters, wildcards standing for
+    /// at most `max_blanks` letters of a word, see [`Dictionary::find_with_wildcards`].
+    pub fn find_with_wildcards(&self, list: &str, max_blanks: usize) -> Vec<&'a str> {
+        let blanks = list.chars().filter(|c| WILDCARDS.contains(c)).count().min(max_blanks);
+        let letters: String = list.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+        let rack: Rack = format!("{}{}", letters, BLANK.to_string().repeat(blanks)).parse()
+            .expect("ASCII letters and blanks always make a rack");
+        self.find_rack(&rack)
+    }
+
+    /// Words in the view that can be played from a rack, in dictionary order. Blanks stand for
+    /// any letter, so a rack with blanks is checked against every word of the view.
+    pub fn find_rack(&self, rack: &Rack) -> Vec<&'a str> {
+        if rack.blanks() == 0 {
+            return self.find(&rack.letters());
+        }
+        let view = DictionaryView { filters: vec![], ..*self }.lengths(..=rack.len());
+        let (candidates, checked) = view.matching(None);
+        self.dictionary.record_query(Some(checked));
+        candidates.into_iter()
+            .filter(|word| self.accepts(word) && (self.search_min_len(), self.max_len).contains(&word_len(&word.letters)) && rack.can_make(&word.value))
+            .map(|word| word.value.as_str())
+            .collect()
+    }
+
+    /// Same as [`find`](DictionaryView::find), also returning how many words had to be checked.
+    pub fn find_with_stats(&self, list: &str) -> (Vec<&'a str>, SearchStats) {
+        let (words, trace) = self.find_with_trace(list);
+        (words, SearchStats::from(trace))
+    }
+
+    /// Same as [`find`](DictionaryView::find), also returning a trace of the search.
+    pub fn find_with_trace(&self, list: &str) -> (Vec<&'a str>, QueryTrace) {
+        let start = Instant::now();
+        let list = self.effective_normalization().counts(list);
+        let trace = QueryTrace { parse: start.elapsed(), ..QueryTrace::default() };
+        self.find_counts_traced(&list, trace)
+    }
+
+    /// Words in the view that can be constructed from letter counts, e.g. those of a
+    /// [`PreparedQuery`](crate::cache::PreparedQuery), in dictionary order.
+    pub fn find_counts(&self, list: &LetterCounts) -> Vec<&'a str> {
+        self.find_counts_traced(list, QueryTrace::default()).0
+    }
+
+    /// Searches the letter counts, adding the counts and timings of the search to `trace`.
+    fn find_counts_traced(&self, list: &LetterCounts, mut trace: QueryTrace) -> (Vec<&'a str>, QueryTrace) {
+        let words = self.matching_traced(Some(list), &mut trace);
+        self.dictionary.record_query(Some(trace.candidates));
+        if self.verify {
+            let start = Instant::now();
+            self.verify_matches(&words, &list.to_map::<English>());
+            trace.verify = start.elapsed();
+        }
+        (words.into_iter().map(|word| word.value.as_str()).collect(), trace)
+    }
+
+    /// Up to `n` of the longest words in the view that can be constructed from the list of
+    /// letters, longest first, words of the same length in dictionary order. Lengths are
+    /// searched from the longest down, stopping as soon as `n` words are found.
+    pub fn find_longest(&self, list: &str, n: usize) -> Vec<&'a str> {
+        self.find_longest_with_trace(list, n).0
+    }
+
+    /// Same as [`find_longest`](DictionaryView::find_longest), also returning a trace of the
+    /// search, which only counts the buckets of the lengths it had to search.
+    pub fn find_longest_with_trace(&self, list: &str, n: usize) -> (Vec<&'a str>, QueryTrace) {
+        let start = Instant::now();
+        let list = self.effective_normalization().counts(list);
+        let mut trace = QueryTrace { words: self.dictionary.words.len(), parse: start.elapsed(), ..QueryTrace::default() };
+        let start = Instant::now();
+        let mut found: Vec<&'a Word> = vec![];
+        if self.overrides_normalization() {
+            found = self.matching_traced(Some(&list), &mut trace);
+            found.sort_by_cached_key(|word| Reverse(self.effective_normalization().counts(&word.value).len()));
+            found.truncate(n);
+        } else if let Some(lengths) = self.search_lengths(Some(&list)) {
+            for by_letter in self.dictionary.buckets.range(lengths).rev().map(|(_, by_letter)| by_letter) {
+                if found.len() >= n { break; }
+                let runs = self.matching_runs(by_letter, Some(&list), &mut trace);
+                found.extend(kmerge(runs).take(n - found.len()).map(|index| &self.dictionary.words[index]));
+            }
+        }
+        trace.check = start.elapsed();
+        trace.matches = found.len();
+        self.dictionary.record_query(Some(trace.candidates));
+        if self.verify {
+            let start = Instant::now();
+            self.verify_matches(&found, &list.to_map::<English>());
+            trace.verify = start.elapsed();
+        }
+        (found.into_iter().map(|word| word.value.as_str()).collect(), trace)
+    }
+
+    /// Sequences of 1 to `depth` words of the view that can be played one after the other from the
+    /// list of letters, each word taking its letters off the list. A chain is listed before the
+    /// chains extending it, and the words following a word are in dictionary order. The number of
+    /// chains grows quickly with the depth: each word after the first is searched among the words
+    /// found for the word before it, not in the whole view.
+    pub fn find_word_chains(&self, list: &str, depth: usize) -> Vec<Vec<&'a str>> {
+        let normalization = self.effective_normalization();
+        let list = normalization.counts(list);
+        let candidates: Vec<(&'a str, LetterCounts)> = self.find_counts(&list).into_iter()
+            .map(|word| (word, normalization.counts(word)))
+            .collect();
+        let mut chains = vec![];
+        extend_chains(&candidates, &list, depth, &mut vec![], &mut chains);
+        chains
+    }
+
+    /// For each letter of `target`, the words of the view starting with it. Characters of
+    /// `target` that are not ASCII letters are skipped.
+    pub fn acrostic_candidates(&self, target: &str) -> Vec<Vec<&'a str>> {
+        let mut view = DictionaryView { filters: vec![], ..*self };
+        target.chars()
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|letter| {
+                view.first_letter = Some(letter.to_ascii_lowercase());
+                view.matching(None).0.into_iter()
+                    .filter(|word| self.accepts(word))
+                    .map(|word| word.value.as_str())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Up to `max` acrostics of `target`: lists of words of the view whose first letters spell
+    /// `target`, without using a word twice. Acrostics are listed in dictionary order of their words.
+    pub fn acrostics(&self, target: &str, max: usize) -> Vec<Vec<&'a str>> {
+        fn search<'a>(candidates: &[Vec<&'a str>], current: &mut Vec<&'a str>, found: &mut Vec<Vec<&'a str>>, max: usize) {
+            if found.len() >= max { return; }
+            let Some(position_candidates) = candidates.get(current.len()) else {
+                found.push(current.clone());
+                return;
+            };
+            for &word in position_candidates {
+                if current.contains(&word) { continue; }
+                current.push(word);
+                search(candidates, current, found, max);
+                current.pop();
+                if found.len() >= max { return; }
+            }
+        }
+
+        let candidates = self.acrostic_candidates(target);
+        let mut found = vec![];
+        if !candidates.is_empty() {
+            search(&candidates, &mut vec![], &mut found, max);
+        }
+        found
+    }
+
+    /// Words of the view that can be constructed from `list` (every word without a list),
+    /// along with the number of candidates checked.
+    fn matching(&self, list: Option<&LetterCounts>) -> (Vec<&'a Word>, usize) {
+        let mut trace = QueryTrace::default();
+        let words = self.matching_traced(list, &mut trace);
+        (words, trace.candidates)
+    }
+
+    /// Same as `matching`, recording the counts and timings of the search in `trace`.
+    fn matching_traced(&self, list: Option<&LetterCounts>, trace: &mut QueryTrace) -> Vec<&'a Word> {
+        let start = Instant::now();
+        let words = &self.dictionary.words;
+        trace.words = words.len();
+        let Some(lengths) = self.search_lengths(list) else { return vec![]; };
+        if let (Some(list), true) = (list, self.overrides_normalization()) {
+            trace.candidates = words.len();
+            let found: Vec<&'a Word> = self.dictionary.alternate_counts().iter()
+                .zip(words)
+                .filter(|(counts, word)| lengths.contains(&counts.len()) && can_be_constructed(counts, list) && self.accepts(word))
+                .map(|(_, word)| word)
+                .collect();
+            trace.check = start.elapsed();
+            trace.matches = found.len();
+            return found;
+        }
+        let mut runs = vec![];
+        for by_letter in self.dictionary.buckets.range(lengths).map(|(_, by_letter)| by_letter) {
+            runs.extend(self.matching_runs(by_letter, list, trace));
+        }
+        trace.check = start.elapsed();
+        // Buckets hold their indexes in ascending order, so merging them restores dictionary order.
+        let start = Instant::now();
+        let found: Vec<&'a Word> = kmerge(runs).map(|index| &words[index]).collect();
+        trace.merge = start.elapsed();
+        trace.matches = found.len();
+        found
+    }
+
+    /// Lengths of the words a search of `list` (every word without a list) can find, `None` if
+    /// there are none.
+    fn search_lengths(&self, list: Option<&LetterCounts>) -> Option<RangeInclusive<usize>> {
+        let max_len = list.map_or(usize::MAX, LetterCounts::len);
+        let min_bound = if list.is_some() { self.search_min_len() } else { self.min_len };
+        let min_len = match min_bound {
+            Bound::Included(len) => len,
+            Bound::Excluded(len) => len.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let max_len = match self.max_len {
+            Bound::Included(len) => len.min(max_len),
+            Bound::Excluded(0) => return None,
+            Bound::Excluded(len) => (len - 1).min(max_len),
+            Bound::Unbounded => max_len,
+        };
+        (min_len <= max_len).then_some(min_len..=max_len)
+    }
+
+    /// Indexes of the words of the buckets of one length that are in the view and can be
+    /// constructed from `list`, one run in ascending order per bucket holding any.
+    fn matching_runs(&self, by_letter: &BTreeMap<Option<char>, Vec<usize>>, list: Option<&LetterCounts>, trace: &mut QueryTrace) -> Vec<Vec<usize>> {
+        let (words, counts) = (&self.dictionary.words, &self.dictionary.counts);
+        let mut runs = vec![];
+        for (rarest, bucket) in by_letter {
+            trace.length_candidates += bucket.len();
+            if list.is_some_and(|list| rarest.and_then(English::index).is_none_or(|index| list.get(index) == 0)) {
+                continue;
+            }
+            trace.buckets += 1;
+            trace.candidates += bucket.len();
+            let run: Vec<usize> = bucket.iter().copied().filter(|&index| {
+                self.accepts(&words[index]) && list.is_none_or(|list| can_be_constructed(&counts[index], list))
+            }).collect();
+            if !run.is_empty() {
+                runs.push(run);
+            }
+        }
+        runs
+    }
+
+    /// Panics if one of `words` is outside the view or cannot be constructed from `list`.
+    fn verify_matches(&self, words: &[&Word], list: &HashMap<char, u8>) {
+        for word in words {
+            let letters = letters_of(&word.value, self.effective_normalization() == Normalization::FoldDiacritics);
+            assert!(map_can_be_constructed(&letters, list),
+                "search returned {:?}, which cannot be constructed from the list", word.value);
+            assert!(self.accepts(word) && (self.search_min_len(), self.max_len).contains(&word_len(&letters)),
+                "search returned {:?}, which is outside the view", word.value);
+        }
+    }
+
+    fn effective_normalization(&self) -> Normalization {
+        self.normalization.unwrap_or_else(|| self.dictionary.normalization())
+    }
+
+    /// Returns `true` if the view counts letters with another normalization than its dictionary.
+    fn overrides_normalization(&self) -> bool {
+        self.effective_normalization() != self.dictionary.normalization()
+    }
+
+    /// Lower length bound of searches, the crate's minimum word length if the view has none.
+    fn search_min_len(&self) -> Bound<usize> {
+        match self.min_len {
+            Bound::Unbounded => Bound::Included(DEFAULT_MIN_WORD_LEN),
+            bound => bound,
+        }
+    }
+
+    fn accepts(&self, word: &Word) -> bool {
+        let first_letter_matches = self.first_letter.is_none_or(|letter| {
+            word.value.chars().next().map(|c| c.to_ascii_lowercase()) == Some(letter)
+        });
+        first_letter_matches && self.filters.iter().all(|filter| filter(word))
+    }
+}
+
+impl IndexBuild {
+    /// Returns `true` once every index has been built.
+    pub fn is_ready(&self) -> bool {
+        self.dictionary.indexes_ready()
+    }
+
+    /// Stops the build before the next index. An index already being built is finished, and the
+    /// indexes that were not built will be built on first use.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Waits for the build to finish or stop, returning `true` if every index was built.
+    pub fn wait(mut self) -> bool {
+        if let Some(thread) = self.thread.take() {
+            thread.join().expect("index build thread panicked");
+        }
+        self.is_ready()
+    }
+}
+
+/// Adds to `chains` every chain extending `chain` with up to `depth` of the candidates, the
+/// words that `list`, the letters left by the chain, can make.
+fn extend_chains<'a>(candidates: &[(&'a str, LetterCounts)], list: &LetterCounts, depth: usize, chain: &mut Vec<&'a str>, chains: &mut Vec<Vec<&'a str>>) {
+    if depth == 0 { return; }
+    for (word, counts) in candidates {
+        let left = list.checked_sub(counts).expect("candidates can be made from the letters left");
+        chain.push(word);
+        chains.push(chain.clone());
+        let next: Vec<(&'a str, LetterCounts)> = candidates.iter().filter(|(_, counts)| can_be_constructed(counts, &left)).copied().collect();
+        extend_chains(&next, &left, depth - 1, chain, chains);
+        chain.pop();
+    }
+}
+
+impl Normalization {
+    /// Counts of the letters of a word or a list of letters.
+    pub fn counts(self, value: &str) -> LetterCounts {
+        match self {
+            Normalization::Ascii => LetterCounts::from_word::<English>(value),
+            Normalization::FoldDiacritics => LetterCounts::from_word::<Folded>(value),
+        }
+    }
+}
+
+/// Character count mapping of a word, its diacritics folded if `fold` is `true`.
+fn letters_of(value: &str, fold: bool) -> HashMap<char, u8> {
+    if fold { Word::folded(value).letters } else { get_letters_count(value) }
+}
+
+/// Lowercase ASCII letters of a word, as keyed in the trie.
+fn letters_key(word: &str) -> String {
+    lowercase_letters(word).collect()
+}
+
+/// Dictionaries are equal when they have the same words in the same order, whichever of their
+/// indexes have been built.
+impl PartialEq for Dictionary {
+    fn eq(&self, other: &Self) -> bool {
+        self.words == other.words
+    }
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Dictionary::new(vec![])
+    }
+}
+
+impl From<Vec<Word>> for Dictionary {
+    fn from(words: Vec<Word>) -> Self {
+        Dictionary::new(words)
+    }
+}
+
+/// A dictionary serializes as the sequence of its words, without its indexes.
+#[cfg(feature = "serde")]
+impl Serialize for Dictionary {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.words.serialize(serializer)
+    }
+}
+
+/// A dictionary deserializes from a sequence of words, building its indexes again.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Dictionary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Word>::deserialize(deserializer).map(Dictionary::new)
+    }
+}
+
+impl Fingerprint {
+    /// 64-bit FNV-1a hash of the words, each followed by a byte that cannot appear in UTF-8.
+    pub(crate) fn of(words: &[Word]) -> Fingerprint {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+        let hash = words.iter()
+            .flat_map(|word| word.value.bytes().chain([0xff]))
+            .fold(OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(PRIME));
+        Fingerprint(hash)
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl WordId {
+    fn new(fingerprint: Fingerprint, position: usize) -> Self {
+        let tag = (fingerprint.0 >> 32) ^ (fingerprint.0 & 0xffff_ffff);
+        WordId(tag << 32 | position as u64)
+    }
+
+    /// Position of the word in the dictionary.
+    pub fn position(self) -> usize {
+        (self.0 & 0xffff_ffff) as usize
+    }
+
+    fn tag(self) -> u64 {
+        self.0 >> 32
+    }
+}
+
+impl fmt::Display for WordId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<QueryTrace> for SearchStats {
+    fn from(trace: QueryTrace) -> Self {
+        SearchStats { words: trace.words, candidates: trace.candidates, matches: trace.matches }
+    }
+}
+
+/// Bytes of a word with ASCII letters lowercased, in the order of [`Dictionary::range`].
+fn folded(word: &str) -> impl Iterator<Item=u8> + '_ {
+    word.bytes().map(|byte| byte.to_ascii_lowercase())
+}
+
+impl fmt::Display for QueryTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} words, {} of a possible length, {} candidates in {} buckets, {} matches; ",
+            self.words, self.length_candidates, self.candidates, self.buckets, self.matches)?;
+        write!(f, "parse {}µs, check {}µs, merge {}µs, verify {}µs",
+            self.parse.as_micros(), self.check.as_micros(), self.merge.as_micros(), self.verify.as_micros())
+    }
+}
+
+#[cfg(test)]
+mod dictionary_tests {
+    use std::collections::BTreeMap;
+    use std::ops::Bound;
+    use std::sync::Arc;
+
+    use crate::{get_constructable_words, get_letters_count, test_words, FindWordsError, ParseError, Rack, Word};
+
+    use super::{Dictionary, Fingerprint, Normalization, SearchStats, UsageStats, WordId};
+
+    fn dictionary() -> Dictionary {
+        test_words(&["a", "at", "cat", "act", "tact", "dog"]).into()
+    }
+
+    #[test]
+    fn find() {
+        assert_eq!(dictionary().find("tac"), vec!["at", "cat", "act"]);
+        assert_eq!(dictionary().view().lengths(1..).find("tac"), vec!["a", "at", "cat", "act"]);
+    }
+
+    #[test]
+    fn find_can_be_repeated() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.find("ttac"), vec!["at", "cat", "act", "tact"]);
+        assert_eq!(dictionary.find("god"), vec!["dog"]);
+        assert!(dictionary.find("").is_empty());
+    }
+
+    #[test]
+    fn try_find() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.try_find("T a c").unwrap(), vec!["at", "cat", "act"]);
+        assert!(matches!(dictionary.try_find("ta3"), Err(FindWordsError::InvalidLetters(ParseError::InvalidChar { c: '3', position: 2 }))));
+        assert!(matches!(dictionary.try_find(""), Err(FindWordsError::InvalidLetters(ParseError::Empty))));
+    }
+
+    #[test]
+    fn diacritic_folding() {
+        let mut dictionary = Dictionary::new(test_words(&["élève", "elle", "Noël", "lev"]));
+        assert_eq!(dictionary.find("elv"), vec!["élève", "lev"]);
+        dictionary.set_fold_diacritics(true);
+        assert!(dictionary.folds_diacritics());
+        assert_eq!(dictionary.find("elv"), vec!["lev"]);
+        assert_eq!(dictionary.find("EVELE"), vec!["élève", "lev"]);
+        assert_eq!(dictionary.find("élève"), vec!["élève", "lev"]);
+        assert_eq!(dictionary.find("lone"), vec!["Noël"]);
+        assert_eq!(dictionary.anagrams("veele"), vec!["élève"]);
+        dictionary.set_fold_diacritics(false);
+        assert_eq!(dictionary.find("elv"), vec!["élève", "lev"]);
+    }
+
+    #[test]
+    fn normalization_per_query() {
+        let mut dictionary = Dictionary::new(test_words(&["élève", "elle", "Noël", "lev"]));
+        let generation = dictionary.generation();
+        let folded = || dictionary.view().normalization(Normalization::FoldDiacritics);
+        assert_eq!(folded().find("EVELE"), vec!["élève", "lev"]);
+        assert_eq!(folded().find("elv"), vec!["lev"]);
+        assert_eq!(folded().find_longest("eleven", 1), vec!["élève"]);
+        assert_eq!(dictionary.find("elv"), vec!["élève", "lev"]);
+        assert_eq!(dictionary.generation(), generation);
+
+        dictionary.set_fold_diacritics(true);
+        assert_eq!(dictionary.view().normalization(Normalization::Ascii).find("elv"), vec!["élève", "lev"]);
+        assert_eq!(dictionary.view().normalization(Normalization::FoldDiacritics).find("elv"), vec!["lev"]);
+    }
+
+    #[test]
+    fn words_of_length() {
+        let dictionary = dictionary();
+        let output: Vec<&str> = dictionary.words_of_length(3).map(|word| word.value.as_str()).collect();
+        assert_eq!(output, vec!["cat", "act", "dog"]);
+        assert_eq!(dictionary.words_of_length(10).count(), 0);
+    }
+
+    #[test]
+    fn short_word_lists() {
+        let mut dictionary = dictionary();
+        assert_eq!(dictionary.two_letter_words(None), vec!["at"]);
+        assert_eq!(dictionary.three_letter_words(None), vec!["cat", "act", "dog"]);
+        assert_eq!(dictionary.three_letter_words(Some(&"tac".parse().unwrap())), vec!["cat", "act"]);
+        assert_eq!(dictionary.three_letter_words(Some(&"do?".parse().unwrap())), vec!["dog"]);
+        dictionary.retain(|word| word.value != "cat");
+        assert_eq!(dictionary.three_letter_words(None), vec!["act", "dog"]);
+    }
+
+    #[test]
+    fn retain_updates_indexes() {
+        let mut dictionary = dictionary();
+        dictionary.retain(|word| word.value.len() >= 3);
+        assert_eq!(dictionary.len(), 4);
+        assert_eq!(dictionary.words_of_length(1).count(), 0);
+        assert_eq!(dictionary.words_of_length(4).count(), 1);
+        assert_eq!(dictionary.find("ttac"), vec!["cat", "act", "tact"]);
+    }
+
+    #[test]
+    fn heavy_indexes_are_built_on_demand() {
+        let mut dictionary = dictionary();
+        assert_eq!(dictionary.find("ttac"), vec!["at", "cat", "act", "tact"]);
+        assert!(dictionary.trie.get().is_none() && dictionary.suffixes.get().is_none());
+        assert!(dictionary.trie().contains("tact"));
+        assert_eq!(dictionary.words_ending_with("at"), vec!["at", "cat"]);
+        assert!(dictionary.trie.get().is_some() && dictionary.suffixes.get().is_some());
+        dictionary.retain(|word| word.value != "tact");
+        assert!(dictionary.trie.get().is_none());
+        assert!(!dictionary.trie().contains("tact"));
+    }
+
+    #[test]
+    fn lists_beyond_the_count_cap() {
+        let dictionary = dictionary();
+        let list = format!("{} dgo", "Tac ".repeat(1000));
+        assert_eq!(dictionary.find(&list), vec!["at", "cat", "act", "tact", "dog"]);
+        assert_eq!(dictionary.find(&list), get_constructable_words(dictionary.words().to_vec(), &get_letters_count(&list)));
+        assert_eq!(dictionary.find_with_wildcards(&format!("{}?", "a".repeat(300)), 1), vec!["at"]);
+        let rack: Rack = format!("{}c", "ta".repeat(300)).parse().unwrap();
+        assert_eq!(dictionary.find_rack(&rack), vec!["at", "cat", "act", "tact"]);
+    }
+
+    #[test]
+    fn prepared_for_sharing() {
+        let mut dictionary = dictionary();
+        dictionary.set_usage_tracking(true);
+        assert!(!dictionary.is_read_only());
+        dictionary.build_indexes();
+        assert!(dictionary.indexes_ready() && !dictionary.is_read_only());
+        dictionary.prepare_for_sharing();
+        assert!(dictionary.is_read_only() && dictionary.alternate_counts.get().is_some());
+        assert_eq!(dictionary.find("ttac"), vec!["at", "cat", "act", "tact"]);
+        assert!(dictionary.stats_snapshot().is_none());
+        dictionary.retain(|word| word.value != "tact");
+        assert!(!dictionary.is_read_only());
+    }
+
+    #[test]
+    fn indexes_built_in_background() {
+        let dictionary = Arc::new(dictionary());
+        assert!(!dictionary.indexes_ready());
+        let build = dictionary.build_indexes_in_background();
+        assert_eq!(dictionary.find("ttac"), vec!["at", "cat", "act", "tact"]);
+        assert!(build.wait());
+        assert!(dictionary.indexes_ready());
+    }
+
+    #[test]
+    fn cancelled_background_build() {
+        let dictionary = Arc::new(dictionary());
+        let build = dictionary.build_indexes_in_background();
+        build.cancel();
+        build.wait();
+        assert!(dictionary.trie().contains("tact"));
+        assert_eq!(dictionary.words_ending_with("og"), vec!["dog"]);
+        assert_eq!(dictionary.anagrams("god"), vec!["dog"]);
+        assert!(dictionary.indexes_ready());
+    }
+
+    #[test]
+    fn generations() {
+        let mut first = dictionary();
+        let generation = first.generation();
+        assert_ne!(dictionary().generation(), generation);
+        assert_eq!(first.clone().generation(), generation);
+        first.retain(|word| word.value != "dog");
+        assert_ne!(first.generation(), generation);
+    }
+
+    #[test]
+    fn fingerprints() {
+        let mut dictionary = dictionary();
+        let fingerprint = dictionary.fingerprint();
+        assert_eq!(Dictionary::from(test_words(&["a", "at", "cat", "act", "tact", "dog"])).fingerprint(), fingerprint);
+        assert_eq!(Dictionary::default().fingerprint(), Fingerprint(0xcbf2_9ce4_8422_2325));
+        assert_eq!(Dictionary::from(test_words(&["a"])).fingerprint().to_string(), "089bc907b544c769");
+        dictionary.retain(|word| word.value != "dog");
+        assert_ne!(dictionary.fingerprint(), fingerprint);
+    }
+
+    #[test]
+    fn contains() {
+        let mut dictionary = dictionary();
+        assert!(dictionary.contains("TACT"));
+        assert!(!dictionary.contains("ta"));
+        dictionary.set_prefilter(Some(0.01));
+        assert_eq!(dictionary.prefilter().map(|filter| filter.len()), Some(6));
+        assert!(dictionary.contains("TACT"));
+        assert!(!dictionary.contains("ta"));
+        dictionary.retain(|word| word.value != "tact");
+        assert!(!dictionary.contains("tact"));
+        assert_eq!(dictionary.prefilter().map(|filter| filter.len()), Some(5));
+        dictionary.set_prefilter(None);
+        assert!(dictionary.prefilter().is_none());
+    }
+
+    #[test]
+    fn retain_everything_removed() {
+        let mut dictionary = dictionary();
+        dictionary.retain(|_| false);
+        assert!(dictionary.is_empty());
+        assert!(dictionary.find("ttac").is_empty());
+    }
+
+    #[cfg(all(feature = "embedded-dict", feature = "fs"))]
+    #[test]
+    fn builtin_words_are_the_word_list() {
+        let builtin = Dictionary::builtin();
+        assert_eq!(builtin.words(), Dictionary::from_file("words.txt").unwrap().words());
+        assert!(builtin.find("tac").contains(&"cat"));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn file_does_not_exist() {
+        assert!(Dictionary::from_file("invalid_file.txt").is_err());
+    }
+
+    #[test]
+    fn find_with_stats_skips_buckets() {
+        let dictionary = dictionary();
+        let (output, stats) = dictionary.find_with_stats("god");
+        assert_eq!(output, vec!["dog"]);
+        assert_eq!(stats.words, 6);
+        assert_eq!(stats.candidates, 1);
+        assert_eq!(stats.matches, 1);
+    }
+
+    #[test]
+    fn find_embedded_words() {
+        let found = dictionary().find_embedded_words("Tact, dog!", 2);
+        let output: Vec<(&str, usize, usize)> = found.iter()
+            .map(|found| (found.word.as_str(), found.start, found.end))
+            .collect();
+        assert_eq!(output, vec![("tact", 0, 4), ("act", 1, 4), ("dog", 6, 9)]);
+    }
+
+    #[test]
+    fn find_embedded_words_across_word_boundaries() {
+        let dictionary: Dictionary = test_words(&["brat", "tic"]).into();
+        let found = dictionary.find_embedded_words("the bra-tactic", 3);
+        let output: Vec<(&str, usize, usize)> = found.iter()
+            .map(|found| (found.word.as_str(), found.start, found.end))
+            .collect();
+        assert_eq!(output, vec![("brat", 4, 9), ("tic", 11, 14)]);
+    }
+
+    #[test]
+    fn position_frequencies() {
+        let frequencies = dictionary().position_frequencies(3);
+        assert_eq!(frequencies.len(), 3);
+        assert_eq!(frequencies[0][2], 1);
+        assert_eq!(frequencies[0][0], 1);
+        assert_eq!(frequencies[0][3], 1);
+        assert_eq!(frequencies[1][0], 1);
+        assert_eq!(frequencies[2][19], 2);
+        assert!(frequencies.iter().all(|position| position.iter().sum::<usize>() == 3));
+    }
+
+    #[test]
+    fn letter_frequencies() {
+        let frequencies = dictionary().letter_frequencies();
+        assert_eq!(frequencies.total(), dictionary().words().iter().map(|word| word.value.len() as u64).sum::<u64>());
+        assert_eq!(frequencies.count(25), 0);
+    }
+
+    #[test]
+    fn position_frequencies_count_letters_only() {
+        let dictionary: Dictionary = test_words(&["co-op", "Loop"]).into();
+        let frequencies = dictionary.position_frequencies(4);
+        assert_eq!(frequencies[2][14], 2);
+        assert_eq!(frequencies[0][11], 1);
+        assert!(dictionary.position_frequencies(0).is_empty());
+    }
+
+    #[test]
+    fn starting_with() {
+        let dictionary: Dictionary = test_words(&["cat", "Act", "at", "-ab", "Cab", "a", "dog"]).into();
+        let values = |letter| dictionary.starting_with(letter).iter().map(|word| word.value.as_str()).collect::<Vec<_>>();
+        assert_eq!(values('A'), vec!["a", "Act", "at"]);
+        assert_eq!(values('c'), vec!["Cab", "cat"]);
+        assert!(values('b').is_empty());
+        assert!(values('-').is_empty());
+    }
+
+    #[test]
+    fn word_ids() {
+        let dictionary: Dictionary = test_words(&["cat", "Cat", "act", "c-a-t", ""]).into();
+        let ids: Vec<WordId> = ["cat", "Cat", "act", "c-a-t"].iter().map(|word| dictionary.word_id(word).unwrap()).collect();
+        assert_eq!(ids.iter().map(|id| id.position()).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(dictionary.word_by_id(ids[1]).map(|word| word.value.as_str()), Some("Cat"));
+        assert_eq!((dictionary.word_id("CAT"), dictionary.word_id("dog"), dictionary.word_id("")), (None, None, None));
+        let same: Dictionary = test_words(&["cat", "Cat", "act", "c-a-t", ""]).into();
+        assert_eq!(same.word_id("act"), Some(ids[2]));
+        let other: Dictionary = test_words(&["cat", "dog"]).into();
+        assert_eq!(other.word_by_id(ids[0]), None);
+        assert_eq!(dictionary.word_by_id(WordId(ids[0].0 + 10)), None);
+    }
+
+    #[test]
+    fn ranges() {
+        fn values<'a>(words: impl Iterator<Item=&'a Word>) -> Vec<&'a str> {
+            words.map(|word| word.value.as_str()).collect()
+        }
+        let dictionary: Dictionary = test_words(&["cat", "Car", "card", "cart", "-ab", "at", "cab", "dog", "CAT"]).into();
+        assert_eq!(values(dictionary.range("car".."cat")), vec!["Car", "card", "cart"]);
+        assert_eq!(values(dictionary.range("CAR"..="cat")), vec!["Car", "card", "cart", "CAT", "cat"]);
+        assert_eq!(values(dictionary.range(.."b")), vec!["-ab", "at"]);
+        assert_eq!(values(dictionary.range((Bound::Excluded("cat"), Bound::Unbounded))), vec!["dog"]);
+        assert_eq!(dictionary.range("d".."c").len(), 0);
+        assert_eq!(values(dictionary.range(..).skip(3).take(2)), vec!["Car", "card"]);
+        assert_eq!(values(dictionary.with_prefix("CAR")), vec!["Car", "card", "cart"]);
+        assert_eq!(values(dictionary.with_prefix("").rev().take(1)), vec!["dog"]);
+        assert_eq!(dictionary.with_prefix("z").len(), 0);
+    }
+
+    #[test]
+    fn completions() {
+        let mut words = test_words(&["carton", "cart", "car", "Cat", "c-a-t", "cathedral", "dog", "ca"]);
+        assert_eq!(Dictionary::new(words.clone()).complete("CA", 4), vec!["ca", "car", "Cat", "c-a-t"]);
+        words[0].rank = Some(1);
+        words[5].rank = Some(20);
+        words[3].rank = Some(20);
+        let dictionary = Dictionary::new(words);
+        assert_eq!(dictionary.complete("ca", 4), vec!["carton", "Cat", "cathedral", "ca"]);
+        assert_eq!(dictionary.complete("cat", 10), vec!["Cat", "cathedral", "c-a-t"]);
+        assert_eq!(dictionary.complete("", 2), vec!["carton", "Cat"]);
+        assert!(dictionary.complete("x", 5).is_empty() && dictionary.complete("ca", 0).is_empty());
+    }
+
+    #[test]
+    fn word_chains() {
+        let dictionary = dictionary();
+        let chains: Vec<String> = dictionary.find_word_chains("tacat", 2).iter().map(|chain| chain.join(" ")).collect();
+        assert_eq!(chains, vec!["at", "at at", "at cat", "at act", "cat", "cat at", "act", "act at", "tact"]);
+        assert_eq!(dictionary.find_word_chains("tacat", 1), dictionary.find("tacat").into_iter().map(|word| vec![word]).collect::<Vec<_>>());
+        assert!(dictionary.find_word_chains("tacat", 0).is_empty());
+        assert!(dictionary.view().lengths(3..).find_word_chains("tacat", 3).iter().all(|chain| chain.len() == 1));
+    }
+
+    #[test]
+    fn wildcards() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.find_with_wildcards("ta?", 1), dictionary.find_rack(&"ta?".parse().unwrap()));
+        assert_eq!(dictionary.find_with_wildcards("t*", 2), vec!["at"]);
+        assert_eq!(dictionary.find_with_wildcards("t**", 1), vec!["at"]);
+        assert_eq!(dictionary.find_with_wildcards("t**", 2), vec!["at", "cat", "act"]);
+        assert_eq!(dictionary.find_with_wildcards("tca?", 0), dictionary.find("tca"));
+    }
+
+    #[test]
+    fn anagrams() {
+        let dictionary: Dictionary = test_words(&["cat", "Act", "tact", "a-ct", "123", "dog"]).into();
+        assert_eq!(dictionary.anagrams("TCA"), vec!["cat", "Act", "a-ct"]);
+        assert!(dictionary.anagrams("tc").is_empty());
+        assert!(dictionary.anagrams("").is_empty());
+        assert!(dictionary.anagrams("321").is_empty());
+    }
+
+    #[test]
+    fn subanagrams() {
+        let dictionary: Dictionary = test_words(&["a", "tact", "cat", "at", "a-ct", "dog"]).into();
+        let groups = dictionary.subanagrams("TACT");
+        assert_eq!(groups, BTreeMap::from([(2, vec!["at"]), (3, vec!["cat", "a-ct"]), (4, vec!["tact"])]));
+        assert!(dictionary.subanagrams("xyz").is_empty());
+    }
+
+    #[test]
+    fn words_ending_with() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.words_ending_with("AT"), vec!["at", "cat"]);
+        assert_eq!(dictionary.words_ending_with("ct"), vec!["act", "tact"]);
+    }
+
+    #[test]
+    fn extensions_of() {
+        let dictionary: Dictionary = test_words(&["conquest", "quest", "Question", "quests", "request", "quiet"]).into();
+        let extensions = dictionary.extensions_of("QUEST");
+        let extensions: Vec<(&str, &str, &str)> = extensions.iter()
+            .map(|extension| (extension.word, extension.front.as_str(), extension.back.as_str()))
+            .collect();
+        assert_eq!(extensions, vec![
+            ("Question", "", "ion"),
+            ("quests", "", "s"),
+            ("conquest", "con", ""),
+            ("request", "re", ""),
+        ]);
+        assert!(dictionary.extensions_of("quiet").is_empty());
+        assert!(dictionary.extensions_of("").is_empty());
+    }
+
+    #[test]
+    fn rhymes() {
+        let dictionary: Dictionary = test_words(&["nation", "lion", "station", "ration", "cat", "Nation"]).into();
+        assert_eq!(dictionary.rhymes("nation", 2), vec!["station", "ration", "lion"]);
+        assert_eq!(dictionary.rhymes("nation", 4), vec!["station", "ration"]);
+        assert!(dictionary.rhymes("on", 3).is_empty());
+    }
+
+    #[test]
+    fn usage_tracking() {
+        let mut dictionary = dictionary();
+        dictionary.find("tac");
+        assert_eq!(dictionary.stats_snapshot(), None);
+        dictionary.set_usage_tracking(true);
+        let dictionary = Arc::new(dictionary);
+        let threads: Vec<_> = (0..4).map(|_| {
+            let dictionary = Arc::clone(&dictionary);
+            std::thread::spawn(move || { dictionary.find("tac"); })
+        }).collect();
+        threads.into_iter().for_each(|thread| thread.join().unwrap());
+        dictionary.find_rack(&"t?".parse().unwrap());
+        let stats = dictionary.stats_snapshot().unwrap();
+        assert_eq!((stats.queries, stats.cache_hits), (5, 0));
+        let candidates = dictionary.find_with_stats("tac").1.candidates as u64;
+        assert_eq!(dictionary.stats_snapshot().unwrap().candidates, stats.candidates + candidates);
+        assert!(stats.average_candidates() > 0.0);
+        assert_eq!(UsageStats::default().average_candidates(), 0.0);
+    }
+
+    #[test]
+    fn retain_updates_suffix_index() {
+        let mut dictionary = dictionary();
+        dictionary.retain(|word| word.value != "cat");
+        assert_eq!(dictionary.words_ending_with("at"), vec!["at"]);
+    }
+
+    #[test]
+    fn find_with_stats_skips_long_words() {
+        let dictionary = dictionary();
+        let (output, stats) = dictionary.find_with_stats("ta");
+        assert_eq!(output, vec!["at"]);
+        assert_eq!(stats.candidates, 1);
+    }
+
+    #[test]
+    fn longest_first() {
+        let dictionary: Dictionary = test_words(&["a", "at", "cat", "tact", "act", "ta", "dog", "attach"]).into();
+        assert_eq!(dictionary.find_longest("tacta", 3), vec!["tact", "cat", "act"]);
+        assert_eq!(dictionary.find_longest("tacta", 10), vec!["tact", "cat", "act", "at", "ta"]);
+        assert!(dictionary.find_longest("tacta", 0).is_empty());
+        let (output, trace) = dictionary.view().find_longest_with_trace("tacta", 1);
+        assert_eq!(output, vec!["tact"]);
+        assert_eq!((trace.buckets, trace.matches), (1, 1));
+        let (_, full) = dictionary.find_with_trace("tacta");
+        assert!(full.buckets > trace.buckets);
+        assert_eq!(dictionary.view().lengths(..=3).find_longest("tacta", 1), vec!["cat"]);
+    }
+
+    #[test]
+    fn query_trace() {
+        let dictionary = dictionary();
+        let (output, trace) = dictionary.find_with_trace("tac");
+        assert_eq!(output, dictionary.find("tac"));
+        assert_eq!((trace.words, trace.matches), (dictionary.len(), output.len()));
+        assert!(trace.matches <= trace.candidates && trace.candidates <= trace.length_candidates);
+        assert_eq!(SearchStats::from(trace), dictionary.find_with_stats("tac").1);
+        assert!(trace.to_string().starts_with(&format!("{} words, {} of a possible length", trace.words, trace.length_candidates)));
+    }
+}
+
+#[cfg(test)]
+mod dictionary_view_tests {
+    use crate::{test_words, Word};
+
+    use super::Dictionary;
+
+    fn dictionary() -> Dictionary {
+        test_words(&["a", "at", "cat", "act", "tact", "Cab", "dog"]).into()
+    }
+
+    fn values(words: Vec<&Word>) -> Vec<&str> {
+        words.into_iter().map(|word| word.value.as_str()).collect()
+    }
+
+    #[test]
+    fn whole_dictionary() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.view().len(), dictionary.len());
+        assert_eq!(dictionary.view().find("tac"), dictionary.find("tac"));
+    }
+
+    #[test]
+    fn rack_search() {
+        let dictionary = dictionary();
+        let rack = "ta?".parse().unwrap();
+        assert_eq!(dictionary.find_rack(&rack), vec!["at", "cat", "act"]);
+        assert_eq!(dictionary.view().lengths(1..).find_rack(&rack), vec!["a", "at", "cat", "act"]);
+        assert_eq!(dictionary.view().lengths(3..).find_rack(&rack), vec!["cat", "act"]);
+        assert_eq!(dictionary.find_rack(&"tac".parse().unwrap()), dictionary.find("tac"));
+    }
+
+    #[test]
+    fn verified_search() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.view().lengths(3..).verify(true).find("tacb"), vec!["cat", "act", "Cab"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be constructed")]
+    fn verification_catches_stale_index() {
+        let mut dictionary = dictionary();
+        dictionary.words[2].value = "dog".to_string();
+        dictionary.view().verify(true).find("cat");
+    }
+
+    #[test]
+    fn length_range() {
+        let dictionary = dictionary();
+        assert_eq!(values(dictionary.view().lengths(2..4).words()), vec!["at", "cat", "act", "Cab", "dog"]);
+        assert_eq!(values(dictionary.view().lengths(4..).words()), vec!["tact"]);
+        assert_eq!(dictionary.view().lengths(3..=3).find("ttac"), vec!["cat", "act"]);
+        assert!(dictionary.view().lengths(5..=10).is_empty());
+        assert!(dictionary.view().lengths(..0).is_empty());
+    }
+
+    #[test]
+    fn starting_with() {
+        let dictionary = dictionary();
+        assert_eq!(values(dictionary.view().starting_with('c').words()), vec!["cat", "Cab"]);
+        assert_eq!(dictionary.view().starting_with('C').find("tacb"), vec!["cat", "Cab"]);
+    }
+
+    #[test]
+    fn filters_are_combined() {
+        let dictionary = dictionary();
+        let view = dictionary.view()
+            .filter(|word| word.value.contains('t'))
+            .filter(|word| word.value.contains('c'));
+        assert_eq!(values(view.words()), vec!["cat", "act", "tact"]);
+        assert_eq!(view.lengths(..4).find("ttac"), vec!["cat", "act"]);
+    }
+
+    #[test]
+    fn acrostic_candidates() {
+        let dictionary = dictionary();
+        let expected: Vec<Vec<&str>> = vec![vec!["dog"], vec!["a", "at", "act"], vec!["tact"]];
+        assert_eq!(dictionary.view().acrostic_candidates("D-at"), expected);
+    }
+
+    #[test]
+    fn acrostics() {
+        let dictionary = dictionary();
+        let expected: Vec<Vec<&str>> = vec![vec!["cat", "a", "tact"], vec!["cat", "at", "tact"]];
+        assert_eq!(dictionary.view().acrostics("cat", 2), expected);
+        assert_eq!(dictionary.view().acrostics("cat", 10).len(), 6);
+    }
+
+    #[test]
+    fn acrostics_do_not_repeat_words() {
+        let dictionary = dictionary();
+        assert!(dictionary.view().acrostics("dd", 10).is_empty());
+        assert!(dictionary.view().acrostics("xyz", 10).is_empty());
+        assert!(dictionary.view().acrostics("", 10).is_empty());
+    }
+
+    #[test]
+    fn acrostics_restricted_by_view() {
+        let dictionary = dictionary();
+        let view = dictionary.view().lengths(3..);
+        let expected: Vec<Vec<&str>> = vec![vec!["cat", "act", "tact"], vec!["Cab", "act", "tact"]];
+        assert_eq!(view.acrostics("cat", 10), expected);
+    }
+}
+
+#[cfg(all(test, feature = "fs"))]
+mod dictionary_cache_tests {
+    use std::env;
+    use std::fs;
+
+    use crate::{test_words, FindWordsError};
+
+    use super::Dictionary;
+
+    #[test]
+    fn save_and_load() {
+        let mut words = test_words(&["cat", "act", "Éclair"]);
+        words[0].rank = Some(2);
+        let dictionary = Dictionary::new(words);
+        let path = env::temp_dir().join(format!("find-words-cache-{}.bin", std::process::id()));
+        let path = path.to_str().unwrap();
+        dictionary.save_cache(path).unwrap();
+        let loaded = Dictionary::load_cache(path).unwrap();
+        assert_eq!(loaded, dictionary);
+        assert_eq!(loaded.words()[0].rank, Some(2));
+        assert_eq!(loaded.find("tca"), vec!["cat", "act"]);
+
+        let bytes = fs::read(path).unwrap();
+        fs::write(path, &bytes[..bytes.len() - 3]).unwrap();
+        assert!(matches!(Dictionary::load_cache(path), Err(FindWordsError::InvalidCache { .. })));
+        fs::remove_file(path).unwrap();
+        assert!(matches!(Dictionary::load_cache(path), Err(FindWordsError::Io { .. })));
+    }
+
+    #[test]
+    fn empty_word_list() {
+        let path = env::temp_dir().join(format!("find-words-empty-{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, "").unwrap();
+        assert!(matches!(Dictionary::from_file(path), Err(FindWordsError::EmptyDictionary { .. })));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let dictionary = Dictionary::new(test_words(&["aa"]));
+        let json = serde_json::to_string(&dictionary).unwrap();
+        assert_eq!(json, r#"[{"value":"aa","letters":{"a":2},"rank":null}]"#);
+        let parsed: Dictionary = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, dictionary);
+        assert_eq!(parsed.find("aab"), vec!["aa"]);
+    }
+}
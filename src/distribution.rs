@@ -0,0 +1,155 @@
+//! Letter distributions inferred from the words of a dictionary.
+//!
+//! Counting how often every letter appears in a word list gives the proportions of a tile bag
+//! for a language without an established one: [`LetterFrequencies::tile_counts`] turns the counts
+//! into a number of tiles per letter, and the frequencies can generate random letters or, for
+//! English, fill a [`TileBag`] directly.
+
+use std::cmp::Reverse;
+
+#[cfg(feature = "random")]
+use rand::distributions::WeightedIndex;
+#[cfg(feature = "random")]
+use rand::prelude::*;
+
+use crate::alphabet::{Alphabet, English};
+use crate::game::TileBag;
+use crate::{Tile, Word};
+
+/// Number of occurrences of every letter of an alphabet in a list of words.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LetterFrequencies<const N: usize = 26> {
+    counts: [u64; N],
+}
+
+impl<const N: usize> LetterFrequencies<N> {
+    /// Frequencies of given counts, e.g. the tile counts of a [`TileScheme`](crate::scheme::TileScheme).
+    pub fn from_counts(counts: [u64; N]) -> Self {
+        LetterFrequencies { counts }
+    }
+
+    /// Counts the letters of the words, ignoring characters that are not part of the alphabet.
+    pub fn from_words<A: Alphabet<N>>(words: &[Word]) -> Self {
+        let mut counts = [0; N];
+        for index in words.iter().flat_map(|word| word.value.chars().filter_map(A::index)) {
+            counts[index] += 1;
+        }
+        LetterFrequencies { counts }
+    }
+
+    /// Occurrences of the letter at an index of the alphabet.
+    pub fn count(&self, index: usize) -> u64 {
+        self.counts[index]
+    }
+
+    /// Occurrences of every letter.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Fraction of the occurrences that are of the letter at an index, 0 without any letter.
+    pub fn share(&self, index: usize) -> f64 {
+        let total = self.total();
+        if total == 0 { return 0.0; }
+        self.counts[index] as f64 / total as f64
+    }
+
+    /// Number of tiles of each letter in a bag of `bag_size` tiles. Every letter that appears gets
+    /// at least one tile while the bag has room, the most frequent first, and the other tiles are
+    /// shared in proportion to the counts, rounding by largest remainder.
+    pub fn tile_counts(&self, bag_size: usize) -> [usize; N] {
+        let mut tiles = [0; N];
+        let mut used: Vec<usize> = (0..N).filter(|&index| self.counts[index] > 0).collect();
+        used.sort_by_key(|&index| Reverse(self.counts[index]));
+        for &index in used.iter().take(bag_size) {
+            tiles[index] = 1;
+        }
+        let remaining = bag_size.saturating_sub(used.len()) as u64;
+        let total = self.total();
+        if remaining == 0 { return tiles; }
+
+        let mut remainders: Vec<(u64, usize)> = vec![];
+        let mut shared = 0;
+        for &index in &used {
+            let quota = self.counts[index] * remaining;
+            tiles[index] += (quota / total) as usize;
+            shared += quota / total;
+            remainders.push((quota % total, index));
+        }
+        remainders.sort_by_key(|&(remainder, index)| (Reverse(remainder), index));
+        for &(_, index) in remainders.iter().take((remaining - shared) as usize) {
+            tiles[index] += 1;
+        }
+        tiles
+    }
+
+    /// A random string of `len` letters, each drawn independently with a probability proportional
+    /// to its count. Empty if no letter was counted.
+    #[cfg(feature = "random")]
+    pub fn random_letters<A: Alphabet<N>, R: Rng + ?Sized>(&self, rng: &mut R, len: usize) -> String {
+        let Ok(weights) = WeightedIndex::new(self.counts) else { return String::new(); };
+        (0..len).map(|_| A::letter(weights.sample(rng))).collect()
+    }
+}
+
+impl LetterFrequencies<26> {
+    /// A bag of English tiles in the proportions of [`tile_counts`](LetterFrequencies::tile_counts),
+    /// with the usual tile values.
+    pub fn tile_bag(&self, bag_size: usize) -> TileBag {
+        let tiles = self.tile_counts(bag_size)
+            .iter()
+            .enumerate()
+            .flat_map(|(index, &count)| std::iter::repeat_n(Tile::english(English::letter(index)), count))
+            .collect();
+        TileBag::new(tiles)
+    }
+}
+
+#[cfg(test)]
+mod letter_frequencies_tests {
+    #[cfg(feature = "random")]
+    use rand::rngs::StdRng;
+    #[cfg(feature = "random")]
+    use rand::SeedableRng;
+
+    use crate::alphabet::{English, Greek};
+    use crate::test_words;
+
+    use super::LetterFrequencies;
+
+    fn frequencies() -> LetterFrequencies {
+        LetterFrequencies::from_words::<English>(&test_words(&["Aaa", "ab", "a-c"]))
+    }
+
+    #[test]
+    fn counts() {
+        let frequencies = frequencies();
+        assert_eq!((frequencies.count(0), frequencies.count(1), frequencies.count(2)), (5, 1, 1));
+        assert_eq!(frequencies.total(), 7);
+        assert!((frequencies.share(0) - 5.0 / 7.0).abs() < 1e-9);
+        let greek = LetterFrequencies::<24>::from_words::<Greek>(&test_words(&["αβα", "cat"]));
+        assert_eq!((greek.count(0), greek.total()), (2, 3));
+    }
+
+    #[test]
+    fn tile_counts() {
+        let frequencies = frequencies();
+        assert_eq!(frequencies.tile_counts(10)[..3], [6, 2, 2]);
+        assert_eq!(frequencies.tile_counts(100).iter().sum::<usize>(), 100);
+        assert_eq!(frequencies.tile_counts(2)[..3], [1, 1, 0]);
+        assert_eq!(frequencies.tile_counts(0), [0; 26]);
+        assert_eq!(LetterFrequencies::from_words::<English>(&[]).tile_counts(10), [0; 26]);
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn generators() {
+        let frequencies = frequencies();
+        assert_eq!(frequencies.tile_bag(10).len(), 10);
+        let letters = frequencies.random_letters::<English, _>(&mut StdRng::seed_from_u64(1), 50);
+        assert_eq!(letters.len(), 50);
+        assert!(letters.chars().all(|c| "abc".contains(c)));
+        assert!(letters.matches('a').count() > letters.matches('b').count());
+        assert!(LetterFrequencies::from_words::<English>(&[]).random_letters::<English, _>(&mut StdRng::seed_from_u64(1), 5).is_empty());
+    }
+}
@@ -0,0 +1,117 @@
+//! Errors of loading dictionaries and of queries, in a single type.
+//!
+//! [`FindWordsError`] tells the failures apart: a file that cannot be read, with its path, a
+//! file with lines that are not text, a word list without words, a cache that is not valid
+//! and letters that cannot be searched. Functions kept from the first releases, such as
+//! [`read_words_from_file`](crate::read_words_from_file), still return an [`io::Error`], which
+//! holds the [`FindWordsError`] for failures that are not I/O errors, see
+//! [`FindWordsError::from_io`].
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::binary::DecodeError;
+use crate::ParseError;
+
+/// Error returned when loading a dictionary or running a query fails.
+#[derive(Debug)]
+pub enum FindWordsError {
+    /// The file at `path` could not be opened or read.
+    Io { path: String, source: io::Error },
+    /// Line `line` (starting at 1) of the file at `path` is not valid UTF-8.
+    MalformedLine { path: String, line: usize },
+    /// The word list at `path` has no words.
+    EmptyDictionary { path: String },
+    /// The file at `path` is not a valid dictionary cache.
+    InvalidCache { path: String, error: DecodeError },
+    /// The letters of a query cannot be parsed.
+    InvalidLetters(ParseError),
+}
+
+impl FindWordsError {
+    /// The path of the file the error is about, if any.
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            FindWordsError::Io { path, .. }
+            | FindWordsError::MalformedLine { path, .. }
+            | FindWordsError::EmptyDictionary { path }
+            | FindWordsError::InvalidCache { path, .. } => Some(path),
+            FindWordsError::InvalidLetters(_) => None,
+        }
+    }
+
+    /// Recovers the error held by an [`io::Error`] returned by the functions of the first
+    /// releases, or `None` for a plain I/O error.
+    pub fn from_io(error: &io::Error) -> Option<&FindWordsError> {
+        error.get_ref().and_then(|inner| inner.downcast_ref())
+    }
+}
+
+impl fmt::Display for FindWordsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FindWordsError::Io { path, source } => write!(f, "cannot read {}: {}", path, source),
+            FindWordsError::MalformedLine { path, line } => write!(f, "{}: line {} is not valid UTF-8", path, line),
+            FindWordsError::EmptyDictionary { path } => write!(f, "{}: the word list has no words", path),
+            FindWordsError::InvalidCache { path, error } => write!(f, "{}: {}", path, error),
+            FindWordsError::InvalidLetters(error) => write!(f, "invalid letters: {}", error),
+        }
+    }
+}
+
+impl Error for FindWordsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FindWordsError::Io { source, .. } => Some(source),
+            FindWordsError::InvalidCache { error, .. } => Some(error),
+            FindWordsError::InvalidLetters(error) => Some(error),
+            FindWordsError::MalformedLine { .. } | FindWordsError::EmptyDictionary { .. } => None,
+        }
+    }
+}
+
+impl From<ParseError> for FindWordsError {
+    fn from(error: ParseError) -> Self {
+        FindWordsError::InvalidLetters(error)
+    }
+}
+
+/// I/O errors are returned as they were raised, other errors as [`io::ErrorKind::InvalidData`].
+impl From<FindWordsError> for io::Error {
+    fn from(error: FindWordsError) -> Self {
+        match error {
+            FindWordsError::Io { source, .. } => source,
+            error => io::Error::new(io::ErrorKind::InvalidData, error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod find_words_error_tests {
+    use std::io;
+
+    use crate::ParseError;
+
+    use super::FindWordsError;
+
+    #[test]
+    fn io_round_trip() {
+        let error: io::Error = FindWordsError::MalformedLine { path: "words.txt".to_string(), line: 3 }.into();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(FindWordsError::from_io(&error), Some(FindWordsError::MalformedLine { line: 3, .. })));
+        let missing = io::Error::from(io::ErrorKind::NotFound);
+        let error: io::Error = FindWordsError::Io { path: "words.txt".to_string(), source: missing }.into();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(FindWordsError::from_io(&error).is_none());
+    }
+
+    #[test]
+    fn messages() {
+        let error = FindWordsError::from(ParseError::Empty);
+        assert_eq!(error.to_string(), "invalid letters: list of letters is empty");
+        assert_eq!(error.path(), None);
+        let error = FindWordsError::EmptyDictionary { path: "empty.txt".to_string() };
+        assert_eq!((error.to_string().as_str(), error.path()), ("empty.txt: the word list has no words", Some("empty.txt")));
+    }
+}
@@ -0,0 +1,119 @@
+//! Anagram families: the words of a dictionary grouped by alphagram, their letters in
+//! alphabetical order, for word-study lists and statistics.
+//!
+//! Keying, scoring and formatting all run in parallel, so a whole dictionary can be exported
+//! at once. JSON output requires the `serde` feature.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::matches::csv_field;
+use crate::parallel::*;
+use crate::{Dictionary, LetterKey};
+
+/// Words made of the same letters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Family {
+    /// The letters of the family in alphabetical order, lowercased.
+    pub alphagram: String,
+    /// Words of the family, in dictionary order.
+    pub members: Vec<String>,
+    /// Sum of the scores of the members.
+    pub total_score: u32,
+}
+
+impl Family {
+    /// Number of words of the family.
+    pub fn size(&self) -> usize {
+        self.members.len()
+    }
+}
+
+/// Every family of the dictionary, sorted by alphagram. Words without letters are left out.
+pub fn families(dictionary: &Dictionary, scorer: &(dyn Fn(&str) -> u32 + Sync)) -> Vec<Family> {
+    let words = dictionary.words();
+    let keys: Vec<LetterKey> = words.par_iter().map(|word| LetterKey::from_word(&word.value)).collect();
+    let mut groups: HashMap<LetterKey, Vec<usize>> = HashMap::new();
+    for (index, key) in keys.into_iter().enumerate() {
+        groups.entry(key).or_default().push(index);
+    }
+    let mut families: Vec<Family> = groups.into_par_iter()
+        .map(|(key, indexes)| {
+            let members: Vec<String> = indexes.into_iter().map(|index| words[index].value.clone()).collect();
+            let total_score = members.iter().map(|word| scorer(word)).sum();
+            Family { alphagram: key.alphagram(), members, total_score }
+        })
+        .filter(|family| !family.alphagram.is_empty())
+        .collect();
+    families.par_sort_unstable_by(|a, b| a.alphagram.cmp(&b.alphagram));
+    families
+}
+
+/// An `alphagram,size,members,total_score` header, then one record per family, members
+/// separated by spaces. Fields are quoted when needed.
+pub fn to_csv(families: &[Family]) -> String {
+    let records: Vec<String> = families.par_iter()
+        .map(|family| format!(
+            "{},{},{},{}\n",
+            family.alphagram,
+            family.size(),
+            csv_field(&family.members.join(" ")),
+            family.total_score,
+        ))
+        .collect();
+    let mut csv = String::from("alphagram,size,members,total_score\n");
+    csv.extend(records);
+    csv
+}
+
+/// The families as a JSON array of objects with the fields of [`Family`] and its size.
+#[cfg(feature = "serde")]
+pub fn to_json(families: &[Family]) -> String {
+    let records: Vec<String> = families.par_iter()
+        .map(|family| serde_json::json!({
+            "alphagram": family.alphagram,
+            "size": family.size(),
+            "members": family.members,
+            "total_score": family.total_score,
+        }).to_string())
+        .collect();
+    format!("[{}]", records.join(","))
+}
+
+#[cfg(test)]
+mod families_tests {
+    use crate::rack::word_score;
+    use crate::{test_words, Dictionary};
+
+    use super::{families, to_csv};
+
+    fn dictionary() -> Dictionary {
+        test_words(&["tac", "cat", "act", "dog", "God", "a", "123", "b,a"]).into()
+    }
+
+    #[test]
+    fn grouped_by_alphagram() {
+        let families = families(&dictionary(), &word_score);
+        let summary: Vec<(&str, usize, u32)> = families.iter()
+            .map(|family| (family.alphagram.as_str(), family.size(), family.total_score))
+            .collect();
+        assert_eq!(summary, vec![("a", 1, 1), ("ab", 1, 4), ("act", 3, 15), ("dgo", 2, 10)]);
+        assert_eq!(families[2].members, vec!["tac", "cat", "act"]);
+    }
+
+    #[test]
+    fn csv() {
+        let csv = to_csv(&families(&test_words(&["dog", "God", "b,a"]).into(), &word_score));
+        assert_eq!(csv, "alphagram,size,members,total_score\nab,1,\"b,a\",4\ndgo,2,dog God,10\n");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let json = super::to_json(&families(&test_words(&["dog", "God"]).into(), &word_score));
+        assert_eq!(json, r#"[{"alphagram":"dgo","members":["dog","God"],"size":2,"total_score":10}]"#);
+    }
+}
@@ -0,0 +1,221 @@
+//! A C ABI, for game engines and applications that are not written in Rust.
+//!
+//! The crate is built as a C dynamic library with the `ffi` feature:
+//!
+//! ```sh
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! and `include/find_words.h` declares the functions. A dictionary is loaded from a word list
+//! with [`fw_dictionary_load`] and freed with [`fw_dictionary_free`]. [`fw_find`] returns the
+//! words a list of letters can make as an array of NUL-terminated UTF-8 strings, which the
+//! caller gives back to [`fw_free_results`]. Strings passed in must be NUL-terminated UTF-8.
+//!
+//! Functions that can fail set an error code, one of the `FW_*` constants, that
+//! [`fw_last_error`] returns on the same thread until the next call; [`fw_error_message`]
+//! describes it. No panic unwinds into the caller: it is reported as [`FW_PANIC`].
+
+use std::cell::Cell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use crate::{Dictionary, FindWordsError};
+
+/// The last call succeeded.
+pub const FW_OK: i32 = 0;
+/// A pointer that must not be null was null.
+pub const FW_NULL_POINTER: i32 = 1;
+/// A string passed in is not valid UTF-8.
+pub const FW_INVALID_UTF8: i32 = 2;
+/// The word list could not be opened or read.
+pub const FW_IO_ERROR: i32 = 3;
+/// A line of the word list is not valid UTF-8.
+pub const FW_MALFORMED_DICTIONARY: i32 = 4;
+/// The word list has no words.
+pub const FW_EMPTY_DICTIONARY: i32 = 5;
+/// The library panicked, which is a bug.
+pub const FW_PANIC: i32 = 6;
+
+thread_local! {
+    static LAST_ERROR: Cell<i32> = const { Cell::new(FW_OK) };
+}
+
+/// A dictionary loaded through the C ABI.
+pub struct FwDictionary {
+    dictionary: Dictionary,
+}
+
+/// Code of the error of the last call on this thread, [`FW_OK`] if it succeeded.
+#[no_mangle]
+pub extern "C" fn fw_last_error() -> i32 {
+    LAST_ERROR.with(Cell::get)
+}
+
+/// Description of an error code, as a static NUL-terminated string the caller must not free.
+#[no_mangle]
+pub extern "C" fn fw_error_message(code: i32) -> *const c_char {
+    let message: &'static CStr = match code {
+        FW_OK => c"no error",
+        FW_NULL_POINTER => c"a pointer argument is null",
+        FW_INVALID_UTF8 => c"a string argument is not valid UTF-8",
+        FW_IO_ERROR => c"the word list cannot be read",
+        FW_MALFORMED_DICTIONARY => c"a line of the word list is not valid UTF-8",
+        FW_EMPTY_DICTIONARY => c"the word list has no words",
+        FW_PANIC => c"find_words panicked",
+        _ => c"unknown error code",
+    };
+    message.as_ptr()
+}
+
+/// Loads a dictionary from the word list at `path`, one word per line. Returns null on error.
+///
+/// # Safety
+/// `path` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn fw_dictionary_load(path: *const c_char) -> *mut FwDictionary {
+    guard(ptr::null_mut(), || {
+        let path = string(path)?;
+        let dictionary = Dictionary::from_file(path).map_err(|error| match error {
+            FindWordsError::MalformedLine { .. } => FW_MALFORMED_DICTIONARY,
+            FindWordsError::EmptyDictionary { .. } => FW_EMPTY_DICTIONARY,
+            _ => FW_IO_ERROR,
+        })?;
+        Ok(Box::into_raw(Box::new(FwDictionary { dictionary })))
+    })
+}
+
+/// Number of words of the dictionary, 0 for a null dictionary.
+///
+/// # Safety
+/// `dictionary` must be null or returned by [`fw_dictionary_load`] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn fw_dictionary_len(dictionary: *const FwDictionary) -> usize {
+    guard(0, || Ok(dictionary.as_ref().ok_or(FW_NULL_POINTER)?.dictionary.len()))
+}
+
+/// Frees a dictionary. Does nothing with null.
+///
+/// # Safety
+/// `dictionary` must be null or returned by [`fw_dictionary_load`] and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn fw_dictionary_free(dictionary: *mut FwDictionary) {
+    if !dictionary.is_null() {
+        drop(Box::from_raw(dictionary));
+    }
+}
+
+/// The words of the dictionary that can be constructed from `letters`, in dictionary order, as
+/// an array of `*out_len` strings to free with [`fw_free_results`]. Returns null, with
+/// `*out_len` set to 0, when no word is found or on error, which [`fw_last_error`] tells apart.
+///
+/// # Safety
+/// `dictionary` must be null or returned by [`fw_dictionary_load`] and not freed, `letters` null
+/// or a NUL-terminated string, and `out_len` null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn fw_find(dictionary: *const FwDictionary, letters: *const c_char, out_len: *mut usize) -> *mut *mut c_char {
+    let Some(out_len) = out_len.as_mut() else {
+        LAST_ERROR.with(|error| error.set(FW_NULL_POINTER));
+        return ptr::null_mut();
+    };
+    *out_len = 0;
+    guard(ptr::null_mut(), || {
+        let dictionary = &dictionary.as_ref().ok_or(FW_NULL_POINTER)?.dictionary;
+        // A word with a NUL byte cannot be passed as a C string, and is left out.
+        let words: Box<[*mut c_char]> = dictionary.find(string(letters)?).into_iter()
+            .filter_map(|word| CString::new(word).ok())
+            .map(CString::into_raw)
+            .collect();
+        if words.is_empty() { return Ok(ptr::null_mut()); }
+        *out_len = words.len();
+        Ok(Box::into_raw(words) as *mut *mut c_char)
+    })
+}
+
+/// Frees the words returned by [`fw_find`]. Does nothing with null.
+///
+/// # Safety
+/// `results` must be null or returned by [`fw_find`] with `len` words, and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn fw_free_results(results: *mut *mut c_char, len: usize) {
+    if results.is_null() { return; }
+    let words = Box::from_raw(ptr::slice_from_raw_parts_mut(results, len));
+    for &word in words.iter() {
+        drop(CString::from_raw(word));
+    }
+}
+
+/// The string a C string points to.
+///
+/// # Safety
+/// `text` must be null or point to a NUL-terminated string that outlives the result.
+unsafe fn string<'a>(text: *const c_char) -> Result<&'a str, i32> {
+    if text.is_null() { return Err(FW_NULL_POINTER); }
+    CStr::from_ptr(text).to_str().map_err(|_| FW_INVALID_UTF8)
+}
+
+/// Runs `call`, setting the last error, and returns `failed` if it fails or panics.
+fn guard<T, F: FnOnce() -> Result<T, i32>>(failed: T, call: F) -> T {
+    let (value, code) = match catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(value)) => (value, FW_OK),
+        Ok(Err(code)) => (failed, code),
+        Err(_) => (failed, FW_PANIC),
+    };
+    LAST_ERROR.with(|error| error.set(code));
+    value
+}
+
+#[cfg(test)]
+mod ffi_tests {
+    use std::ffi::{CStr, CString};
+    use std::ptr;
+
+    use super::*;
+
+    fn load(path: &str) -> *mut FwDictionary {
+        let path = CString::new(path).unwrap();
+        unsafe { fw_dictionary_load(path.as_ptr()) }
+    }
+
+    fn find(dictionary: *const FwDictionary, letters: &[u8]) -> Option<Vec<String>> {
+        let letters = CString::new(letters).unwrap();
+        let mut len = 99;
+        unsafe {
+            let results = fw_find(dictionary, letters.as_ptr(), &mut len);
+            if fw_last_error() != FW_OK { return None; }
+            if results.is_null() { return Some(vec![]); }
+            let words = (0..len).map(|index| CStr::from_ptr(*results.add(index)).to_str().unwrap().to_string()).collect();
+            fw_free_results(results, len);
+            Some(words)
+        }
+    }
+
+    #[test]
+    fn load_find_and_free() {
+        let dictionary = load("tests/golden/lexicons/tiny.txt");
+        assert!(!dictionary.is_null() && fw_last_error() == FW_OK);
+        assert_eq!(unsafe { fw_dictionary_len(dictionary) }, 26);
+        assert_eq!(find(dictionary, b"tca"), Some(vec!["act".to_string(), "at".to_string(), "cat".to_string()]));
+        assert_eq!(find(dictionary, b"zzz"), Some(vec![]));
+        assert_eq!(find(dictionary, b"t\xffca"), None);
+        assert_eq!(fw_last_error(), FW_INVALID_UTF8);
+        unsafe { fw_dictionary_free(dictionary) };
+    }
+
+    #[test]
+    fn errors() {
+        assert!(load("missing.txt").is_null());
+        assert_eq!(fw_last_error(), FW_IO_ERROR);
+        assert!(unsafe { fw_dictionary_load(ptr::null()) }.is_null());
+        assert_eq!(fw_last_error(), FW_NULL_POINTER);
+        assert_eq!(find(ptr::null(), b"tca"), None);
+        assert_eq!(fw_last_error(), FW_NULL_POINTER);
+        assert!(unsafe { fw_find(ptr::null(), ptr::null(), ptr::null_mut()) }.is_null());
+        let message = unsafe { CStr::from_ptr(fw_error_message(FW_EMPTY_DICTIONARY)) };
+        assert_eq!(message.to_str(), Ok("the word list has no words"));
+        unsafe {
+            fw_dictionary_free(ptr::null_mut());
+            fw_free_results(ptr::null_mut(), 0);
+        }
+    }
+}
@@ -0,0 +1,381 @@
+//! Turn-based word games: players draw racks of tiles from a shared bag, and take turns playing
+//! a word from their rack, exchanging tiles or passing.
+//!
+//! Plays are checked against the dictionary and the player's rack like guesses are in a puzzle,
+//! rejections being reported with the same [`Verdict`]s. There is no board: a play is a word
+//! made from the rack, and the played tiles are replaced from the bag. Plays score the values of
+//! their tiles by default, blanks standing for the letters they were played as and scoring nothing.
+//!
+//! Drawing tiles, and so playing games, needs the `random` feature.
+
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "random")]
+use rand::prelude::*;
+
+#[cfg(feature = "random")]
+use crate::notation::PlayRecord;
+use crate::puzzle::Verdict;
+#[cfg(feature = "random")]
+use crate::rack::tile_score;
+#[cfg(feature = "random")]
+use crate::Dictionary;
+use crate::{Rack, Tile};
+
+/// Number of tiles of each letter in an English bag, in the proportions of Scrabble (without blanks).
+pub(crate) const ENGLISH_TILES: [(char, usize); 26] = [
+    ('a', 9), ('b', 2), ('c', 2), ('d', 4), ('e', 12), ('f', 2), ('g', 3), ('h', 2), ('i', 9),
+    ('j', 1), ('k', 1), ('l', 4), ('m', 2), ('n', 6), ('o', 8), ('p', 2), ('q', 1), ('r', 6),
+    ('s', 4), ('t', 6), ('u', 4), ('v', 2), ('w', 2), ('x', 1), ('y', 2), ('z', 1),
+];
+
+/// Number of tiles on a full rack.
+pub const RACK_SIZE: usize = 7;
+
+/// Tiles not drawn yet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TileBag {
+    tiles: Vec<Tile>,
+}
+
+/// A player and their rack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub rack: Rack,
+    pub score: u32,
+}
+
+/// What a player does on their turn.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Move {
+    /// Play a word made from the rack.
+    Play(String),
+    /// Put the given tiles back in the bag and draw as many.
+    Exchange(String),
+    Pass,
+}
+
+/// Why a move was refused. The turn does not change.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameError {
+    GameOver,
+    /// The word was rejected, with [`Verdict::NotInDictionary`] or [`Verdict::LettersNotAvailable`].
+    Rejected(Verdict),
+    /// Tiles can only be exchanged while the bag has a full rack of tiles left.
+    ExchangeNotAllowed,
+}
+
+/// State of a game.
+#[cfg(feature = "random")]
+pub struct Game<'a> {
+    dictionary: &'a Dictionary,
+    scorer: &'a dyn Fn(&[Tile]) -> u32,
+    bag: TileBag,
+    players: Vec<Player>,
+    current: usize,
+    consecutive_passes: usize,
+    history: Vec<PlayRecord>,
+}
+
+impl TileBag {
+    pub fn new(tiles: Vec<Tile>) -> TileBag {
+        TileBag { tiles }
+    }
+
+    /// A bag of English tiles written one character each, blanks as `?`.
+    pub fn from_letters(letters: &str) -> TileBag {
+        TileBag::new(letters.chars().map(Tile::english).collect())
+    }
+
+    /// The 98 letter tiles of an English bag.
+    pub fn english() -> TileBag {
+        TileBag::new(ENGLISH_TILES.iter().flat_map(|&(letter, count)| std::iter::repeat_n(Tile::english(letter), count)).collect())
+    }
+
+    /// Adds `count` blanks to the bag.
+    pub fn with_blanks(mut self, count: usize) -> Self {
+        self.tiles.extend(std::iter::repeat_n(Tile::blank(), count));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Draws up to `count` tiles at random.
+    #[cfg(feature = "random")]
+    pub fn draw<R: Rng + ?Sized>(&mut self, rng: &mut R, count: usize) -> Rack {
+        let mut rack = Rack::new();
+        rack.extend((0..count.min(self.tiles.len())).map(|_| self.tiles.swap_remove(rng.gen_range(0..self.tiles.len()))));
+        rack
+    }
+
+    /// Puts tiles back in the bag, undoing the designation of blanks.
+    pub fn put_back<I: IntoIterator<Item=Tile>>(&mut self, tiles: I) {
+        self.tiles.extend(tiles.into_iter().map(|tile| if tile.is_blank { Tile::blank() } else { tile }));
+    }
+}
+
+#[cfg(feature = "random")]
+impl<'a> Game<'a> {
+    /// Starts a game, drawing a full rack for every player in turn order. Panics if `names` is
+    /// empty.
+    pub fn new<R: Rng + ?Sized>(dictionary: &'a Dictionary, mut bag: TileBag, names: &[&str], rng: &mut R) -> Self {
+        assert!(!names.is_empty(), "a game needs at least one player");
+        let players = names.iter()
+            .map(|name| Player { name: name.to_string(), rack: bag.draw(rng, RACK_SIZE), score: 0 })
+            .collect();
+        Game { dictionary, scorer: &tile_score, bag, players, current: 0, consecutive_passes: 0, history: vec![] }
+    }
+
+    /// Scores plays with `scorer`, given the tiles played, instead of the values of the tiles.
+    pub fn with_scorer(mut self, scorer: &'a dyn Fn(&[Tile]) -> u32) -> Self {
+        self.scorer = scorer;
+        self
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn bag(&self) -> &TileBag {
+        &self.bag
+    }
+
+    /// Every move played so far, in order, e.g. to be written down with
+    /// [`write_log`](crate::notation::write_log).
+    pub fn history(&self) -> &[PlayRecord] {
+        &self.history
+    }
+
+    /// The player whose turn it is.
+    pub fn current_player(&self) -> &Player {
+        &self.players[self.current]
+    }
+
+    /// The game is over once the bag is empty and a player has emptied their rack, or once
+    /// every player has passed (or exchanged) twice in a row.
+    pub fn is_over(&self) -> bool {
+        (self.bag.is_empty() && self.players.iter().any(|player| player.rack.is_empty()))
+            || self.consecutive_passes >= 2 * self.players.len()
+    }
+
+    /// Plays a move for the current player, returning the points scored, and passes the turn on.
+    pub fn play<R: Rng + ?Sized>(&mut self, rng: &mut R, next: Move) -> Result<u32, GameError> {
+        if self.is_over() {
+            return Err(GameError::GameOver);
+        }
+        let player = &mut self.players[self.current];
+        let rack_before = player.rack.clone();
+        let mut action = next.clone();
+        let points = match next {
+            Move::Play(word) => {
+                let mut rack = player.rack.clone();
+                let Some(played) = rack.remove_word(&word) else {
+                    return Err(GameError::Rejected(Verdict::LettersNotAvailable { missing: player.rack.missing(&word) }));
+                };
+                if !self.dictionary.contains(&word) {
+                    return Err(GameError::Rejected(Verdict::NotInDictionary));
+                }
+                rack.extend(self.bag.draw(rng, RACK_SIZE.saturating_sub(rack.len())));
+                player.rack = rack;
+                action = Move::Play(played.iter().map(Tile::to_string).collect());
+                let points = (self.scorer)(&played);
+                player.score += points;
+                self.consecutive_passes = 0;
+                points
+            }
+            Move::Exchange(tiles) => {
+                if self.bag.len() < RACK_SIZE {
+                    return Err(GameError::ExchangeNotAllowed);
+                }
+                let mut kept = player.rack.clone();
+                let Some(returned) = tiles.chars().filter(|c| !c.is_whitespace()).map(|tile| kept.remove(tile)).collect::<Option<Vec<Tile>>>() else {
+                    return Err(GameError::Rejected(Verdict::LettersNotAvailable { missing: player.rack.missing(&tiles) }));
+                };
+                kept.extend(self.bag.draw(rng, returned.len()));
+                self.bag.put_back(returned);
+                player.rack = kept;
+                self.consecutive_passes += 1;
+                0
+            }
+            Move::Pass => {
+                self.consecutive_passes += 1;
+                0
+            }
+        };
+        self.history.push(PlayRecord {
+            rack_before,
+            action,
+            position: None,
+            score: points,
+            rack_after: self.players[self.current].rack.clone(),
+        });
+        self.current = (self.current + 1) % self.players.len();
+        Ok(points)
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::GameOver => write!(f, "the game is over"),
+            GameError::Rejected(Verdict::NotInDictionary) => write!(f, "word is not in the dictionary"),
+            GameError::Rejected(Verdict::LettersNotAvailable { .. }) => write!(f, "letters are not on the rack"),
+            GameError::Rejected(verdict) => write!(f, "move rejected: {:?}", verdict),
+            GameError::ExchangeNotAllowed => write!(f, "not enough tiles left in the bag to exchange"),
+        }
+    }
+}
+
+impl Error for GameError {}
+
+#[cfg(test)]
+mod tile_bag_tests {
+    #[cfg(feature = "random")]
+    use rand::rngs::StdRng;
+    #[cfg(feature = "random")]
+    use rand::SeedableRng;
+
+    #[cfg(feature = "random")]
+    use crate::Tile;
+
+    use super::TileBag;
+
+    #[test]
+    fn english_bag() {
+        assert_eq!(TileBag::english().len(), 98);
+        assert_eq!(TileBag::english().with_blanks(2).len(), 100);
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn draw_and_put_back() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut bag = TileBag::from_letters("abc");
+        let mut drawn: Vec<char> = bag.draw(&mut rng, 5).letters().chars().collect();
+        drawn.sort_unstable();
+        assert_eq!(drawn, vec!['a', 'b', 'c']);
+        assert!(bag.is_empty());
+        bag.put_back([Tile::english('a'), Tile::blank().designate('b')]);
+        assert_eq!(bag.draw(&mut rng, 2).blanks(), 1);
+    }
+}
+
+#[cfg(all(test, feature = "random"))]
+mod game_tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::notation::write_log;
+    use crate::puzzle::Verdict;
+    use crate::{test_words, Dictionary, Tile};
+
+    use super::{Game, GameError, Move, TileBag, RACK_SIZE};
+
+    fn dictionary() -> Dictionary {
+        test_words(&["cat", "act", "dog", "at"]).into()
+    }
+
+    /// A game where the first player holds "catdogx", drawing from a bag of x tiles.
+    fn game<'a>(dictionary: &'a Dictionary, rng: &mut StdRng) -> Game<'a> {
+        let mut game = Game::new(dictionary, TileBag::from_letters("xxxxxxxxxxxxxxxxxxxxx"), &["ann", "bob"], rng);
+        game.players[0].rack = "catdogx".parse().unwrap();
+        game
+    }
+
+    #[test]
+    fn new_game_deals_racks() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(1);
+        let game = Game::new(&dictionary, TileBag::english(), &["ann", "bob"], &mut rng);
+        assert!(game.players().iter().all(|player| player.rack.len() == RACK_SIZE));
+        assert_eq!(game.bag().len(), 98 - 2 * RACK_SIZE);
+        assert_eq!(game.current_player().name, "ann");
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one player")]
+    fn new_game_needs_players() {
+        let dictionary = dictionary();
+        Game::new(&dictionary, TileBag::english(), &[], &mut StdRng::seed_from_u64(1));
+    }
+
+    #[test]
+    fn play_scores_and_refills() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut game = game(&dictionary, &mut rng);
+        assert_eq!(game.play(&mut rng, Move::Play("cat".to_string())), Ok(5));
+        assert_eq!(game.players()[0].score, 5);
+        assert_eq!(game.players()[0].rack.to_string(), "dogxxxx");
+        assert_eq!(game.current_player().name, "bob");
+        assert_eq!(write_log(game.history()), "catdogx cat +5 dogxxxx\n");
+    }
+
+    #[test]
+    fn blanks_score_nothing() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut game = game(&dictionary, &mut rng);
+        game.players[0].rack = "ca?dogx".parse().unwrap();
+        assert_eq!(game.play(&mut rng, Move::Play("cat".to_string())), Ok(4));
+        assert_eq!(write_log(game.history()), "ca?dogx caT +4 dogxxxx\n");
+    }
+
+    #[test]
+    fn custom_scorer() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(1);
+        let scorer = |tiles: &[Tile]| tiles.len() as u32;
+        let mut game = game(&dictionary, &mut rng).with_scorer(&scorer);
+        assert_eq!(game.play(&mut rng, Move::Play("cat".to_string())), Ok(3));
+    }
+
+    #[test]
+    fn rejected_plays_keep_the_turn() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut game = game(&dictionary, &mut rng);
+        assert_eq!(game.play(&mut rng, Move::Play("cog".to_string())), Err(GameError::Rejected(Verdict::NotInDictionary)));
+        assert!(matches!(game.play(&mut rng, Move::Play("cattle".to_string())), Err(GameError::Rejected(Verdict::LettersNotAvailable { .. }))));
+        assert_eq!(game.current_player().name, "ann");
+    }
+
+    #[test]
+    fn exchange() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut game = game(&dictionary, &mut rng);
+        let bag_len = game.bag().len();
+        assert_eq!(game.play(&mut rng, Move::Exchange("cd".to_string())), Ok(0));
+        assert_eq!(game.players()[0].rack.to_string(), "atogxxx");
+        assert_eq!(game.bag().len(), bag_len);
+    }
+
+    #[test]
+    fn exchange_needs_a_full_bag() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut game = Game::new(&dictionary, TileBag::from_letters("catdogxcatdog"), &["ann"], &mut rng);
+        assert_eq!(game.play(&mut rng, Move::Exchange(String::new())), Err(GameError::ExchangeNotAllowed));
+    }
+
+    #[test]
+    fn game_ends_after_passes() {
+        let dictionary = dictionary();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut game = game(&dictionary, &mut rng);
+        for _ in 0..4 {
+            assert_eq!(game.play(&mut rng, Move::Pass), Ok(0));
+        }
+        assert!(game.is_over());
+        assert_eq!(game.play(&mut rng, Move::Pass), Err(GameError::GameOver));
+    }
+}
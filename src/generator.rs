@@ -0,0 +1,172 @@
+//! Random lists of letters closer to the racks of a game than uniform letters.
+//!
+//! [`generate_random_string`](crate::generate_random_string) draws every letter uniformly, so
+//! that a `q` is as likely as an `e` and lists often have no vowel. A [`LetterGenerator`] draws
+//! letters with the frequencies of English text, or from a full English tile bag without
+//! replacement, and can require a minimum number of vowels. It owns its random number generator:
+//! one built with [`LetterGenerator::from_seed`] generates the same lists on every run, for
+//! tests and benchmarks.
+
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use crate::alphabet::{Alphabet, English};
+use crate::distribution::LetterFrequencies;
+use crate::game::ENGLISH_TILES;
+
+/// Occurrences of every letter, a to z, per 10,000 letters of English text.
+pub const ENGLISH_FREQUENCIES: [u64; 26] = [
+    817, 149, 278, 425, 1270, 223, 202, 609, 697, 15, 77, 403, 241,
+    675, 751, 193, 10, 599, 633, 906, 276, 98, 236, 15, 197, 7,
+];
+
+/// Letters counted as vowels by [`LetterGenerator::min_vowels`].
+pub const VOWELS: [char; 5] = ['a', 'e', 'i', 'o', 'u'];
+
+/// How the letters of a list are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Weighting {
+    /// Every letter from a to z with the same probability, as [`generate_random_string`](crate::generate_random_string).
+    #[default]
+    Uniform,
+    /// Letters with the probabilities of [`ENGLISH_FREQUENCIES`].
+    EnglishFrequency,
+    /// Tiles of a full English bag without blanks, without replacement: a list has at most as many
+    /// of a letter as the bag has tiles of it, and at most 98 letters.
+    TileBag,
+}
+
+/// Generator of random lowercase lists of letters.
+#[derive(Clone, Debug)]
+pub struct LetterGenerator {
+    weighting: Weighting,
+    min_vowels: usize,
+    rng: StdRng,
+}
+
+impl LetterGenerator {
+    /// A generator of uniform letters, seeded from the operating system.
+    pub fn new() -> Self {
+        LetterGenerator::with_rng(StdRng::from_entropy())
+    }
+
+    /// A generator of uniform letters generating the same lists for the same seed.
+    pub fn from_seed(seed: u64) -> Self {
+        LetterGenerator::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(rng: StdRng) -> Self {
+        LetterGenerator { weighting: Weighting::Uniform, min_vowels: 0, rng }
+    }
+
+    pub fn weighting(mut self, weighting: Weighting) -> Self {
+        self.weighting = weighting;
+        self
+    }
+
+    /// Lists have at least `count` vowels, or only vowels when they are shorter. Vowels are drawn
+    /// with the probabilities of the weighting, and placed at random among the other letters.
+    pub fn min_vowels(mut self, count: usize) -> Self {
+        self.min_vowels = count;
+        self
+    }
+
+    /// A random list of `len` letters, fewer from a tile bag with fewer tiles.
+    pub fn generate(&mut self, len: usize) -> String {
+        let mut letters = match self.weighting {
+            Weighting::Uniform => self.draw_weighted([1; 26], len),
+            Weighting::EnglishFrequency => self.draw_weighted(ENGLISH_FREQUENCIES, len),
+            Weighting::TileBag => self.draw_tiles(len),
+        };
+        letters.shuffle(&mut self.rng);
+        letters.into_iter().collect()
+    }
+
+    /// The vowels required first, drawn with the weights of the vowels only, then the other letters.
+    fn draw_weighted(&mut self, weights: [u64; 26], len: usize) -> Vec<char> {
+        let vowels = self.min_vowels.min(len);
+        let mut vowel_weights = weights;
+        for (index, weight) in vowel_weights.iter_mut().enumerate() {
+            if !VOWELS.contains(&English::letter(index)) { *weight = 0; }
+        }
+        let mut letters: Vec<char> = LetterFrequencies::from_counts(vowel_weights).random_letters::<English, _>(&mut self.rng, vowels).chars().collect();
+        letters.extend(LetterFrequencies::from_counts(weights).random_letters::<English, _>(&mut self.rng, len - vowels).chars());
+        letters
+    }
+
+    /// The vowels required first, taken in the order of a shuffled bag, then the next tiles.
+    fn draw_tiles(&mut self, len: usize) -> Vec<char> {
+        let mut bag: Vec<char> = ENGLISH_TILES.iter().flat_map(|&(letter, count)| std::iter::repeat_n(letter, count)).collect();
+        bag.shuffle(&mut self.rng);
+        let len = len.min(bag.len());
+        let mut letters = vec![];
+        while letters.len() < self.min_vowels.min(len) {
+            let Some(position) = bag.iter().position(|letter| VOWELS.contains(letter)) else { break; };
+            letters.push(bag.remove(position));
+        }
+        let rest = len - letters.len();
+        letters.extend(bag.drain(..rest));
+        letters
+    }
+}
+
+impl Default for LetterGenerator {
+    fn default() -> Self {
+        LetterGenerator::new()
+    }
+}
+
+#[cfg(test)]
+mod generator_tests {
+    use std::collections::HashMap;
+
+    use crate::game::ENGLISH_TILES;
+    use crate::get_letters_count;
+
+    use super::{LetterGenerator, Weighting, VOWELS};
+
+    fn vowels(letters: &str) -> usize {
+        letters.chars().filter(|letter| VOWELS.contains(letter)).count()
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        for weighting in [Weighting::Uniform, Weighting::EnglishFrequency, Weighting::TileBag] {
+            let generate = |seed| LetterGenerator::from_seed(seed).weighting(weighting).min_vowels(2).generate(12);
+            assert_eq!(generate(7), generate(7), "{:?}", weighting);
+            assert_ne!(generate(7), generate(8), "{:?}", weighting);
+        }
+    }
+
+    #[test]
+    fn lengths_and_vowels() {
+        for weighting in [Weighting::Uniform, Weighting::EnglishFrequency, Weighting::TileBag] {
+            let mut generator = LetterGenerator::from_seed(1).weighting(weighting).min_vowels(3);
+            for len in [0, 2, 7, 20] {
+                let letters = generator.generate(len);
+                assert_eq!(letters.len(), len, "{:?}", weighting);
+                assert!(letters.chars().all(|letter| letter.is_ascii_lowercase()));
+                assert!(vowels(&letters) >= len.min(3), "{:?} {}", weighting, letters);
+            }
+        }
+    }
+
+    #[test]
+    fn tile_bag_does_not_replace_tiles() {
+        let mut generator = LetterGenerator::from_seed(3).weighting(Weighting::TileBag).min_vowels(50);
+        let tiles: HashMap<char, u8> = ENGLISH_TILES.iter().map(|&(letter, count)| (letter, count as u8)).collect();
+        for len in [30, 98, 150] {
+            let letters = generator.generate(len);
+            assert_eq!(letters.len(), len.min(98));
+            assert!(get_letters_count(&letters).iter().all(|(letter, count)| count <= &tiles[letter]), "{}", letters);
+        }
+        assert_eq!(vowels(&generator.generate(60)), 42);
+    }
+
+    #[test]
+    fn english_frequencies() {
+        let letters = LetterGenerator::from_seed(5).weighting(Weighting::EnglishFrequency).generate(1000);
+        let counts = get_letters_count(&letters);
+        assert!(counts[&'e'] > 80 && counts[&'t'] > counts.get(&'z').copied().unwrap_or(0) * 10);
+    }
+}
@@ -0,0 +1,528 @@
+//! Validation of user-provided lists of letters.
+//!
+//! [`get_letters_count`](crate::get_letters_count) silently drops anything that is not an ASCII
+//! letter. [`parse_letters`] instead applies a [`ParsePolicy`] and reports what it rejected, which
+//! is what front ends taking input from users want.
+//!
+//! Parsed letters are stored in a [`LetterCounts`], a fixed-size array of counts indexed through
+//! an [`Alphabet`], along with a bitmask of the letters present. The English alphabet is the
+//! default, but any alphabet can be used. Maps and caches keyed by a multiset of English letters
+//! should use the compact [`LetterKey`] instead.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::alphabet::{Alphabet, English};
+
+/// Number of occurrences of each letter of an `N`-letter alphabet in a word or a list of letters.
+/// Counts saturate at [`MAX_LETTER_COUNT`](crate::MAX_LETTER_COUNT).
+///
+/// The counts come with a bitmask of the letters present, so that [`can_be_constructed`] rejects
+/// most words with a single test before comparing counts, without hashing anything.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LetterCounts<const N: usize = 26> {
+    counts: [u8; N],
+    /// Bit `i` is set when the letter at index `i` is present, for the first 64 letters.
+    mask: u64,
+}
+
+impl<const N: usize> Default for LetterCounts<N> {
+    fn default() -> Self {
+        LetterCounts { counts: [0; N], mask: 0 }
+    }
+}
+
+impl<const N: usize> LetterCounts<N> {
+    /// Counts with no letters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts the letters of a word, ignoring characters that are not part of the alphabet.
+    pub fn from_word<A: Alphabet<N>>(word: &str) -> Self {
+        word.chars()
+            .filter_map(A::index)
+            .fold(Self::default(), |mut counts, index| {
+                counts.increment(index);
+                counts
+            })
+    }
+
+    /// Count of the letter at an index of the alphabet.
+    pub fn get(&self, index: usize) -> u8 {
+        self.counts[index]
+    }
+
+    /// Adds one occurrence of the letter at an index of the alphabet.
+    pub fn increment(&mut self, index: usize) {
+        self.add(index, 1);
+    }
+
+    /// Bitmask of the letters present: bit `i` is set when the letter at index `i` is, for the
+    /// first 64 letters of the alphabet.
+    pub fn mask(&self) -> u64 {
+        self.mask
+    }
+
+    /// The counts left after taking the letters of `other`, or `None` if `other` has more of
+    /// some letter.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let mut left = Self::default();
+        for (index, (&count, &taken)) in self.counts.iter().zip(other.counts.iter()).enumerate() {
+            left.add(index, count.checked_sub(taken)?);
+        }
+        Some(left)
+    }
+
+    fn add(&mut self, index: usize, count: u8) {
+        self.counts[index] = self.counts[index].saturating_add(count);
+        if index < 64 && count > 0 {
+            self.mask |= 1 << index;
+        }
+    }
+
+    /// Counts indexed by the alphabet.
+    pub fn as_array(&self) -> &[u8; N] {
+        &self.counts
+    }
+
+    /// Total number of letters.
+    pub fn len(&self) -> usize {
+        self.counts.iter().map(|&count| count as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|&count| count == 0)
+    }
+
+    /// Character count mapping of the letters, as returned by [`get_letters_count`](crate::get_letters_count).
+    pub fn to_map<A: Alphabet<N>>(&self) -> HashMap<char, u8> {
+        self.counts.iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(index, &count)| (A::letter(index), count))
+            .collect()
+    }
+}
+
+impl From<&HashMap<char, u8>> for LetterCounts {
+    /// Converts a character count mapping, ignoring characters that are not English letters.
+    fn from(map: &HashMap<char, u8>) -> Self {
+        let mut counts = LetterCounts::new();
+        for (&c, &count) in map {
+            if let Some(index) = English::index(c) {
+                counts.add(index, count);
+            }
+        }
+        counts
+    }
+}
+
+/// Canonical key of a multiset of English letters: two words or racks get the same key exactly
+/// when they have the same letters, in any order. Keys are ordered, but the order is only
+/// meant for sorted maps, not for display.
+///
+/// Counts up to 15 per letter are packed in a `u128`; larger multisets fall back on their
+/// sorted letters.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LetterKey(KeyRepr);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum KeyRepr {
+    /// Four bits per letter, `a` in the lowest bits.
+    Packed(u128),
+    Sorted(Box<str>),
+}
+
+impl LetterKey {
+    /// Key of the English letters of a word, ignoring case and any other character.
+    pub fn from_word(word: &str) -> LetterKey {
+        LetterKey::from(&LetterCounts::from_word::<English>(word))
+    }
+
+    /// The letter counts the key stands for.
+    pub fn counts(&self) -> LetterCounts {
+        let mut counts = LetterCounts::new();
+        match &self.0 {
+            KeyRepr::Packed(packed) => {
+                for index in 0..counts.counts.len() {
+                    counts.add(index, ((packed >> (4 * index)) & 0xf) as u8);
+                }
+            }
+            KeyRepr::Sorted(letters) => {
+                for letter in letters.chars() {
+                    counts.increment(English::index(letter).expect("sorted keys only hold English letters"));
+                }
+            }
+        }
+        counts
+    }
+
+    /// The letters of the key in alphabetical order, e.g. "act" for "cat".
+    pub fn alphagram(&self) -> String {
+        match &self.0 {
+            KeyRepr::Packed(_) => self.counts().counts.iter()
+                .enumerate()
+                .flat_map(|(index, &count)| std::iter::repeat_n(English::letter(index), count as usize))
+                .collect(),
+            KeyRepr::Sorted(letters) => letters.to_string(),
+        }
+    }
+}
+
+impl From<&LetterCounts> for LetterKey {
+    fn from(counts: &LetterCounts) -> Self {
+        if counts.counts.iter().all(|&count| count <= 0xf) {
+            let packed = counts.counts.iter()
+                .enumerate()
+                .fold(0u128, |packed, (index, &count)| packed | (count as u128) << (4 * index));
+            LetterKey(KeyRepr::Packed(packed))
+        } else {
+            let sorted: String = counts.counts.iter()
+                .enumerate()
+                .flat_map(|(index, &count)| std::iter::repeat_n(English::letter(index), count as usize))
+                .collect();
+            LetterKey(KeyRepr::Sorted(sorted.into_boxed_str()))
+        }
+    }
+}
+
+/// What to do with a class of characters that are not letters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharPolicy {
+    /// Drop the character and carry on.
+    Strip,
+    /// Fail with [`ParseError::InvalidChar`].
+    Reject,
+}
+
+/// Rules applied by [`parse_letters_with`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParsePolicy {
+    pub digits: CharPolicy,
+    pub whitespace: CharPolicy,
+    /// Punctuation, symbols, emoji and non-ASCII letters.
+    pub other: CharPolicy,
+    /// Maximum number of letters kept.
+    pub max_len: usize,
+}
+
+/// A parsed list of letters along with what parsing changed, see [`parse_letters_with_report`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseReport {
+    pub counts: LetterCounts,
+    /// The letters kept, lowercased, in the order of the input.
+    pub normalized: String,
+    /// The characters stripped and their positions (in characters) in the input.
+    pub removed: Vec<(usize, char)>,
+}
+
+/// Error returned when a list of letters does not satisfy a [`ParsePolicy`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// No letters were left after parsing.
+    Empty,
+    TooLong { len: usize, max: usize },
+    /// A rejected character and its position (in characters) in the input.
+    InvalidChar { c: char, position: usize },
+}
+
+impl Default for ParsePolicy {
+    fn default() -> Self {
+        ParsePolicy {
+            digits: CharPolicy::Reject,
+            whitespace: CharPolicy::Strip,
+            other: CharPolicy::Reject,
+            max_len: 64,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "list of letters is empty"),
+            ParseError::TooLong { len, max } =>
+                write!(f, "list contains {} letters, at most {} are allowed", len, max),
+            ParseError::InvalidChar { c, position } =>
+                write!(f, "invalid character {:?} at position {}", c, position),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Returns `true` if the letters of `word` can all be taken from `list`, i.e. if `list` has at
+/// least as many of every letter as `word` does.
+///
+/// An empty word can never be constructed, even from an empty list. Checking a word compares
+/// each of the `N` counts once, so it costs the same whatever the length of the word or of the
+/// list, and never allocates.
+pub fn can_be_constructed<const N: usize>(word: &LetterCounts<N>, list: &LetterCounts<N>) -> bool {
+    if word.mask & !list.mask != 0 { return false; }
+    !word.is_empty() && word.counts.iter().zip(list.counts.iter()).all(|(count, available)| count <= available)
+}
+
+/// Same as [`can_be_constructed`], `blanks` wildcards standing in for letters `list` is short of.
+pub fn can_be_constructed_with_blanks<const N: usize>(word: &LetterCounts<N>, list: &LetterCounts<N>, blanks: usize) -> bool {
+    let shortfall: usize = word.counts.iter()
+        .zip(list.counts.iter())
+        .map(|(&needed, &available)| needed.saturating_sub(available) as usize)
+        .sum();
+    !word.is_empty() && shortfall <= blanks
+}
+
+/// Parses a list of letters with the default [`ParsePolicy`].
+pub fn parse_letters(input: &str) -> Result<LetterCounts, ParseError> {
+    parse_letters_with(input, &ParsePolicy::default())
+}
+
+/// Parses a list of letters into its letter counts, applying `policy` to every character that
+/// is not an ASCII letter.
+pub fn parse_letters_with(input: &str, policy: &ParsePolicy) -> Result<LetterCounts, ParseError> {
+    parse_letters_with_report(input, policy).map(|report| report.counts)
+}
+
+/// Same as [`parse_letters_with`], also reporting the letters kept and the characters stripped,
+/// so that front ends can tell users what was actually searched.
+pub fn parse_letters_with_report(input: &str, policy: &ParsePolicy) -> Result<ParseReport, ParseError> {
+    let mut counts = LetterCounts::new();
+    let mut normalized = String::new();
+    let mut removed = vec![];
+    for (position, c) in input.chars().enumerate() {
+        let char_policy = if let Some(index) = English::index(c) {
+            normalized.push(c.to_ascii_lowercase());
+            counts.increment(index);
+            continue;
+        } else if c.is_ascii_digit() {
+            policy.digits
+        } else if c.is_whitespace() {
+            policy.whitespace
+        } else {
+            policy.other
+        };
+        if char_policy == CharPolicy::Reject {
+            return Err(ParseError::InvalidChar { c, position });
+        }
+        removed.push((position, c));
+    }
+    if normalized.len() > policy.max_len {
+        return Err(ParseError::TooLong { len: normalized.len(), max: policy.max_len });
+    }
+    if counts.is_empty() { Err(ParseError::Empty) } else { Ok(ParseReport { counts, normalized, removed }) }
+}
+
+#[cfg(test)]
+mod parse_letters_tests {
+    use std::collections::HashMap;
+
+    use crate::alphabet::English;
+
+    use super::{parse_letters, parse_letters_with, parse_letters_with_report, CharPolicy, ParseError, ParsePolicy};
+
+    #[test]
+    fn letters_are_counted_and_lowercased() {
+        let output = parse_letters("DodGe").map(|counts| counts.to_map::<English>());
+        let expected = HashMap::from([
+            ('d', 2),
+            ('o', 1),
+            ('g', 1),
+            ('e', 1)
+        ]);
+        assert_eq!(output, Ok(expected));
+    }
+
+    #[test]
+    fn whitespace_is_stripped() {
+        assert_eq!(parse_letters(" d o\tg\n"), parse_letters("dog"));
+    }
+
+    #[test]
+    fn digits_are_rejected() {
+        assert_eq!(parse_letters("do9g"), Err(ParseError::InvalidChar { c: '9', position: 2 }));
+    }
+
+    #[test]
+    fn emoji_are_rejected() {
+        assert_eq!(parse_letters("dog🐶"), Err(ParseError::InvalidChar { c: '🐶', position: 3 }));
+    }
+
+    #[test]
+    fn strip_everything() {
+        let policy = ParsePolicy {
+            digits: CharPolicy::Strip,
+            whitespace: CharPolicy::Strip,
+            other: CharPolicy::Strip,
+            ..ParsePolicy::default()
+        };
+        assert_eq!(parse_letters_with("d0 o!g🐶", &policy), parse_letters("dog"));
+    }
+
+    #[test]
+    fn report() {
+        let policy = ParsePolicy { digits: CharPolicy::Strip, other: CharPolicy::Strip, ..ParsePolicy::default() };
+        let report = parse_letters_with_report("D0 o!g", &policy).unwrap();
+        assert_eq!(report.normalized, "dog");
+        assert_eq!(report.removed, vec![(1, '0'), (2, ' '), (4, '!')]);
+        assert_eq!(Ok(report.counts), parse_letters("dog"));
+    }
+
+    #[test]
+    fn reject_whitespace() {
+        let policy = ParsePolicy { whitespace: CharPolicy::Reject, ..ParsePolicy::default() };
+        assert_eq!(parse_letters_with("do g", &policy), Err(ParseError::InvalidChar { c: ' ', position: 2 }));
+    }
+
+    #[test]
+    fn too_long() {
+        let policy = ParsePolicy { max_len: 4, ..ParsePolicy::default() };
+        assert_eq!(parse_letters_with("abcdef", &policy), Err(ParseError::TooLong { len: 6, max: 4 }));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(parse_letters(""), Err(ParseError::Empty));
+        assert_eq!(parse_letters("   "), Err(ParseError::Empty));
+    }
+}
+
+#[cfg(test)]
+mod letter_key_tests {
+    use std::collections::BTreeMap;
+
+    use crate::alphabet::English;
+
+    use super::{LetterCounts, LetterKey};
+
+    #[test]
+    fn same_letters_same_key() {
+        assert_eq!(LetterKey::from_word("listen"), LetterKey::from_word("Silent"));
+        assert_eq!(LetterKey::from_word("c a-t"), LetterKey::from_word("act"));
+        assert_ne!(LetterKey::from_word("cat"), LetterKey::from_word("tact"));
+    }
+
+    #[test]
+    fn round_trip() {
+        for word in ["", "zebra", "aaaaaaaaaaaaaaa", "aaaaaaaaaaaaaaaab"] {
+            let counts = LetterCounts::from_word::<English>(word);
+            assert_eq!(LetterKey::from(&counts).counts(), counts);
+        }
+    }
+
+    #[test]
+    fn large_counts_fall_back_on_sorted_letters() {
+        let key = LetterKey::from_word(&format!("{}b", "a".repeat(20)));
+        assert_eq!(key, LetterKey::from_word(&format!("b{}", "a".repeat(20))));
+        assert_ne!(key, LetterKey::from_word("ab"));
+    }
+
+    #[test]
+    fn keyed_maps() {
+        let mut anagrams: BTreeMap<LetterKey, Vec<&str>> = BTreeMap::new();
+        for word in ["cat", "dog", "act", "god", "tact"] {
+            anagrams.entry(LetterKey::from_word(word)).or_default().push(word);
+        }
+        assert_eq!(anagrams.len(), 3);
+        assert_eq!(anagrams[&LetterKey::from_word("tca")], vec!["cat", "act"]);
+        assert_eq!(LetterKey::from_word("Tac-T").alphagram(), "actt");
+    }
+}
+
+#[cfg(test)]
+mod letter_counts_tests {
+    use std::collections::HashMap;
+
+    use crate::alphabet::{Cyrillic, English, Greek};
+    use crate::get_letters_count;
+
+    use super::{can_be_constructed, can_be_constructed_with_blanks, LetterCounts, LetterKey};
+
+    #[test]
+    fn from_english_word() {
+        let counts = LetterCounts::from_word::<English>("Dodge!");
+        assert_eq!(counts.get(3), 2);
+        assert_eq!(counts.get(25), 0);
+        assert_eq!(counts.len(), 5);
+        assert_eq!(counts.to_map::<English>(), get_letters_count("Dodge!"));
+    }
+
+    #[test]
+    fn from_greek_word() {
+        let counts = LetterCounts::from_word::<Greek>("Σοφος");
+        assert_eq!(counts.len(), 5);
+        assert_eq!(counts.to_map::<Greek>(), HashMap::from([('σ', 2), ('ο', 2), ('φ', 1)]));
+    }
+
+    #[test]
+    fn from_cyrillic_word() {
+        let counts: LetterCounts<33> = LetterCounts::from_word::<Cyrillic>("Ёлка");
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts.to_map::<Cyrillic>(), HashMap::from([('ё', 1), ('л', 1), ('к', 1), ('а', 1)]));
+    }
+
+    #[test]
+    fn empty() {
+        assert!(LetterCounts::<26>::new().is_empty());
+        assert!(LetterCounts::from_word::<English>("123").is_empty());
+        assert!(!LetterCounts::from_word::<English>("a").is_empty());
+    }
+
+    #[test]
+    fn counts_saturate() {
+        let counts = LetterCounts::from_word::<English>(&"a".repeat(300));
+        assert_eq!(counts.get(0), u8::MAX);
+    }
+
+    #[test]
+    fn subtraction() {
+        let list = LetterCounts::from_word::<English>("tacts");
+        let left = list.checked_sub(&LetterCounts::from_word::<English>("cat")).unwrap();
+        assert_eq!(left, LetterCounts::from_word::<English>("ts"));
+        assert_eq!(left.mask(), 1 << 18 | 1 << 19);
+        assert_eq!(list.checked_sub(&LetterCounts::from_word::<English>("cc")), None);
+    }
+
+    #[test]
+    fn blanks_cover_missing_letters() {
+        let list = LetterCounts::from_word::<English>("cat");
+        let word = |word| LetterCounts::from_word::<English>(word);
+        assert!(can_be_constructed_with_blanks(&word("tact"), &list, 1));
+        assert!(!can_be_constructed_with_blanks(&word("tact"), &list, 0));
+        assert!(can_be_constructed_with_blanks(&word("chat"), &list, 1));
+        assert!(!can_be_constructed_with_blanks(&word("chart"), &list, 1));
+        assert!(!can_be_constructed_with_blanks(&word(""), &list, 5));
+    }
+
+    #[test]
+    fn cost_does_not_depend_on_lengths() {
+        let word = |word: &str| LetterCounts::from_word::<English>(word);
+        let rack = word("tace");
+        let padded = word(&format!("tac{}", "e".repeat(100_000)));
+        // Any rack or word is N saturating counts and a mask, so a check compares at most N counts.
+        assert_eq!((std::mem::size_of_val(&padded), padded.get(4)), (std::mem::size_of::<LetterCounts>(), u8::MAX));
+        for candidate in ["cat", "act", "eat", "tact", "ace", "dog", ""] {
+            assert_eq!(can_be_constructed(&word(candidate), &rack), can_be_constructed(&word(candidate), &padded), "{}", candidate);
+        }
+        let long_word = word(&"e".repeat(100_000));
+        assert_eq!(std::mem::size_of_val(&long_word), std::mem::size_of::<LetterCounts>());
+        assert!(can_be_constructed(&long_word, &padded) && !can_be_constructed(&long_word, &rack));
+    }
+
+    #[test]
+    fn from_map() {
+        let counts = LetterCounts::from(&get_letters_count("dodge"));
+        assert_eq!(counts, LetterCounts::from_word::<English>("dodge"));
+    }
+
+    #[test]
+    fn presence_mask() {
+        assert_eq!(LetterCounts::from_word::<English>("Abba z").mask(), 0b11 | 1 << 25);
+        assert_eq!(LetterCounts::<26>::new().mask(), 0);
+        assert_eq!(LetterKey::from_word("zebra").counts(), LetterCounts::from_word::<English>("zebra"));
+        let list = LetterCounts::from_word::<English>("aabc");
+        assert!(!can_be_constructed(&LetterCounts::from_word::<English>("cd"), &list));
+        assert!(!can_be_constructed(&LetterCounts::from_word::<English>("aaa"), &list));
+        assert!(can_be_constructed(&LetterCounts::from_word::<English>("cab"), &list));
+    }
+}
@@ -0,0 +1,213 @@
+//! Several named dictionaries searched together, e.g. to show which words are valid in one word
+//! list but not in another.
+//!
+//! A [`LexiconSet`] also replaces keeping a word list per language, or per tournament list
+//! (SOWPODS, TWL), next to each other: each lexicon can be tagged with its language, searched
+//! by name with [`LexiconSet::find_in`], or searched with every other one with
+//! [`LexiconSet::find_in_all`].
+
+use std::collections::HashMap;
+
+use crate::merge::kmerge;
+use crate::parallel::*;
+use crate::Dictionary;
+#[cfg(feature = "fs")]
+use crate::FindWordsError;
+
+/// Named dictionaries, kept in the order they were added.
+#[derive(Clone, Debug, Default)]
+pub struct LexiconSet {
+    lexicons: Vec<Lexicon>,
+}
+
+#[derive(Clone, Debug)]
+struct Lexicon {
+    name: String,
+    /// Language tag of the words, e.g. `en` or `fr`.
+    language: Option<String>,
+    dictionary: Dictionary,
+}
+
+/// Which words a search of a [`LexiconSet`] returns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Words found in any lexicon.
+    Union,
+    /// Words found in every lexicon.
+    OnlyCommon,
+}
+
+/// A word found by a search, with the names of the lexicons it was found in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LabeledMatch<'a> {
+    pub word: String,
+    /// Names of the lexicons containing the word, in the order of the set.
+    pub lexicons: Vec<&'a str>,
+}
+
+impl LexiconSet {
+    pub fn new() -> LexiconSet {
+        LexiconSet::default()
+    }
+
+    /// Adds a lexicon, replacing any lexicon with the same name.
+    pub fn add(&mut self, name: &str, dictionary: Dictionary) {
+        self.insert(name, None, dictionary);
+    }
+
+    /// Adds a lexicon tagged with the language of its words, replacing any lexicon with the
+    /// same name.
+    pub fn add_with_language(&mut self, name: &str, language: &str, dictionary: Dictionary) {
+        self.insert(name, Some(language.to_string()), dictionary);
+    }
+
+    /// Reads a word list into a lexicon named `name`, replacing any lexicon with the same name.
+    #[cfg(feature = "fs")]
+    pub fn load(&mut self, name: &str, path: &str) -> Result<(), FindWordsError> {
+        self.add(name, Dictionary::from_file(path)?);
+        Ok(())
+    }
+
+    fn insert(&mut self, name: &str, language: Option<String>, dictionary: Dictionary) {
+        match self.lexicons.iter_mut().find(|lexicon| lexicon.name == name) {
+            Some(existing) => (existing.language, existing.dictionary) = (language, dictionary),
+            None => self.lexicons.push(Lexicon { name: name.to_string(), language, dictionary }),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Dictionary> {
+        self.lexicon(name).map(|lexicon| &lexicon.dictionary)
+    }
+
+    /// Language tag of a lexicon, if it was added with one.
+    pub fn language(&self, name: &str) -> Option<&str> {
+        self.lexicon(name)?.language.as_deref()
+    }
+
+    fn lexicon(&self, name: &str) -> Option<&Lexicon> {
+        self.lexicons.iter().find(|lexicon| lexicon.name == name)
+    }
+
+    /// Names of the lexicons, in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item=&str> {
+        self.lexicons.iter().map(|lexicon| lexicon.name.as_str())
+    }
+
+    /// Names of the lexicons tagged with `language`, in the order they were added.
+    pub fn names_for_language<'s>(&'s self, language: &'s str) -> impl Iterator<Item=&'s str> {
+        self.lexicons.iter()
+            .filter(move |lexicon| lexicon.language.as_deref() == Some(language))
+            .map(|lexicon| lexicon.name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.lexicons.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lexicons.is_empty()
+    }
+
+    /// Words of the lexicon named `name` constructable from `list`, in dictionary order, or
+    /// `None` without a lexicon of that name.
+    pub fn find_in(&self, name: &str, list: &str) -> Option<Vec<&str>> {
+        self.get(name).map(|dictionary| dictionary.find(list))
+    }
+
+    /// Words of every lexicon constructable from `list`, by lexicon name, each in dictionary
+    /// order. The lexicons are searched in parallel.
+    pub fn find_in_all(&self, list: &str) -> HashMap<String, Vec<String>> {
+        self.lexicons.par_iter()
+            .map(|lexicon| (lexicon.name.clone(), lexicon.dictionary.find(list).into_iter().map(str::to_string).collect()))
+            .collect()
+    }
+
+    /// Searches every lexicon in parallel for the words constructable from `list`, and merges the
+    /// results according to `policy`. Words are compared ignoring case, reported in lowercase and
+    /// sorted alphabetically.
+    pub fn find(&self, list: &str, policy: MergePolicy) -> Vec<LabeledMatch<'_>> {
+        let results: Vec<(&str, Vec<String>)> = self.lexicons.par_iter()
+            .map(|Lexicon { name, dictionary, .. }| {
+                let mut words: Vec<String> = dictionary.find(list).into_iter().map(|word| word.to_lowercase()).collect();
+                words.sort_unstable();
+                words.dedup();
+                (name.as_str(), words)
+            })
+            .collect();
+        let mut merged: Vec<LabeledMatch<'_>> = vec![];
+        for (word, index) in kmerge(results.iter().enumerate().map(|(index, (_, words))| words.iter().map(move |word| (word, index)))) {
+            match merged.last_mut() {
+                Some(last) if last.word == *word => last.lexicons.push(results[index].0),
+                _ => merged.push(LabeledMatch { word: word.clone(), lexicons: vec![results[index].0] }),
+            }
+        }
+        merged.retain(|found| policy == MergePolicy::Union || found.lexicons.len() == self.lexicons.len());
+        merged
+    }
+}
+
+#[cfg(test)]
+mod lexicon_set_tests {
+    use crate::{test_words, Dictionary};
+
+    use super::{LabeledMatch, LexiconSet, MergePolicy};
+
+    fn lexicons() -> LexiconSet {
+        let mut lexicons = LexiconSet::new();
+        lexicons.add("twl", test_words(&["cat", "act", "at"]).into());
+        lexicons.add("sowpods", test_words(&["Cat", "tact", "ta", "at"]).into());
+        lexicons
+    }
+
+    fn labeled<'a>(word: &str, lexicons: &[&'a str]) -> LabeledMatch<'a> {
+        LabeledMatch { word: word.to_string(), lexicons: lexicons.to_vec() }
+    }
+
+    #[test]
+    fn union() {
+        let expected = vec![
+            labeled("act", &["twl"]),
+            labeled("at", &["twl", "sowpods"]),
+            labeled("cat", &["twl", "sowpods"]),
+            labeled("ta", &["sowpods"]),
+            labeled("tact", &["sowpods"]),
+        ];
+        assert_eq!(lexicons().find("ttac", MergePolicy::Union), expected);
+    }
+
+    #[test]
+    fn only_common() {
+        let expected = vec![labeled("at", &["twl", "sowpods"]), labeled("cat", &["twl", "sowpods"])];
+        assert_eq!(lexicons().find("ttac", MergePolicy::OnlyCommon), expected);
+    }
+
+    #[test]
+    fn searches_by_name() {
+        let lexicons = lexicons();
+        assert_eq!(lexicons.find_in("sowpods", "ttac"), Some(vec!["Cat", "tact", "ta", "at"]));
+        assert_eq!(lexicons.find_in("en", "ttac"), None);
+        let all = lexicons.find_in_all("tca");
+        assert_eq!(all.len(), 2);
+        assert_eq!(all["twl"], ["cat", "act", "at"]);
+        assert_eq!(all["sowpods"], ["Cat", "ta", "at"]);
+    }
+
+    #[test]
+    fn languages() {
+        let mut lexicons = lexicons();
+        lexicons.add_with_language("fr", "fr", test_words(&["acte", "te"]).into());
+        lexicons.add_with_language("twl", "en", test_words(&["cat"]).into());
+        assert_eq!((lexicons.language("fr"), lexicons.language("twl"), lexicons.language("sowpods")), (Some("fr"), Some("en"), None));
+        assert_eq!(lexicons.names_for_language("en").collect::<Vec<_>>(), vec!["twl"]);
+        assert_eq!(lexicons.find_in("fr", "etca"), Some(vec!["acte", "te"]));
+    }
+
+    #[test]
+    fn replacing_a_lexicon() {
+        let mut lexicons = lexicons();
+        lexicons.add("twl", Dictionary::default());
+        assert_eq!(lexicons.names().collect::<Vec<_>>(), vec!["twl", "sowpods"]);
+        assert!(lexicons.get("twl").unwrap().is_empty());
+        assert!(lexicons.find("ttac", MergePolicy::OnlyCommon).is_empty());
+    }
+}
@@ -9,7 +9,7 @@
 //! ```ignore
 //! fn main() -> Result<(), std::io::Error> {
 //!    let words = read_words_from_file("words.txt")?;
-//!    let list = generate_random_string(Some(20));
+//!    let list = Rack::random(RackSpec::letters(20), &mut thread_rng()).to_string();
 //!    println!("List of letters: {}", list);
 //!    println!("Words that can be constructed");
 //!    for word  in get_constructable_words(words, &get_letters_count(&list)) {
@@ -19,83 +19,420 @@
 //! }
 //! ```
 //!
+//! ## Compatibility
+//! [`read_words_from_file`], [`get_letters_count`], [`get_constructable_words`] and
+//! [`generate_random_string`] keep the signatures and the behavior they had in the first
+//! releases, which `tests/compat.rs` checks, with one change: [`get_constructable_words`] skips
+//! single-letter words since the minimum word length became 2, see below, and
+//! [`get_constructable_words_min_len`] with a minimum of 1 finds them again. New code should
+//! prefer a [`Dictionary`], which indexes the words once and finds the same words, in the same
+//! order, and [`Rack::random`], which [`generate_random_string`] now wraps.
+//!
+//! Version 0.2 breaks one thing: [`Word`] has a public [`rank`](Word::rank) field, so a word
+//! written as a struct literal for 0.1 no longer compiles. Build words with [`Word::new`], or
+//! add `rank: None` to the literal:
+//!
+//! ```compile_fail,E0063
+//! use find_words::{get_letters_count, Word};
+//!
+//! let word = Word { value: "act".to_string(), letters: get_letters_count("act") };
+//! ```
+//!
+//! ## Minimum word length
+//! Searches skip the words shorter than [`DEFAULT_MIN_WORD_LEN`] (2) letters unless the query
+//! asks for another minimum, so that single letters such as "a" do not fill the results. A view
+//! with a lower length bound, e.g. `dictionary.view().lengths(1..)`, the `min_len` of a
+//! [`QueryOptions`](cache::QueryOptions) or of a [`SearchOptions`](search::SearchOptions), and
+//! [`get_constructable_words_min_len`] take the minimum of the query; a minimum of 1 finds every
+//! word, as the first releases did.
+//!
+//! ## Features
+//! The default features can be turned off to embed the core matcher on constrained targets:
+//! - `fs`: reading word lists, deny lists and corpora from files.
+//! - `parallel`: searching in parallel with rayon, on its global pool unless a [`Parallelism`]
+//!   gives another pool or asks for a sequential search. Without it, searches are sequential and
+//!   find the same words in the same order.
+//! - `random`: random letters and tile draws with rand, and the games, bots and simulations
+//!   built on them.
+//!
+//! The optional `embedded-dict` feature compiles the English word list of `words.txt` into the
+//! crate, see [`Dictionary::builtin`], so that no file is needed at run time.
+//!
+//! ## Unsafe code
+//! The crate is `#![forbid(unsafe_code)]` unless a feature needing unsafe code is enabled, and
+//! such code is kept to a module behind an explicitly named feature, which then only denies it
+//! elsewhere: `alloc-count` for the counting global allocator of the `allocation` module, `ffi`
+//! for the C ABI of the `ffi` module, and `wasm` for the bindings generated by wasm-bindgen. An
+//! optimization needing unsafe code, e.g. SIMD or memory-mapped word lists, gets its own feature
+//! in the same way, with a safe implementation compiled without it and tests checking that both
+//! find the same words in the same order.
+//!
+#![cfg_attr(not(any(feature = "alloc-count", feature = "ffi", feature = "wasm")), forbid(unsafe_code))]
+#![cfg_attr(any(feature = "alloc-count", feature = "ffi", feature = "wasm"), deny(unsafe_code))]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+#[cfg(feature = "fs")]
 use std::fs::File;
-use std::io::{BufRead, BufReader, Error};
+use std::io::{BufRead, Error};
+#[cfg(feature = "fs")]
+use std::io::{BufReader, ErrorKind};
+#[cfg(feature = "random")]
 use std::iter;
+use std::ops::ControlFlow;
 
+#[cfg(feature = "random")]
 use rand::prelude::*;
-use rayon::prelude::*;
+
+use crate::alphabet::{English, Folded};
+use crate::letters::can_be_constructed_with_blanks;
+use crate::parallel::*;
+
+pub use dictionary::{Dictionary, DictionaryView, Fingerprint, IndexBuild, Normalization, QueryTrace, SearchStats, UsageStats, WordId};
+pub use error::FindWordsError;
+pub use letters::{can_be_constructed, parse_letters, LetterCounts, LetterKey, ParseError};
+pub use matches::{Grouping, Matches, Weights};
+pub use parallel::Parallelism;
+pub use rack::{Rack, RackSpec, Tile};
+pub use search::Search;
+
+#[cfg(feature = "alloc-count")]
+#[allow(unsafe_code)]
+pub mod allocation;
+pub mod alphabet;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod binary;
+pub mod bloom;
+#[cfg(feature = "random")]
+pub mod bot;
+pub mod cache;
+pub mod compound;
+pub mod corpus;
+pub mod deny;
+pub mod dictionary;
+pub mod distribution;
+pub mod error;
+pub mod families;
+#[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
+pub mod ffi;
+pub mod game;
+#[cfg(feature = "random")]
+pub mod generator;
+pub mod letters;
+pub mod lexicon;
+pub mod limits;
+pub mod load;
+pub mod mapping;
+pub mod matches;
+pub mod merge;
+pub mod notation;
+mod parallel;
+pub mod pattern;
+pub mod phrase;
+pub mod postprocess;
+pub mod puzzle;
+pub mod rack;
+pub mod scheme;
+pub mod scoring;
+pub mod search;
+pub mod sequence;
+pub mod session;
+#[cfg(feature = "random")]
+pub mod simulation;
+#[cfg(feature = "random")]
+pub mod soak;
+pub mod source;
+pub mod suffix;
+pub mod top;
+pub mod trie;
+#[cfg(feature = "wasm")]
+#[allow(unsafe_code)]
+pub mod wasm;
+pub mod wordle;
 
 /// Struct that contains the word and the mapping of characters
 /// that make up the word.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Word {
     pub value: String,
     pub letters: HashMap<char, u8>,
+    /// Position of the word in a list ranked by frequency, 1 for the most frequent word, if the
+    /// list was loaded with ranks, see [`read_ranked_words`](load::read_ranked_words).
+    pub rank: Option<u32>,
+}
+
+impl Word {
+    /// A word without a rank.
+    pub fn new(value: &str) -> Word {
+        Word { value: value.to_string(), letters: get_letters_count(value), rank: None }
+    }
+
+    /// A word without a rank whose letters with diacritics are counted as the letter they are
+    /// written on, see [`Folded`], so that "élève" can be constructed from "eleve". The value
+    /// keeps its diacritics.
+    pub fn folded(value: &str) -> Word {
+        Word { value: value.to_string(), letters: LetterCounts::from_word::<Folded>(value).to_map::<English>(), rank: None }
+    }
 }
 
-/// Returns a `HashMap` containing the character count mapping for a given word.
+/// Minimum number of letters of the words returned by [`get_constructable_words`],
+/// [`Dictionary::find`] and the other searches of a [`DictionaryView`] without a lower length bound.
+pub const DEFAULT_MIN_WORD_LEN: usize = 2;
+
+/// Highest count of a letter in a character count mapping or a [`LetterCounts`]: counts
+/// saturate there, so a list of letters pasted from a long text has at most this many of each
+/// letter. The list still makes every word with at most this many of each of its letters, which
+/// is every word of any real dictionary.
+pub const MAX_LETTER_COUNT: u8 = u8::MAX;
+
+/// Returns a `HashMap` containing the character count mapping for a given word. Counts saturate
+/// at [`MAX_LETTER_COUNT`].
 pub fn get_letters_count(word: &str) -> HashMap<char, u8> {
     word.to_ascii_lowercase()
         .chars()
         .filter(|c| c.is_ascii_alphabetic())
         .fold(HashMap::new(), |mut map, c| {
             let count = map.entry(c).or_insert(0);
-            *count += 1;
+            *count = count.saturating_add(1);
             map
         })
 }
 
+/// Number of letters of a word as [`get_letters_count`] counts them: ASCII letters, ignoring any
+/// other character, so that length bounds, sorting and searches agree on accented words.
+pub fn letter_count(word: &str) -> usize {
+    word.chars().filter(char::is_ascii_alphabetic).count()
+}
+
+/// ASCII letters of a word, lowercased, ignoring any other character.
+pub(crate) fn lowercase_letters(word: &str) -> impl DoubleEndedIterator<Item=char> + '_ {
+    word.chars().filter(char::is_ascii_alphabetic).map(|c| c.to_ascii_lowercase())
+}
+
 /// Compare a `HashMap` containing the character count mapping for a word to the list of random letters.
-fn can_be_constructed(word: &HashMap<char, u8>, list: &HashMap<char, u8>) -> bool {
+/// Same semantics as [`can_be_constructed`], for words that still use the map representation.
+fn map_can_be_constructed(word: &HashMap<char, u8>, list: &HashMap<char, u8>) -> bool {
     if word.is_empty() { return false; }
-    word.iter().all(|(letter, letter_count)| match list.get(letter) {
-        Some(list_letter_count) if list_letter_count >= letter_count => true,
-        _ => false
+    word.iter().all(|(letter, letter_count)| {
+        matches!(list.get(letter), Some(list_letter_count) if list_letter_count >= letter_count)
     })
 }
 
+/// Number of letters in a character count mapping.
+fn word_len(letters: &HashMap<char, u8>) -> usize {
+    letters.values().map(|&count| count as usize).sum()
+}
+
+/// Returns the letters (and how many of each) that `word` needs but `list` does not have.
+fn missing_letters(word: &HashMap<char, u8>, list: &HashMap<char, u8>) -> HashMap<char, u8> {
+    word.iter()
+        .filter_map(|(letter, &letter_count)| {
+            let available = list.get(letter).copied().unwrap_or(0);
+            if letter_count > available { Some((*letter, letter_count - available)) } else { None }
+        })
+        .collect()
+}
+
+/// Letters left in `list` once the letters of `word` are taken off it, or `None` if `list` does
+/// not have them. Letters used up are removed from the mapping.
+pub fn subtract_letters(list: &HashMap<char, u8>, word: &str) -> Option<HashMap<char, u8>> {
+    let mut left = list.clone();
+    for (letter, count) in get_letters_count(word) {
+        let available = left.get_mut(&letter).filter(|available| **available >= count)?;
+        *available -= count;
+        if *available == 0 { left.remove(&letter); }
+    }
+    Some(left)
+}
+
 /// Read words from a file and puts them into a vector containing `Word` structs
+///
+/// A line that is not valid UTF-8 fails with an error of kind
+/// [`InvalidData`](std::io::ErrorKind::InvalidData), from which [`FindWordsError::from_io`]
+/// recovers the line number. [`read_words`] returns a [`FindWordsError`] directly.
+#[cfg(feature = "fs")]
 pub fn read_words_from_file(file: &str) -> Result<Vec<Word>, Error> {
-    let mut words = vec![];
+    Ok(read_words(file)?)
+}
+
+/// Same as [`read_words_from_file`], telling a file that cannot be read from a file whose lines
+/// are not text.
+#[cfg(feature = "fs")]
+pub fn read_words(path: &str) -> Result<Vec<Word>, FindWordsError> {
+    let io_error = |source| FindWordsError::Io { path: path.to_string(), source };
 
-    let file = File::open(file)?;
-    let buf_reader = BufReader::new(file);
+    let file = File::open(path).map_err(io_error)?;
+    read_lines(BufReader::new(file)).map_err(|(line, error)| match error.kind() {
+        ErrorKind::InvalidData => FindWordsError::MalformedLine { path: path.to_string(), line },
+        _ => io_error(error),
+    })
+}
+
+/// Reads a word per line from any reader, e.g. embedded bytes, stdin or the body of a network
+/// response, as [`read_words_from_file`] reads a file. The crate does not decompress lists
+/// itself: a compressed list is read through a decoder of the caller, e.g.
+/// `BufReader::new(GzDecoder::new(file))` with flate2.
+pub fn read_words_from_reader<R: BufRead>(reader: R) -> Result<Vec<Word>, Error> {
+    read_lines(reader).map_err(|(_, error)| error)
+}
 
-    for line in buf_reader.lines() {
-        let value = line?;
+/// Words of the lines of `reader`, or the first error and the number (starting at 1) of the line
+/// it was raised on.
+fn read_lines<R: BufRead>(reader: R) -> Result<Vec<Word>, (usize, Error)> {
+    let mut words = vec![];
+    for (index, line) in reader.lines().enumerate() {
+        let value = line.map_err(|error| (index + 1, error))?;
         let letters = get_letters_count(&value);
-        words.push(Word { value, letters });
+        words.push(Word { value, letters, rank: None });
     }
-
     Ok(words)
 }
 
+/// The words of the English word list compiled into the crate, in the order of `words.txt`.
+#[cfg(feature = "embedded-dict")]
+pub fn builtin_words() -> Vec<Word> {
+    include_str!("../words.txt").lines().map(Word::new).collect()
+}
 
-/// Retrieve the constructable `Word` objects from the list
+/// Retrieve the constructable `Word` objects from the list, skipping words shorter than
+/// [`DEFAULT_MIN_WORD_LEN`].
 pub fn get_constructable_words(words: Vec<Word>, list: &HashMap<char, u8>) -> Vec<String> {
+    get_constructable_words_min_len(words, list, DEFAULT_MIN_WORD_LEN)
+}
+
+/// Same as [`get_constructable_words`], skipping words shorter than `min_len` instead, e.g. 1 to
+/// also find single-letter words such as "a".
+pub fn get_constructable_words_min_len(words: Vec<Word>, list: &HashMap<char, u8>, min_len: usize) -> Vec<String> {
+    let list = LetterCounts::from(list);
     words.into_par_iter()
-        .filter_map(|Word { value, letters }|
-            if can_be_constructed(&letters, &list) { Some(value) } else { None }
-        )
+        .filter_map(|Word { value, letters, .. }| is_constructable(&letters, &list, min_len).then_some(value))
         .collect()
 }
 
+/// Same as [`get_constructable_words`], searching where `parallelism` says: sequentially on the
+/// calling thread, on rayon's global pool or on a pool of the caller.
+pub fn get_constructable_words_with(words: Vec<Word>, list: &HashMap<char, u8>, parallelism: &Parallelism) -> Vec<String> {
+    if parallelism.is_sequential() { return get_constructable_words_iter(words, list).collect(); }
+    parallelism.install(|| get_constructable_words(words, list))
+}
+
+/// Same as [`get_constructable_words`], yielding the words one at a time as they are found, so
+/// that callers can stop early or write them out without collecting them. Searches sequentially.
+pub fn get_constructable_words_iter<I: IntoIterator<Item=Word>>(words: I, list: &HashMap<char, u8>) -> impl Iterator<Item=String> {
+    let list = LetterCounts::from(list);
+    words.into_iter()
+        .filter_map(move |Word { value, letters, .. }| is_constructable(&letters, &list, DEFAULT_MIN_WORD_LEN).then_some(value))
+}
+
+/// Same as [`get_constructable_words`], as a parallel iterator for callers that consume the words
+/// in parallel, e.g. with `find_any` or `for_each`, instead of collecting them.
+#[cfg(feature = "parallel")]
+pub fn get_constructable_words_par_iter(words: Vec<Word>, list: &HashMap<char, u8>) -> impl rayon::iter::ParallelIterator<Item=String> {
+    let list = LetterCounts::from(list);
+    words.into_par_iter()
+        .filter_map(move |Word { value, letters, .. }| is_constructable(&letters, &list, DEFAULT_MIN_WORD_LEN).then_some(value))
+}
+
+/// Calls `visit` with every word of `words` that can be constructed from `list`, in order, until
+/// it returns [`ControlFlow::Break`]. Returns `Break` if `visit` stopped the search.
+pub fn for_each_constructable_word<F: FnMut(&str) -> ControlFlow<()>>(words: &[Word], list: &HashMap<char, u8>, mut visit: F) -> ControlFlow<()> {
+    let list = LetterCounts::from(list);
+    words.iter()
+        .filter(|word| is_constructable(&word.letters, &list, DEFAULT_MIN_WORD_LEN))
+        .try_for_each(|word| visit(&word.value))
+}
+
+/// Same as [`get_constructable_words`] for each of `lists`, in order. The words are borrowed and
+/// counted once for every list, and the lists are searched in parallel, each one sequentially,
+/// which suits many short queries against the same words, e.g. simulated deals.
+pub fn get_constructable_words_batch(words: &[Word], lists: &[HashMap<char, u8>]) -> Vec<Vec<String>> {
+    let candidates: Vec<(&str, LetterCounts)> = words.par_iter()
+        .filter(|word| word_len(&word.letters) >= DEFAULT_MIN_WORD_LEN)
+        .map(|word| (word.value.as_str(), LetterCounts::from(&word.letters)))
+        .collect();
+    lists.par_iter()
+        .map(|list| {
+            let list = LetterCounts::from(list);
+            candidates.iter()
+                .filter(|(_, letters)| can_be_constructed(letters, &list))
+                .map(|(value, _)| value.to_string())
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns `true` if a word with these letters is long enough and can be constructed from `list`.
+fn is_constructable(letters: &HashMap<char, u8>, list: &LetterCounts, min_len: usize) -> bool {
+    word_len(letters) >= min_len && can_be_constructed(&LetterCounts::from(letters), list)
+}
+
+/// Retrieve the words that `list` could make with at most `max_missing` more letters, in the
+/// order of `words`, each with the letters it is short of, e.g. to tell which letter to draw or
+/// trade for. Words `list` can make are returned with no missing letters. Words shorter than
+/// [`DEFAULT_MIN_WORD_LEN`] are skipped.
+pub fn find_nearly_constructable(words: &[Word], list: &HashMap<char, u8>, max_missing: usize) -> Vec<(String, HashMap<char, u8>)> {
+    let counts = LetterCounts::from(list);
+    words.par_iter()
+        .filter(|word| word_len(&word.letters) >= DEFAULT_MIN_WORD_LEN && can_be_constructed_with_blanks(&LetterCounts::from(&word.letters), &counts, max_missing))
+        .map(|word| (word.value.clone(), missing_letters(&word.letters, list)))
+        .collect()
+}
+
+/// Retrieve the words made of exactly the letters of `list`, in the order of `words`. They use
+/// every letter of the list, so the minimum word length does not apply.
+pub fn find_anagrams(words: &[Word], list: &str) -> Vec<String> {
+    let list = get_letters_count(list);
+    if list.is_empty() { return vec![]; }
+    words.par_iter()
+        .filter(|word| word.letters == list)
+        .map(|word| word.value.clone())
+        .collect()
+}
+
+/// Retrieve the words that [`get_constructable_words`] finds for `list`, grouped by number of
+/// letters, each group in the order of `words`.
+pub fn find_subanagrams(words: &[Word], list: &str) -> BTreeMap<usize, Vec<String>> {
+    let list = LetterCounts::from(&get_letters_count(list));
+    let found: Vec<(usize, String)> = words.par_iter()
+        .filter(|word| is_constructable(&word.letters, &list, DEFAULT_MIN_WORD_LEN))
+        .map(|word| (word_len(&word.letters), word.value.clone()))
+        .collect();
+    let mut groups: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for (len, value) in found {
+        groups.entry(len).or_default().push(value);
+    }
+    groups
+}
+
 /// Used to generate a random string given a length as an input. If `None` is passed in,
 /// a random string of possible length from 1 to 200 is generated.
+#[cfg(feature = "random")]
+#[deprecated(note = "use `Rack::random`, and `to_string` for display")]
 pub fn generate_random_string(length: Option<u8>) -> String {
-    let mut rng = thread_rng();
-    let length = length.unwrap_or_else(|| rng.gen_range(1..=200));
+    Rack::random(RackSpec { letters: length, blanks: 0 }, &mut thread_rng()).to_string()
+}
+
+/// Random string of `length` lowercase ASCII letters.
+#[cfg(feature = "random")]
+fn random_string<R: Rng + ?Sized>(rng: &mut R, length: u8) -> String {
     iter::repeat_with(|| rng.gen_range('a'..='z')).take(length as usize).collect()
 }
 
+/// Builds `Word` structs for tests.
+#[cfg(test)]
+fn test_words(values: &[&str]) -> Vec<Word> {
+    values.iter()
+        .map(|&value| Word::new(value))
+        .collect()
+}
+
 #[cfg(test)]
 mod get_letters_count_tests {
     use std::collections::HashMap;
 
-    use super::get_letters_count;
+    use super::{get_letters_count, MAX_LETTER_COUNT};
 
     #[test]
     fn empty_string() {
@@ -104,6 +441,12 @@ mod get_letters_count_tests {
         assert_eq!(output, expected);
     }
 
+    #[test]
+    fn counts_saturate() {
+        let output = get_letters_count(&format!("{}bb", "a".repeat(1000)));
+        assert_eq!(output, HashMap::from([('a', MAX_LETTER_COUNT), ('b', 2)]));
+    }
+
     #[test]
     fn only_lowercase() {
         let output = get_letters_count("dodge");
@@ -190,14 +533,98 @@ mod get_letters_count_tests {
     }
 }
 
+#[cfg(test)]
+mod constructable_words_iter_tests {
+    use std::ops::ControlFlow;
+
+    use crate::{for_each_constructable_word, get_constructable_words, get_constructable_words_batch, get_constructable_words_iter, get_letters_count, test_words};
+
+    fn words() -> Vec<crate::Word> {
+        test_words(&["a", "at", "cat", "dog", "act", "tact", "ta"])
+    }
+
+    #[test]
+    fn iterator_yields_the_same_words() {
+        let list = get_letters_count("tca");
+        let all: Vec<String> = get_constructable_words_iter(words(), &list).collect();
+        assert_eq!(all, get_constructable_words(words(), &list));
+        assert_eq!(get_constructable_words_iter(words(), &list).take(2).collect::<Vec<_>>(), vec!["at", "cat"]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_iterator() {
+        use rayon::iter::ParallelIterator;
+
+        let list = get_letters_count("tca");
+        let mut all: Vec<String> = crate::get_constructable_words_par_iter(words(), &list).collect();
+        all.sort();
+        assert_eq!(all, vec!["act", "at", "cat", "ta"]);
+        assert!(crate::get_constructable_words_par_iter(words(), &list).any(|word| word == "ta"));
+    }
+
+    #[test]
+    fn visitor_can_stop() {
+        let list = get_letters_count("tca");
+        let mut seen = vec![];
+        let flow = for_each_constructable_word(&words(), &list, |word| {
+            seen.push(word.to_string());
+            if word == "cat" { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        });
+        assert_eq!((flow, seen), (ControlFlow::Break(()), vec!["at".to_string(), "cat".to_string()]));
+        assert_eq!(for_each_constructable_word(&words(), &list, |_| ControlFlow::Continue(())), ControlFlow::Continue(()));
+    }
+
+    #[test]
+    fn batch_keeps_the_order_of_the_lists() {
+        let lists: Vec<_> = ["tca", "god", "", "ttac"].iter().map(|list| get_letters_count(list)).collect();
+        let expected: Vec<Vec<String>> = lists.iter().map(|list| get_constructable_words(words(), list)).collect();
+        assert_eq!(get_constructable_words_batch(&words(), &lists), expected);
+        assert_eq!(expected[3], vec!["at", "cat", "act", "tact", "ta"]);
+        assert!(get_constructable_words_batch(&words(), &[]).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod find_anagrams_tests {
+    use std::collections::BTreeMap;
+
+    use crate::{find_anagrams, find_subanagrams, test_words};
+
+    #[test]
+    fn anagrams_use_every_letter() {
+        let words = test_words(&["cat", "Act", "tact", "a-ct", "at", "123"]);
+        assert_eq!(find_anagrams(&words, "TCA"), vec!["cat", "Act", "a-ct"]);
+        assert!(find_anagrams(&words, "tc").is_empty());
+        assert!(find_anagrams(&words, "").is_empty());
+    }
+
+    #[test]
+    fn subanagrams_by_length() {
+        let words = test_words(&["a", "tact", "cat", "at", "act", "ta", "dog"]);
+        let expected = BTreeMap::from([
+            (2, vec!["at".to_string(), "ta".to_string()]),
+            (3, vec!["cat".to_string(), "act".to_string()]),
+            (4, vec!["tact".to_string()]),
+        ]);
+        assert_eq!(find_subanagrams(&words, "tact"), expected);
+        assert!(find_subanagrams(&words, "xyz").is_empty());
+    }
+}
+
 #[cfg(test)]
 mod can_be_constructed_tests {
-    use crate::{can_be_constructed, get_letters_count};
+    use crate::alphabet::English;
+    use crate::{can_be_constructed, get_letters_count, LetterCounts};
+
+    fn letters(word: &str) -> LetterCounts {
+        LetterCounts::from(&get_letters_count(word))
+    }
 
     #[test]
     fn word_can_be_constructed() {
-        let word = get_letters_count("dog");
-        let list = get_letters_count("dodge");
+        let word = letters("dog");
+        let list = letters("dodge");
 
         assert!(can_be_constructed(&word, &list));
     }
@@ -205,8 +632,8 @@ mod can_be_constructed_tests {
     #[test]
     #[should_panic]
     fn word_cannot_be_constructed() {
-        let word = get_letters_count("dodgy");
-        let list = get_letters_count("dodge");
+        let word = letters("dodgy");
+        let list = letters("dodge");
 
         assert!(can_be_constructed(&word, &list));
     }
@@ -214,8 +641,8 @@ mod can_be_constructed_tests {
     #[test]
     #[should_panic]
     fn empty_list() {
-        let word = get_letters_count("something");
-        let list = get_letters_count("");
+        let word = letters("something");
+        let list = letters("");
 
         assert!(can_be_constructed(&word, &list));
     }
@@ -223,14 +650,101 @@ mod can_be_constructed_tests {
     #[test]
     #[should_panic]
     fn empty_string_for_word() {
-        let word = get_letters_count("");
-        let list = get_letters_count("list");
+        let word = letters("");
+        let list = letters("list");
 
         assert!(can_be_constructed(&word, &list));
     }
+
+    #[test]
+    fn equal_counts() {
+        let word = letters("deed");
+        let list = letters("dede");
+
+        assert!(can_be_constructed(&word, &list));
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_word_and_empty_list() {
+        let word = letters("");
+        let list = letters("");
+
+        assert!(can_be_constructed(&word, &list));
+    }
+
+    #[test]
+    fn saturated_counts() {
+        let word = LetterCounts::from_word::<English>(&"e".repeat(255));
+        let list = LetterCounts::from_word::<English>(&"e".repeat(1000));
+
+        assert!(can_be_constructed(&word, &list));
+        assert!(!can_be_constructed(&list, &LetterCounts::from_word::<English>(&"e".repeat(254))));
+    }
+}
+
+#[cfg(test)]
+mod missing_letters_tests {
+    use std::collections::HashMap;
+
+    use crate::{get_letters_count, missing_letters};
+
+    #[test]
+    fn nothing_missing() {
+        let word = get_letters_count("dog");
+        let list = get_letters_count("dodge");
+
+        assert_eq!(missing_letters(&word, &list), HashMap::new());
+    }
+
+    #[test]
+    fn counts_shortfall() {
+        let word = get_letters_count("deeds");
+        let list = get_letters_count("dodge");
+
+        assert_eq!(missing_letters(&word, &list), HashMap::from([('e', 1), ('s', 1)]));
+    }
 }
 
 #[cfg(test)]
+mod find_nearly_constructable_tests {
+    use std::collections::HashMap;
+
+    use crate::{find_nearly_constructable, get_letters_count, test_words};
+
+    #[test]
+    fn missing_letters_of_each_word() {
+        let words = test_words(&["cat", "tact", "chat", "chant", "dog", "a", "c"]);
+        let list = get_letters_count("tac");
+        assert_eq!(find_nearly_constructable(&words, &list, 0), vec![("cat".to_string(), HashMap::new())]);
+        assert_eq!(find_nearly_constructable(&words, &list, 1), vec![
+            ("cat".to_string(), HashMap::new()),
+            ("tact".to_string(), HashMap::from([('t', 1)])),
+            ("chat".to_string(), HashMap::from([('h', 1)])),
+        ]);
+        assert_eq!(find_nearly_constructable(&words, &list, 2)[3], ("chant".to_string(), HashMap::from([('h', 1), ('n', 1)])));
+        assert_eq!(find_nearly_constructable(&words, &list, 3).len(), 5);
+    }
+}
+
+#[cfg(test)]
+mod subtract_letters_tests {
+    use std::collections::HashMap;
+
+    use crate::{get_letters_count, subtract_letters};
+
+    #[test]
+    fn letters_left() {
+        let list = get_letters_count("dodge");
+        assert_eq!(subtract_letters(&list, "Dog"), Some(HashMap::from([('d', 1), ('e', 1)])));
+        assert_eq!(subtract_letters(&list, "dodge"), Some(HashMap::new()));
+        assert_eq!(subtract_letters(&list, "go-go"), None);
+        assert_eq!(subtract_letters(&list, "cat"), None);
+    }
+}
+
+#[cfg(all(test, feature = "random"))]
+#[allow(deprecated)]
 mod generate_random_string_tests {
     use crate::generate_random_string;
 
@@ -284,12 +798,13 @@ mod generate_random_string_tests {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "fs"))]
 mod read_words_from_file_tests {
     use std::collections::HashMap;
     use std::env;
+    use std::fs;
 
-    use crate::{read_words_from_file, Word};
+    use crate::{read_words, read_words_from_file, FindWordsError, Word};
 
     fn get_file_path() -> String {
         let path = env::current_dir().unwrap();
@@ -308,6 +823,7 @@ mod read_words_from_file_tests {
                     ('o', 1),
                     ('w', 1)
                 ]),
+                rank: None,
             },
             Word {
                 value: String::from("dog"),
@@ -316,6 +832,7 @@ mod read_words_from_file_tests {
                     ('o', 1),
                     ('g', 1)
                 ]),
+                rank: None,
             },
             Word {
                 value: String::from("milk"),
@@ -325,6 +842,7 @@ mod read_words_from_file_tests {
                     ('l', 1),
                     ('k', 1)
                 ]),
+                rank: None,
             },
             Word {
                 value: String::from("cat"),
@@ -333,6 +851,7 @@ mod read_words_from_file_tests {
                     ('a', 1),
                     ('t', 1)
                 ]),
+                rank: None,
             },
             Word {
                 value: String::from("goat"),
@@ -342,6 +861,7 @@ mod read_words_from_file_tests {
                     ('a', 1),
                     ('t', 1)
                 ]),
+                rank: None,
             },
             Word {
                 value: String::from("mammal"),
@@ -350,6 +870,7 @@ mod read_words_from_file_tests {
                     ('a', 2),
                     ('l', 1)
                 ]),
+                rank: None,
             },
         ];
         assert_eq!(output, expected);
@@ -360,5 +881,37 @@ mod read_words_from_file_tests {
         let filename = "invalid_file.txt";
         let output = read_words_from_file(filename);
         assert!(output.is_err());
+        assert!(matches!(read_words(filename), Err(FindWordsError::Io { path, .. }) if path == filename));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn malformed_line() {
+        let path = env::temp_dir().join(format!("find-words-malformed-{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, b"cat\nd\xffg\n").unwrap();
+        assert!(matches!(read_words(path), Err(FindWordsError::MalformedLine { line: 2, .. })));
+        let error = read_words_from_file(path).unwrap_err();
+        assert!(matches!(FindWordsError::from_io(&error), Some(FindWordsError::MalformedLine { line: 2, .. })));
+        fs::remove_file(path).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod read_words_from_reader_tests {
+    use std::io::{Cursor, ErrorKind};
+
+    use crate::{read_words_from_reader, Word};
+
+    #[test]
+    fn lines_of_bytes() {
+        let words = read_words_from_reader(Cursor::new(b"cow\r\nmilk\n\nmammal")).unwrap();
+        assert_eq!(words, vec![Word::new("cow"), Word::new("milk"), Word::new(""), Word::new("mammal")]);
+        assert_eq!(read_words_from_reader(&b""[..]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn malformed_line() {
+        let error = read_words_from_reader(Cursor::new(b"cat\nd\xffg\n")).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,203 @@
+//! Size limits for queries coming from untrusted users.
+//!
+//! Services exposing the crate to end users should check every query against a
+//! [`QueryLimits`] before running it. The defaults are deliberately conservative.
+//!
+//! Truncated results are deterministic: searches run in parallel, but the words kept only
+//! depend on the words, the list and the [`SortOrder`], never on the number of threads.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::{get_constructable_words, get_letters_count, letter_count, Word};
+
+/// Characters accepted as wildcards (blank tiles) in a list of letters.
+pub const WILDCARDS: [char; 2] = ['?', '*'];
+
+/// Limits enforced on a single query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueryLimits {
+    /// Maximum number of letters in the list, wildcards included.
+    pub max_letters: usize,
+    /// Maximum number of wildcards in the list.
+    pub max_wildcards: usize,
+    /// Maximum number of words returned; longer results are truncated.
+    pub max_results: usize,
+    /// Maximum number of words in a phrase anagram.
+    pub max_phrase_words: usize,
+}
+
+/// Which words a result truncated to [`QueryLimits::max_results`] keeps, and in which order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// The order of the word list.
+    #[default]
+    Dictionary,
+    Alphabetical,
+    /// The words with the most letters first, alphabetically among words of the same length.
+    LongestFirst,
+}
+
+/// Error returned when a query exceeds one of its [`QueryLimits`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LimitError {
+    TooManyLetters { count: usize, max: usize },
+    TooManyWildcards { count: usize, max: usize },
+    TooManyPhraseWords { count: usize, max: usize },
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        QueryLimits {
+            max_letters: 64,
+            max_wildcards: 2,
+            max_results: 1000,
+            max_phrase_words: 4,
+        }
+    }
+}
+
+impl QueryLimits {
+    /// Limits that accept any query.
+    pub fn unlimited() -> Self {
+        QueryLimits {
+            max_letters: usize::MAX,
+            max_wildcards: usize::MAX,
+            max_results: usize::MAX,
+            max_phrase_words: usize::MAX,
+        }
+    }
+
+    /// Checks the number of letters and wildcards in a list of letters.
+    pub fn check_letters(&self, list: &str) -> Result<(), LimitError> {
+        let wildcards = list.chars().filter(|c| WILDCARDS.contains(c)).count();
+        let count = letter_count(list) + wildcards;
+        if count > self.max_letters {
+            return Err(LimitError::TooManyLetters { count, max: self.max_letters });
+        }
+        if wildcards > self.max_wildcards {
+            return Err(LimitError::TooManyWildcards { count: wildcards, max: self.max_wildcards });
+        }
+        Ok(())
+    }
+
+    /// Checks the number of words requested for a phrase anagram.
+    pub fn check_phrase_words(&self, count: usize) -> Result<(), LimitError> {
+        if count > self.max_phrase_words {
+            return Err(LimitError::TooManyPhraseWords { count, max: self.max_phrase_words });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitError::TooManyLetters { count, max } =>
+                write!(f, "list contains {} letters, at most {} are allowed", count, max),
+            LimitError::TooManyWildcards { count, max } =>
+                write!(f, "list contains {} wildcards, at most {} are allowed", count, max),
+            LimitError::TooManyPhraseWords { count, max } =>
+                write!(f, "phrase of {} words requested, at most {} are allowed", count, max),
+        }
+    }
+}
+
+impl Error for LimitError {}
+
+/// Same as [`get_constructable_words`], but checks `list` against `limits` first and
+/// truncates the result to its first `limits.max_results` words, in the order of `words`.
+pub fn get_constructable_words_limited(words: Vec<Word>, list: &str, limits: &QueryLimits) -> Result<Vec<String>, LimitError> {
+    get_constructable_words_sorted(words, list, limits, SortOrder::Dictionary)
+}
+
+/// Same as [`get_constructable_words_limited`], keeping the first `limits.max_results` words in
+/// `order`. Words that compare equal, i.e. duplicates, keep the order of `words`.
+pub fn get_constructable_words_sorted(words: Vec<Word>, list: &str, limits: &QueryLimits, order: SortOrder) -> Result<Vec<String>, LimitError> {
+    limits.check_letters(list)?;
+    let mut output = get_constructable_words(words, &get_letters_count(list));
+    match order {
+        SortOrder::Dictionary => {}
+        SortOrder::Alphabetical => output.sort(),
+        SortOrder::LongestFirst => output.sort_by(|a, b| letter_count(b).cmp(&letter_count(a)).then(a.cmp(b))),
+    }
+    output.truncate(limits.max_results);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod query_limits_tests {
+    use crate::test_words;
+
+    use super::{get_constructable_words_limited, get_constructable_words_sorted, LimitError, QueryLimits, SortOrder};
+
+    #[test]
+    fn within_limits() {
+        assert_eq!(QueryLimits::default().check_letters("abc?d"), Ok(()));
+    }
+
+    #[test]
+    fn too_many_letters() {
+        let limits = QueryLimits { max_letters: 4, ..QueryLimits::default() };
+        let expected = Err(LimitError::TooManyLetters { count: 5, max: 4 });
+        assert_eq!(limits.check_letters("ab cd?"), expected);
+    }
+
+    #[test]
+    fn too_many_wildcards() {
+        let expected = Err(LimitError::TooManyWildcards { count: 3, max: 2 });
+        assert_eq!(QueryLimits::default().check_letters("a?*?"), expected);
+    }
+
+    #[test]
+    fn too_many_phrase_words() {
+        let expected = Err(LimitError::TooManyPhraseWords { count: 5, max: 4 });
+        assert_eq!(QueryLimits::default().check_phrase_words(5), expected);
+    }
+
+    #[test]
+    fn unlimited_accepts_anything() {
+        let limits = QueryLimits::unlimited();
+        assert!(limits.check_letters(&"a?".repeat(1000)).is_ok());
+        assert!(limits.check_phrase_words(1000).is_ok());
+    }
+
+    #[test]
+    fn results_are_truncated() {
+        let limits = QueryLimits { max_results: 2, ..QueryLimits::default() };
+        let output = get_constructable_words_limited(test_words(&["a", "at", "cat", "act"]), "cat", &limits);
+        assert_eq!(output, Ok(vec!["at".to_string(), "cat".to_string()]));
+    }
+
+    #[test]
+    fn sorted_results_are_truncated() {
+        let words = test_words(&["cat", "a", "tact", "act", "at", "Act"]);
+        let limits = QueryLimits { max_results: 3, ..QueryLimits::default() };
+        let output = get_constructable_words_sorted(words.clone(), "tact", &limits, SortOrder::LongestFirst).unwrap();
+        assert_eq!(output, vec!["tact", "Act", "act"]);
+        let output = get_constructable_words_sorted(words, "tact", &limits, SortOrder::Alphabetical).unwrap();
+        assert_eq!(output, vec!["Act", "act", "at"]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn truncation_does_not_depend_on_threads() {
+        let values: Vec<String> = (0..2000).map(|i| format!("{}{}", ["cat", "act", "at", "tact"][i % 4], "a".repeat(i % 7))).collect();
+        let words = test_words(&values.iter().map(String::as_str).collect::<Vec<_>>());
+        let limits = QueryLimits { max_results: 25, ..QueryLimits::default() };
+        for order in [SortOrder::Dictionary, SortOrder::Alphabetical, SortOrder::LongestFirst] {
+            let results: Vec<Vec<String>> = [1, 2, 3, 8].iter()
+                .map(|&threads| rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap()
+                    .install(|| get_constructable_words_sorted(words.clone(), "tacaaaaaat", &limits, order).unwrap()))
+                .collect();
+            assert_eq!(results[0].len(), 25);
+            assert!(results.iter().all(|result| *result == results[0]), "{:?}", order);
+        }
+    }
+
+    #[test]
+    fn error_message() {
+        let error = LimitError::TooManyLetters { count: 5, max: 4 };
+        assert_eq!(error.to_string(), "list contains 5 letters, at most 4 are allowed");
+    }
+}
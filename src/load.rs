@@ -0,0 +1,408 @@
+//! Loading word lists while reporting data-quality issues.
+//!
+//! [`read_words_from_file`](crate::read_words_from_file) keeps every line verbatim.
+//! [`read_words_from_file_with_warnings`] cleans the list up instead and reports every
+//! change it made as a [`LoadWarning`], without failing the load.
+//!
+//! Applications that never search racks longer than some length (15 letters for Scrabble) can
+//! also leave out the words that are longer than that with [`read_words_up_to_len`], saving the
+//! memory and search time they would cost. Those words are only counted, not warned about.
+//!
+//! Lists sorted by frequency, or with a rank column, can be loaded with [`read_ranked_words`],
+//! which stores the rank of every word as [`Word::rank`], a proxy for frequency data.
+//!
+//! [`LoadOptions`] choose which clean-ups and filters apply to a list, e.g. lowercasing the words
+//! and leaving out hyphenated entries, and count the words each of them left out in
+//! [`LoadCounts`] rather than warning about every line. They apply to words already read, with
+//! [`LoadOptions::apply`], to any reader and to files, see also
+//! [`Dictionary::from_file_with`](crate::Dictionary::from_file_with).
+
+#[cfg(feature = "fs")]
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+#[cfg(feature = "fs")]
+use std::fs::File;
+use std::io::{BufRead, Error};
+#[cfg(feature = "fs")]
+use std::io::BufReader;
+
+#[cfg(feature = "fs")]
+use crate::FindWordsError;
+use crate::{get_letters_count, read_words_from_reader, word_len, Word};
+
+/// Why a line was left out of the loaded words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    Empty,
+    NoLetters,
+}
+
+/// A data-quality issue found while loading. Line numbers start at 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoadWarning {
+    SkippedLine { line: usize, reason: SkipReason },
+    Duplicate { line: usize, word: String, first_line: usize },
+    Normalized { line: usize, original: String, normalized: String },
+    /// The rank column is missing (`rank` is empty) or is not a number. The word is loaded
+    /// without a rank.
+    InvalidRank { line: usize, rank: String },
+}
+
+/// Where [`read_ranked_words`] takes the rank of every word from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ranking {
+    /// The line number of the word, for lists sorted from the most to the least frequent word.
+    LineNumber,
+    /// The last whitespace-separated field of the line, e.g. `the 1`.
+    Column,
+}
+
+/// Words loaded from a list along with the warnings raised while loading them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Loaded {
+    pub words: Vec<Word>,
+    pub warnings: Vec<LoadWarning>,
+    /// Number of words left out for being longer than the maximum word length.
+    pub skipped_too_long: usize,
+}
+
+/// Clean-ups and filters applied to the words of a list. The default keeps every word verbatim,
+/// as [`read_words_from_file`](crate::read_words_from_file) does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Removes a leading byte order mark, surrounding whitespace and a trailing carriage return.
+    pub trim: bool,
+    /// Lowercases the words, so that proper nouns and mixed-case entries become plain words.
+    pub lowercase: bool,
+    /// Leaves out the words with a character that is not a letter, such as a hyphen, an
+    /// apostrophe, a digit or a space. Empty words are left out too.
+    pub alphabetic_only: bool,
+    /// Leaves out the words of fewer letters.
+    pub min_len: Option<usize>,
+    /// Leaves out the words of more letters.
+    pub max_len: Option<usize>,
+    /// Leaves out the words already loaded, after trimming and lowercasing.
+    pub dedupe: bool,
+}
+
+/// Number of words loaded, and of words left out by each filter of [`LoadOptions`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadCounts {
+    pub loaded: usize,
+    /// Words changed by trimming or lowercasing, and loaded.
+    pub normalized: usize,
+    pub non_alphabetic: usize,
+    pub too_short: usize,
+    pub too_long: usize,
+    pub duplicates: usize,
+}
+
+impl LoadCounts {
+    /// Number of words left out.
+    pub fn skipped(&self) -> usize {
+        self.non_alphabetic + self.too_short + self.too_long + self.duplicates
+    }
+}
+
+impl LoadOptions {
+    /// Every clean-up, and every filter but the length bounds.
+    pub fn clean() -> Self {
+        LoadOptions { trim: true, lowercase: true, alphabetic_only: true, min_len: None, max_len: None, dedupe: true }
+    }
+
+    /// Cleans up and filters words, keeping the order of the words left.
+    pub fn apply(&self, words: Vec<Word>) -> (Vec<Word>, LoadCounts) {
+        let mut counts = LoadCounts::default();
+        let mut seen = HashSet::new();
+        let mut kept = Vec::with_capacity(words.len());
+        for mut word in words {
+            let mut value = word.value.as_str();
+            if self.trim {
+                value = value.trim_start_matches('\u{feff}').trim();
+            }
+            let value = if self.lowercase { value.to_lowercase() } else { value.to_string() };
+            if self.alphabetic_only && (value.is_empty() || !value.chars().all(char::is_alphabetic)) {
+                counts.non_alphabetic += 1;
+                continue;
+            }
+            if value != word.value {
+                word.letters = get_letters_count(&value);
+            }
+            let len = word_len(&word.letters);
+            if self.min_len.is_some_and(|min_len| len < min_len) {
+                counts.too_short += 1;
+                continue;
+            }
+            if self.max_len.is_some_and(|max_len| len > max_len) {
+                counts.too_long += 1;
+                continue;
+            }
+            if self.dedupe && !seen.insert(value.clone()) {
+                counts.duplicates += 1;
+                continue;
+            }
+            if value != word.value {
+                counts.normalized += 1;
+                word.value = value;
+            }
+            kept.push(word);
+        }
+        counts.loaded = kept.len();
+        (kept, counts)
+    }
+
+    /// Reads a word per line from `reader`, as [`read_words_from_reader`] does, and applies the options.
+    pub fn read<R: BufRead>(&self, reader: R) -> Result<(Vec<Word>, LoadCounts), Error> {
+        Ok(self.apply(read_words_from_reader(reader)?))
+    }
+
+    /// Reads a word per line from a file, as [`read_words`](crate::read_words) does, and applies the options.
+    #[cfg(feature = "fs")]
+    pub fn read_file(&self, path: &str) -> Result<(Vec<Word>, LoadCounts), FindWordsError> {
+        Ok(self.apply(crate::read_words(path)?))
+    }
+}
+
+impl fmt::Display for LoadWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadWarning::SkippedLine { line, reason: SkipReason::Empty } =>
+                write!(f, "line {}: skipped empty line", line),
+            LoadWarning::SkippedLine { line, reason: SkipReason::NoLetters } =>
+                write!(f, "line {}: skipped line without letters", line),
+            LoadWarning::Duplicate { line, word, first_line } =>
+                write!(f, "line {}: skipped duplicate of {:?} from line {}", line, word, first_line),
+            LoadWarning::Normalized { line, original, normalized } =>
+                write!(f, "line {}: normalized {:?} to {:?}", line, original, normalized),
+            LoadWarning::InvalidRank { line, rank } if rank.is_empty() =>
+                write!(f, "line {}: missing rank", line),
+            LoadWarning::InvalidRank { line, rank } =>
+                write!(f, "line {}: invalid rank {:?}", line, rank),
+        }
+    }
+}
+
+/// Read words from a file, trimming surrounding whitespace (and a leading byte order mark)
+/// and skipping empty lines, lines without letters and duplicates.
+#[cfg(feature = "fs")]
+pub fn read_words_from_file_with_warnings(file: &str) -> Result<Loaded, Error> {
+    let file = File::open(file)?;
+    load_with_warnings(BufReader::new(file), None, None)
+}
+
+/// Same as [`read_words_from_file_with_warnings`], also leaving out words of more than
+/// `max_word_len` letters.
+#[cfg(feature = "fs")]
+pub fn read_words_up_to_len(file: &str, max_word_len: usize) -> Result<Loaded, Error> {
+    let file = File::open(file)?;
+    load_with_warnings(BufReader::new(file), Some(max_word_len), None)
+}
+
+/// Same as [`read_words_from_file_with_warnings`], also storing the rank of every word. With
+/// [`Ranking::Column`], the rank column is not part of the word.
+#[cfg(feature = "fs")]
+pub fn read_ranked_words(file: &str, ranking: Ranking) -> Result<Loaded, Error> {
+    let file = File::open(file)?;
+    load_with_warnings(BufReader::new(file), None, Some(ranking))
+}
+
+#[cfg(feature = "fs")]
+fn load_with_warnings<R: BufRead>(reader: R, max_word_len: Option<usize>, ranking: Option<Ranking>) -> Result<Loaded, Error> {
+    let mut words = vec![];
+    let mut warnings = vec![];
+    let mut first_lines = HashMap::new();
+    let mut skipped_too_long = 0;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line?;
+        let (original, rank) = match ranking {
+            None => (line.as_str(), None),
+            Some(Ranking::LineNumber) => (line.as_str(), u32::try_from(line_no).ok()),
+            Some(Ranking::Column) => split_rank(&line, line_no, &mut warnings),
+        };
+        let value = original.trim_start_matches('\u{feff}').trim();
+        if value.is_empty() {
+            warnings.push(LoadWarning::SkippedLine { line: line_no, reason: SkipReason::Empty });
+            continue;
+        }
+        let letters = get_letters_count(value);
+        if letters.is_empty() {
+            warnings.push(LoadWarning::SkippedLine { line: line_no, reason: SkipReason::NoLetters });
+            continue;
+        }
+        if max_word_len.is_some_and(|max_word_len| word_len(&letters) > max_word_len) {
+            skipped_too_long += 1;
+            continue;
+        }
+        if value != original {
+            warnings.push(LoadWarning::Normalized { line: line_no, original: original.to_string(), normalized: value.to_string() });
+        }
+        if let Some(&first_line) = first_lines.get(value) {
+            warnings.push(LoadWarning::Duplicate { line: line_no, word: value.to_string(), first_line });
+            continue;
+        }
+        first_lines.insert(value.to_string(), line_no);
+        words.push(Word { value: value.to_string(), letters, rank });
+    }
+
+    Ok(Loaded { words, warnings, skipped_too_long })
+}
+
+/// Splits the rank column off a line, warning if it is missing or invalid. Blank lines have no
+/// rank to warn about.
+#[cfg(feature = "fs")]
+fn split_rank<'a>(line: &'a str, line_no: usize, warnings: &mut Vec<LoadWarning>) -> (&'a str, Option<u32>) {
+    let trimmed = line.trim_end();
+    match trimmed.rsplit_once(char::is_whitespace) {
+        Some((word, rank)) => match rank.parse() {
+            Ok(rank) => (word, Some(rank)),
+            Err(_) => {
+                warnings.push(LoadWarning::InvalidRank { line: line_no, rank: rank.to_string() });
+                (word, None)
+            }
+        },
+        None => {
+            if !trimmed.trim_start_matches('\u{feff}').is_empty() {
+                warnings.push(LoadWarning::InvalidRank { line: line_no, rank: String::new() });
+            }
+            (line, None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod load_options_tests {
+    use std::io::Cursor;
+
+    use crate::{get_letters_count, test_words};
+
+    use super::{LoadCounts, LoadOptions};
+
+    fn values(options: &LoadOptions, input: &str) -> (Vec<String>, LoadCounts) {
+        let (words, counts) = options.read(Cursor::new(input)).unwrap();
+        (words.into_iter().map(|word| word.value).collect(), counts)
+    }
+
+    #[test]
+    fn default_keeps_every_word() {
+        let input = "\u{feff}Cat\r\ncat\n\nwell-known\n";
+        assert_eq!(values(&LoadOptions::default(), input).0, vec!["\u{feff}Cat", "cat", "", "well-known"]);
+    }
+
+    #[test]
+    fn clean() {
+        let (words, counts) = values(&LoadOptions::clean(), "\u{feff}Cat\r\ncat \n\nwell-known\nParis\nÉlève\n");
+        assert_eq!(words, vec!["cat", "paris", "élève"]);
+        assert_eq!(counts, LoadCounts { loaded: 3, normalized: 3, non_alphabetic: 2, too_short: 0, too_long: 0, duplicates: 1 });
+        assert_eq!(counts.skipped(), 3);
+    }
+
+    #[test]
+    fn lengths() {
+        let options = LoadOptions { min_len: Some(3), max_len: Some(4), ..LoadOptions::default() };
+        let (words, counts) = options.apply(test_words(&["at", "cat", "a-b-c", "cattle"]));
+        assert_eq!(words, test_words(&["cat", "a-b-c"]));
+        assert_eq!((counts.too_short, counts.too_long, counts.loaded), (1, 1, 2));
+    }
+
+    #[test]
+    fn letters_follow_the_normalized_word() {
+        let options = LoadOptions { lowercase: true, dedupe: true, ..LoadOptions::default() };
+        let (words, counts) = options.apply(test_words(&["TAT", "tat"]));
+        assert_eq!((words[0].value.as_str(), &words[0].letters), ("tat", &get_letters_count("tat")));
+        assert_eq!(counts.duplicates, 1);
+    }
+}
+
+#[cfg(all(test, feature = "fs"))]
+mod load_with_warnings_tests {
+    use std::io::Cursor;
+
+    use super::{load_with_warnings, read_ranked_words, read_words_from_file_with_warnings, read_words_up_to_len, LoadWarning, Ranking, SkipReason};
+
+    fn values(input: &str) -> Vec<String> {
+        let loaded = load_with_warnings(Cursor::new(input), None, None).unwrap();
+        loaded.words.into_iter().map(|word| word.value).collect()
+    }
+
+    fn warnings(input: &str) -> Vec<LoadWarning> {
+        load_with_warnings(Cursor::new(input), None, None).unwrap().warnings
+    }
+
+    #[test]
+    fn clean_list_has_no_warnings() {
+        assert_eq!(values("cow\ndog\n"), vec!["cow", "dog"]);
+        assert_eq!(warnings("cow\ndog\n"), vec![]);
+    }
+
+    #[test]
+    fn skipped_lines() {
+        assert_eq!(values("cow\n\n123\ndog"), vec!["cow", "dog"]);
+        assert_eq!(warnings("cow\n\n123\ndog"), vec![
+            LoadWarning::SkippedLine { line: 2, reason: SkipReason::Empty },
+            LoadWarning::SkippedLine { line: 3, reason: SkipReason::NoLetters },
+        ]);
+    }
+
+    #[test]
+    fn duplicates() {
+        assert_eq!(values("cow\ndog\ncow"), vec!["cow", "dog"]);
+        assert_eq!(warnings("cow\ndog\ncow"), vec![
+            LoadWarning::Duplicate { line: 3, word: String::from("cow"), first_line: 1 },
+        ]);
+    }
+
+    #[test]
+    fn normalization() {
+        assert_eq!(values("\u{feff}cow\r\n dog \n"), vec!["cow", "dog"]);
+        assert_eq!(warnings("\u{feff}cow\r\n dog \n"), vec![
+            LoadWarning::Normalized { line: 1, original: String::from("\u{feff}cow"), normalized: String::from("cow") },
+            LoadWarning::Normalized { line: 2, original: String::from(" dog "), normalized: String::from("dog") },
+        ]);
+    }
+
+    #[test]
+    fn maximum_word_length() {
+        let loaded = load_with_warnings(Cursor::new("cat\ncattle\ndog's\n cattle\n"), Some(4), None).unwrap();
+        let values: Vec<&str> = loaded.words.iter().map(|word| word.value.as_str()).collect();
+        assert_eq!(values, vec!["cat", "dog's"]);
+        assert_eq!(loaded.skipped_too_long, 2);
+        assert_eq!(loaded.warnings, vec![]);
+    }
+
+    #[test]
+    fn ranks_from_line_numbers() {
+        let loaded = load_with_warnings(Cursor::new("the\n\nof\nthe\n"), None, Some(Ranking::LineNumber)).unwrap();
+        let ranks: Vec<(&str, Option<u32>)> = loaded.words.iter().map(|word| (word.value.as_str(), word.rank)).collect();
+        assert_eq!(ranks, vec![("the", Some(1)), ("of", Some(3))]);
+        assert!(load_with_warnings(Cursor::new("the"), None, None).unwrap().words[0].rank.is_none());
+    }
+
+    #[test]
+    fn ranks_from_column() {
+        let loaded = load_with_warnings(Cursor::new("the 1\nice cream\t20\nof x\nand\n\n"), None, Some(Ranking::Column)).unwrap();
+        let ranks: Vec<(&str, Option<u32>)> = loaded.words.iter().map(|word| (word.value.as_str(), word.rank)).collect();
+        assert_eq!(ranks, vec![("the", Some(1)), ("ice cream", Some(20)), ("of", None), ("and", None)]);
+        assert_eq!(loaded.warnings, vec![
+            LoadWarning::InvalidRank { line: 3, rank: String::from("x") },
+            LoadWarning::InvalidRank { line: 4, rank: String::new() },
+            LoadWarning::SkippedLine { line: 5, reason: SkipReason::Empty },
+        ]);
+        assert_eq!(loaded.warnings[1].to_string(), "line 4: missing rank");
+    }
+
+    #[test]
+    fn display() {
+        let warning = LoadWarning::Duplicate { line: 3, word: String::from("cow"), first_line: 1 };
+        assert_eq!(warning.to_string(), "line 3: skipped duplicate of \"cow\" from line 1");
+    }
+
+    #[test]
+    fn file_does_not_exist() {
+        assert!(read_words_from_file_with_warnings("invalid_file.txt").is_err());
+        assert!(read_words_up_to_len("invalid_file.txt", 15).is_err());
+        assert!(read_ranked_words("invalid_file.txt", Ranking::LineNumber).is_err());
+    }
+}
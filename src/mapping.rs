@@ -0,0 +1,162 @@
+//! Search modes where the input is not a list of letters but a code mapping to letters.
+//!
+//! With a phone keypad, every digit from 2 to 9 stands for any of the letters printed on its key
+//! (2 = abc, 3 = def, ...). Words are then matched against the multiset of keys, reusing
+//! [`LetterCounts`] with the 8-key [`Keypad`] alphabet. Morse code search finds the words whose
+//! encoding matches a string of dots and dashes.
+
+use crate::alphabet::Alphabet;
+use crate::parallel::*;
+use crate::{can_be_constructed, lowercase_letters, LetterCounts, Word};
+
+/// Letters printed on the keys 2 to 9 of a phone keypad.
+const KEYS: [&str; 8] = ["abc", "def", "ghi", "jkl", "mno", "pqrs", "tuv", "wxyz"];
+
+/// Morse code of the letters a to z.
+const MORSE: [&str; 26] = [
+    ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+    "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--", "--..",
+];
+
+/// The keys 2 to 9 of a phone keypad, as an alphabet. Both the digits and the letters printed
+/// on them map to the key's index, so a word and a string of digits can be compared directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Keypad;
+
+/// How the digits of a keypad search are matched against words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeypadMode {
+    /// Any subset of the digits in any order, like a list of letters.
+    AnyOrder,
+    /// All the digits in the given order, like T9 text input.
+    Exact,
+}
+
+impl Alphabet<8> for Keypad {
+    fn index(c: char) -> Option<usize> {
+        match c.to_ascii_lowercase() {
+            c @ '2'..='9' => Some(c as usize - '2' as usize),
+            c if c.is_ascii_lowercase() => KEYS.iter().position(|letters| letters.contains(c)),
+            _ => None,
+        }
+    }
+
+    /// Digit of the key at an index.
+    fn letter(index: usize) -> char {
+        assert!(index < 8, "index {} out of range for Keypad", index);
+        (b'2' + index as u8) as char
+    }
+}
+
+/// Returns the digits typed on a phone keypad to spell a word. Characters that are not
+/// ASCII letters are skipped.
+pub fn to_keypad_digits(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .filter_map(Keypad::index)
+        .map(Keypad::letter)
+        .collect()
+}
+
+/// Retrieve the words that can be spelled from a string of keypad digits.
+/// Characters other than the digits 2 to 9 are ignored.
+pub fn find_keypad_words(words: &[Word], digits: &str, mode: KeypadMode) -> Vec<String> {
+    let digits: String = digits.chars().filter(|c| ('2'..='9').contains(c)).collect();
+    let keys = LetterCounts::from_word::<Keypad>(&digits);
+    words.par_iter()
+        .filter(|word| match mode {
+            KeypadMode::AnyOrder => can_be_constructed(&LetterCounts::from_word::<Keypad>(&lowercase_letters(&word.value).collect::<String>()), &keys),
+            KeypadMode::Exact => !digits.is_empty() && to_keypad_digits(&word.value) == digits,
+        })
+        .map(|word| word.value.clone())
+        .collect()
+}
+
+/// Returns the Morse code of a word, letters separated by spaces. Characters that are not
+/// ASCII letters are skipped.
+pub fn to_morse(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| MORSE[(c.to_ascii_lowercase() as u8 - b'a') as usize])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Retrieve the words whose Morse code matches `code`. Letters in `code` may be separated by
+/// spaces, in which case the separation must match too; without spaces any split matches.
+pub fn find_morse_words(words: &[Word], code: &str) -> Vec<String> {
+    let separated = code.trim().contains(' ');
+    let code = code.split_whitespace().collect::<Vec<_>>().join(" ");
+    words.par_iter()
+        .filter(|word| {
+            let morse = to_morse(&word.value);
+            let morse = if separated { morse } else { morse.replace(' ', "") };
+            !morse.is_empty() && morse == code
+        })
+        .map(|word| word.value.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod keypad_tests {
+    use crate::test_words;
+
+    use super::{find_keypad_words, to_keypad_digits, KeypadMode};
+
+    #[test]
+    fn digits_of_word() {
+        assert_eq!(to_keypad_digits("Cat"), "228");
+        assert_eq!(to_keypad_digits("hello-world"), "4355696753");
+        assert_eq!(to_keypad_digits(""), "");
+    }
+
+    #[test]
+    fn any_order() {
+        let words = test_words(&["act", "cat", "bat", "at", "tact", "dog", "a2"]);
+        let output = find_keypad_words(&words, "822", KeypadMode::AnyOrder);
+        assert_eq!(output, vec!["act", "cat", "bat", "at", "a2"]);
+    }
+
+    #[test]
+    fn exact() {
+        let words = test_words(&["act", "cat", "bat", "at", "cab"]);
+        assert_eq!(find_keypad_words(&words, "228", KeypadMode::Exact), vec!["act", "cat", "bat"]);
+        assert_eq!(find_keypad_words(&words, "2-2-8", KeypadMode::Exact), vec!["act", "cat", "bat"]);
+    }
+
+    #[test]
+    fn no_digits() {
+        let words = test_words(&["act", "cat"]);
+        assert!(find_keypad_words(&words, "", KeypadMode::AnyOrder).is_empty());
+        assert!(find_keypad_words(&words, "0101", KeypadMode::Exact).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod morse_tests {
+    use crate::test_words;
+
+    use super::{find_morse_words, to_morse};
+
+    #[test]
+    fn morse_of_word() {
+        assert_eq!(to_morse("SOS"), "... --- ...");
+        assert_eq!(to_morse("ab1"), ".- -...");
+    }
+
+    #[test]
+    fn without_separators() {
+        let words = test_words(&["eat", "ant", "tea", "et", "a"]);
+        assert_eq!(find_morse_words(&words, ".-"), vec!["et", "a"]);
+        assert_eq!(find_morse_words(&words, "..--"), vec!["eat"]);
+        assert_eq!(find_morse_words(&words, ".--.-"), vec!["ant"]);
+        assert!(find_morse_words(&words, "").is_empty());
+    }
+
+    #[test]
+    fn with_separators() {
+        let words = test_words(&["et", "a"]);
+        assert_eq!(find_morse_words(&words, ". -"), vec!["et"]);
+        assert_eq!(find_morse_words(&words, "  .-  "), vec!["et", "a"]);
+    }
+}
@@ -0,0 +1,470 @@
+//! The words found by a search, and their text representations.
+//!
+//! Front ends should format results through [`Matches`] rather than on their own, so that the
+//! command line and any service built on the crate print the same output. JSON output requires
+//! the `serde` feature, but for [`Matches::to_word_matches_json`] and
+//! [`Matches::to_json_with_letters`], which front ends built without it can print.
+
+use std::collections::{BTreeMap, HashMap};
+
+#[cfg(feature = "random")]
+use rand::distributions::WeightedIndex;
+#[cfg(feature = "random")]
+use rand::prelude::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::rack::word_score;
+use crate::{letter_count, Dictionary, LetterKey, WordId};
+
+/// Words found by a search, in the order they were found.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Matches {
+    words: Vec<String>,
+    /// Score of every word, once scored with [`Matches::annotate_scores`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    scores: Option<Vec<u32>>,
+}
+
+/// A word found and its ID in the dictionary searched, see [`Matches::with_ids`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IdentifiedWord {
+    /// `None` for a word that is not in the dictionary.
+    pub id: Option<WordId>,
+    pub word: String,
+}
+
+/// A word found with its length, score and letters, for tools consuming results, see [`Matches::word_matches`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WordMatch {
+    pub value: String,
+    /// Number of letters.
+    pub length: usize,
+    pub score: u32,
+    /// Letters of the list the word takes, lowercase and sorted alphabetically.
+    pub letters_used: String,
+}
+
+impl WordMatch {
+    /// The match of a word scored with English tile values.
+    pub fn new(value: &str) -> WordMatch {
+        WordMatch::scored(value, word_score(value))
+    }
+
+    fn scored(value: &str, score: u32) -> WordMatch {
+        let letters_used = LetterKey::from_word(value).alphagram();
+        WordMatch { value: value.to_string(), length: letters_used.len(), score, letters_used }
+    }
+}
+
+/// How [`Matches::to_plaintext`] groups the words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Grouping {
+    /// One word per line, in order.
+    None,
+    /// Words grouped by their number of letters, shortest first.
+    Length,
+    /// Words grouped by their first letter, ignoring case, in alphabetical order.
+    FirstLetter,
+}
+
+/// How [`Matches::sample_weighted`] weighs the words.
+#[derive(Clone, Copy)]
+pub enum Weights<'a> {
+    /// Proportionally to the number of letters of the word.
+    Length,
+    /// Proportionally to a score, e.g. [`Puzzle::word_score`](crate::puzzle::Puzzle::word_score).
+    Score(&'a dyn Fn(&str) -> u32),
+    /// Proportionally to how often the word is used. Words missing from the map are never picked.
+    Frequency(&'a HashMap<String, u64>),
+}
+
+impl Matches {
+    pub fn new(words: Vec<String>) -> Matches {
+        Matches { words, scores: None }
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Score of every word, in order, if the words were scored.
+    pub fn scores(&self) -> Option<&[u32]> {
+        self.scores.as_deref()
+    }
+
+    /// Scores every word, replacing any previous scores.
+    pub fn annotate_scores(&mut self, scorer: &dyn Fn(&str) -> u32) {
+        self.scores = Some(self.words.iter().map(|word| scorer(word)).collect());
+    }
+
+    /// Keeps the words for which `keep` returns `true`, in order, along with their scores.
+    pub fn retain<F: FnMut(&str) -> bool>(&mut self, mut keep: F) {
+        let kept: Vec<bool> = self.words.iter().map(|word| keep(word)).collect();
+        let mut kept_words = kept.iter();
+        self.words.retain(|_| *kept_words.next().unwrap());
+        if let Some(scores) = &mut self.scores {
+            let mut kept_scores = kept.iter();
+            scores.retain(|_| *kept_scores.next().unwrap());
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Picks a word at random, with a probability proportional to its weight, so that a bot or
+    /// a hint can go for a plausible word rather than always the best one. Returns `None` if
+    /// there is no word or no word has a positive weight.
+    #[cfg(feature = "random")]
+    pub fn sample_weighted<R: Rng + ?Sized>(&self, rng: &mut R, weights: Weights) -> Option<&str> {
+        let weights = self.words.iter().map(|word| match weights {
+            Weights::Length => letter_count(word) as u64,
+            Weights::Score(score) => score(word) as u64,
+            Weights::Frequency(frequencies) => frequencies.get(word).copied().unwrap_or(0),
+        });
+        let index = WeightedIndex::new(weights).ok()?;
+        Some(&self.words[index.sample(rng)])
+    }
+
+    /// One word per line, grouped under a heading line per group unless `grouping` is
+    /// [`Grouping::None`]. Groups are separated by a blank line.
+    pub fn to_plaintext(&self, grouping: Grouping) -> String {
+        let groups: Vec<(String, Vec<&str>)> = match grouping {
+            Grouping::None => return self.words.iter().map(|word| format!("{}\n", word)).collect(),
+            Grouping::Length => self.group_by_length()
+                .into_iter()
+                .map(|(len, words)| (format!("{} letter{}:", len, if len == 1 { "" } else { "s" }), words))
+                .collect(),
+            Grouping::FirstLetter => self.group_by_first_letter()
+                .into_iter()
+                .map(|(letter, words)| (format!("{}:", letter.unwrap_or(' ')), words))
+                .collect(),
+        };
+        let groups: Vec<String> = groups.into_iter()
+            .map(|(heading, words)| format!("{}\n{}", heading, words.iter().map(|word| format!("{}\n", word)).collect::<String>()))
+            .collect();
+        groups.join("\n")
+    }
+
+    /// Words by number of letters, in order within each group.
+    pub fn group_by_length(&self) -> BTreeMap<usize, Vec<&str>> {
+        self.group_by(letter_count)
+    }
+
+    /// Words by first character, lowercased, `None` for the empty word.
+    pub fn group_by_first_letter(&self) -> BTreeMap<Option<char>, Vec<&str>> {
+        self.group_by(first_letter)
+    }
+
+    /// Words by score band: a word scoring `s` goes under `s - s % band`, so with a band of 5,
+    /// scores 0 to 4 are under 0 and scores 5 to 9 under 5. Panics if `band` is 0.
+    pub fn group_by_score_band(&self, scorer: &dyn Fn(&str) -> u32, band: u32) -> BTreeMap<u32, Vec<&str>> {
+        assert!(band > 0, "score bands must be at least 1 point wide");
+        self.group_by(|word| score_band(scorer(word), band))
+    }
+
+    /// Number of words of each number of letters.
+    pub fn length_histogram(&self) -> BTreeMap<usize, usize> {
+        self.count_by(letter_count)
+    }
+
+    /// Number of words of each first character, like [`group_by_first_letter`](Matches::group_by_first_letter).
+    pub fn first_letter_histogram(&self) -> BTreeMap<Option<char>, usize> {
+        self.count_by(first_letter)
+    }
+
+    /// Number of words of each score band, like [`group_by_score_band`](Matches::group_by_score_band).
+    pub fn score_histogram(&self, scorer: &dyn Fn(&str) -> u32, band: u32) -> BTreeMap<u32, usize> {
+        assert!(band > 0, "score bands must be at least 1 point wide");
+        self.count_by(|word| score_band(scorer(word), band))
+    }
+
+    /// A `value,length,score,letters_used` header, then one record per [`WordMatch`]. Fields are
+    /// quoted when needed.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("value,length,score,letters_used\n");
+        for found in self.word_matches() {
+            csv.push_str(&format!("{},{},{},{}\n", csv_field(&found.value), found.length, found.score, found.letters_used));
+        }
+        csv
+    }
+
+    /// The words as a JSON object: `{"words":[...]}`, with `"scores":[...]` once scored.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a list of strings can always be serialized")
+    }
+
+    /// The words in order as [`WordMatch`]es, with their scores once scored, English tile scores
+    /// otherwise.
+    pub fn word_matches(&self) -> Vec<WordMatch> {
+        match &self.scores {
+            Some(scores) => self.words.iter().zip(scores).map(|(word, &score)| WordMatch::scored(word, score)).collect(),
+            None => self.words.iter().map(|word| WordMatch::new(word)).collect(),
+        }
+    }
+
+    /// The [`WordMatch`]es as a JSON array of objects, as serde serializes them, written without
+    /// serde.
+    pub fn to_word_matches_json(&self) -> String {
+        let objects: Vec<String> = self.word_matches().iter()
+            .map(|found| format!(
+                r#"{{"value":{},"length":{},"score":{},"letters_used":{}}}"#,
+                json_string(&found.value), found.length, found.score, json_string(&found.letters_used),
+            ))
+            .collect();
+        format!("[{}]", objects.join(","))
+    }
+
+    /// The list of letters searched and the words found as a JSON object, written without serde:
+    /// `{"letters":"...","words":[...],"matches":[...]}`, the matches as in
+    /// [`to_word_matches_json`](Matches::to_word_matches_json).
+    pub fn to_json_with_letters(&self, letters: &str) -> String {
+        let words: Vec<String> = self.words.iter().map(|word| json_string(word)).collect();
+        format!(r#"{{"letters":{},"words":[{}],"matches":{}}}"#, json_string(letters), words.join(","), self.to_word_matches_json())
+    }
+
+    /// The words in order, with their [`WordId`]s in `dictionary`.
+    pub fn with_ids(&self, dictionary: &Dictionary) -> Vec<IdentifiedWord> {
+        self.words.iter().map(|word| IdentifiedWord { id: dictionary.word_id(word), word: word.clone() }).collect()
+    }
+
+    /// The words and their IDs as a JSON object: `{"words":[{"id":...,"word":"..."},...]}`.
+    #[cfg(feature = "serde")]
+    pub fn to_json_with_ids(&self, dictionary: &Dictionary) -> String {
+        serde_json::json!({ "words": self.with_ids(dictionary) }).to_string()
+    }
+
+    fn group_by<K: Ord, F: Fn(&str) -> K>(&self, key: F) -> BTreeMap<K, Vec<&str>> {
+        let mut groups: BTreeMap<K, Vec<&str>> = BTreeMap::new();
+        for word in &self.words {
+            groups.entry(key(word)).or_default().push(word);
+        }
+        groups
+    }
+
+    fn count_by<K: Ord, F: Fn(&str) -> K>(&self, key: F) -> BTreeMap<K, usize> {
+        let mut counts = BTreeMap::new();
+        for word in &self.words {
+            *counts.entry(key(word)).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+impl From<Vec<String>> for Matches {
+    fn from(words: Vec<String>) -> Self {
+        Matches::new(words)
+    }
+}
+
+impl From<Vec<&str>> for Matches {
+    fn from(words: Vec<&str>) -> Self {
+        Matches::new(words.into_iter().map(str::to_string).collect())
+    }
+}
+
+impl IntoIterator for Matches {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.words.into_iter()
+    }
+}
+
+fn first_letter(word: &str) -> Option<char> {
+    word.chars().next().map(|c| c.to_ascii_lowercase())
+}
+
+fn score_band(score: u32, band: u32) -> u32 {
+    score - score % band
+}
+
+/// A JSON string literal of `text`, quoted and escaped as in the JSON output of [`Matches`].
+pub fn json_string(text: &str) -> String {
+    let mut literal = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            c if (c as u32) < 0x20 => literal.push_str(&format!("\\u{:04x}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Quotes a CSV field if it contains a comma, a quote or a line break.
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod matches_tests {
+    use std::collections::BTreeMap;
+    #[cfg(feature = "random")]
+    use std::collections::HashMap;
+
+    #[cfg(feature = "random")]
+    use rand::rngs::StdRng;
+    #[cfg(feature = "random")]
+    use rand::SeedableRng;
+
+    #[cfg(feature = "random")]
+    use super::Weights;
+    use crate::{test_words, Dictionary};
+
+    use super::{Grouping, IdentifiedWord, Matches, WordMatch};
+
+    fn matches() -> Matches {
+        vec!["cat", "at", "act", "a", "Arm", "attention"].into()
+    }
+
+    #[test]
+    fn plaintext() {
+        assert_eq!(matches().to_plaintext(Grouping::None), "cat\nat\nact\na\nArm\nattention\n");
+        assert_eq!(Matches::default().to_plaintext(Grouping::Length), "");
+    }
+
+    #[test]
+    fn plaintext_by_length() {
+        let expected = "1 letter:\na\n\n2 letters:\nat\n\n3 letters:\ncat\nact\nArm\n\n9 letters:\nattention\n";
+        assert_eq!(matches().to_plaintext(Grouping::Length), expected);
+    }
+
+    #[test]
+    fn plaintext_by_first_letter() {
+        let expected = "a:\nat\nact\na\nArm\nattention\n\nc:\ncat\n";
+        assert_eq!(matches().to_plaintext(Grouping::FirstLetter), expected);
+    }
+
+    #[test]
+    fn groups() {
+        let matches = matches();
+        assert_eq!(matches.group_by_length()[&3], vec!["cat", "act", "Arm"]);
+        assert_eq!(matches.group_by_first_letter().keys().copied().collect::<Vec<_>>(), vec![Some('a'), Some('c')]);
+        let bands = matches.group_by_score_band(&|word| word.len() as u32, 3);
+        assert_eq!(bands.iter().map(|(&band, words)| (band, words.len())).collect::<Vec<_>>(), vec![(0, 2), (3, 3), (9, 1)]);
+    }
+
+    #[test]
+    fn histograms() {
+        let matches = matches();
+        assert_eq!(matches.length_histogram(), BTreeMap::from([(1, 1), (2, 1), (3, 3), (9, 1)]));
+        assert_eq!(matches.first_letter_histogram(), BTreeMap::from([(Some('a'), 5), (Some('c'), 1)]));
+        assert_eq!(matches.score_histogram(&|word| word.len() as u32, 5), BTreeMap::from([(0, 5), (5, 1)]));
+        assert!(Matches::default().length_histogram().is_empty());
+        let accented: Matches = vec!["élève"].into();
+        assert_eq!((accented.length_histogram(), accented.word_matches()[0].length), (BTreeMap::from([(3, 1)]), 3));
+    }
+
+    #[test]
+    fn csv() {
+        let matches: Matches = vec!["cat", "o'clock", "a,b", "say \"hi\""].into();
+        let expected = "value,length,score,letters_used\ncat,3,5,act\no'clock,6,14,cckloo\n\"a,b\",2,4,ab\n\"say \"\"hi\"\"\",5,11,ahisy\n";
+        assert_eq!(matches.to_csv(), expected);
+    }
+
+    #[test]
+    fn word_matches() {
+        let matches: Matches = vec!["Cat", "o'clock"].into();
+        assert_eq!(matches.word_matches(), vec![
+            WordMatch { value: "Cat".to_string(), length: 3, score: 5, letters_used: "act".to_string() },
+            WordMatch { value: "o'clock".to_string(), length: 6, score: 14, letters_used: "cckloo".to_string() },
+        ]);
+        let mut scored = matches.clone();
+        scored.annotate_scores(&|word| word.len() as u32);
+        assert_eq!(scored.word_matches()[1].score, 7);
+        assert_eq!(Matches::default().to_csv(), "value,length,score,letters_used\n");
+        let json = Matches::new(vec!["at".to_string(), "say \"hi\"".to_string()]).to_word_matches_json();
+        assert_eq!(json, r#"[{"value":"at","length":2,"score":2,"letters_used":"at"},{"value":"say \"hi\"","length":5,"score":11,"letters_used":"ahisy"}]"#);
+        assert_eq!(Matches::default().to_word_matches_json(), "[]");
+        let json = Matches::new(vec!["at".to_string()]).to_json_with_letters("t\"a");
+        assert_eq!(json, r#"{"letters":"t\"a","words":["at"],"matches":[{"value":"at","length":2,"score":2,"letters_used":"at"}]}"#);
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn sample_by_length() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let matches = matches();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for _ in 0..2000 {
+            *counts.entry(matches.sample_weighted(&mut rng, Weights::Length).unwrap()).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 6);
+        assert!(counts["attention"] > 3 * counts["a"]);
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn sample_by_score_and_frequency() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let score = |word: &str| if word == "act" { 1 } else { 0 };
+        assert_eq!(matches().sample_weighted(&mut rng, Weights::Score(&score)), Some("act"));
+        let frequencies = HashMap::from([("cat".to_string(), 10), ("dog".to_string(), 100)]);
+        assert_eq!(matches().sample_weighted(&mut rng, Weights::Frequency(&frequencies)), Some("cat"));
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn sample_without_weights() {
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(Matches::default().sample_weighted(&mut rng, Weights::Length), None);
+        let frequencies = HashMap::new();
+        assert_eq!(matches().sample_weighted(&mut rng, Weights::Frequency(&frequencies)), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        assert_eq!(matches().to_json(), r#"{"words":["cat","at","act","a","Arm","attention"]}"#);
+        assert_eq!(Matches::default().to_json(), r#"{"words":[]}"#);
+        let mut scored = Matches::new(vec!["at".to_string()]);
+        scored.annotate_scores(&|_| 2);
+        assert_eq!(scored.to_json(), r#"{"words":["at"],"scores":[2]}"#);
+        let dictionary: Dictionary = test_words(&["at", "cat"]).into();
+        let id = dictionary.word_id("cat").unwrap().0;
+        let json = Matches::new(vec!["cat".to_string(), "tac".to_string()]).to_json_with_ids(&dictionary);
+        assert_eq!(json, format!(r#"{{"words":[{{"id":{},"word":"cat"}},{{"id":null,"word":"tac"}}]}}"#, id));
+        let matches = Matches::new(vec!["at".to_string(), "say \"hi\"\n".to_string()]);
+        assert_eq!(matches.to_word_matches_json(), serde_json::to_string(&matches.word_matches()).unwrap());
+    }
+
+    #[test]
+    fn scores_follow_the_words() {
+        let mut matches = matches();
+        assert_eq!(matches.scores(), None);
+        matches.annotate_scores(&|word| word.len() as u32);
+        matches.retain(|word| word.starts_with('a'));
+        assert_eq!(matches.words(), ["at", "act", "a", "attention"]);
+        assert_eq!(matches.scores(), Some(&[2, 3, 1, 9][..]));
+    }
+
+    #[test]
+    fn ids() {
+        let dictionary: Dictionary = test_words(&["at", "cat"]).into();
+        let ids = Matches::new(vec!["cat".to_string(), "tac".to_string()]).with_ids(&dictionary);
+        assert_eq!(ids, vec![
+            IdentifiedWord { id: dictionary.word_id("cat"), word: "cat".to_string() },
+            IdentifiedWord { id: None, word: "tac".to_string() },
+        ]);
+        assert_eq!(ids[0].id.map(|id| id.position()), Some(1));
+    }
+}
@@ -0,0 +1,69 @@
+//! Merging of sorted runs.
+//!
+//! Searches that collect results from several buckets or shards, each already sorted, can merge
+//! them in `O(n log k)` for `k` runs instead of sorting everything again, and can stop as soon as
+//! they have the first few results.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Iterator over the items of several sorted runs, in order, see [`kmerge`].
+#[derive(Debug)]
+pub struct KMerge<I: Iterator> where I::Item: Ord {
+    runs: Vec<I>,
+    /// Next item of every run that is not exhausted, with the index of the run.
+    heads: BinaryHeap<Reverse<(I::Item, usize)>>,
+}
+
+/// Merges runs that are each sorted in ascending order.
+pub fn kmerge<R: IntoIterator>(runs: impl IntoIterator<Item=R>) -> KMerge<R::IntoIter> where R::Item: Ord {
+    let mut runs: Vec<R::IntoIter> = runs.into_iter().map(IntoIterator::into_iter).collect();
+    let heads = runs.iter_mut()
+        .enumerate()
+        .filter_map(|(index, run)| run.next().map(|item| Reverse((item, index))))
+        .collect();
+    KMerge { runs, heads }
+}
+
+impl<I: Iterator> Iterator for KMerge<I> where I::Item: Ord {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((item, index)) = self.heads.pop()?;
+        if let Some(next) = self.runs[index].next() {
+            self.heads.push(Reverse((next, index)));
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.runs.iter().fold((self.heads.len(), Some(self.heads.len())), |(low, high), run| {
+            let (run_low, run_high) = run.size_hint();
+            (low + run_low, high.zip(run_high).map(|(high, run_high)| high + run_high))
+        })
+    }
+}
+
+#[cfg(test)]
+mod kmerge_tests {
+    use super::kmerge;
+
+    #[test]
+    fn merges_in_order() {
+        let runs = vec![vec![1, 4, 9], vec![], vec![2, 3, 10], vec![5]];
+        assert_eq!(kmerge(runs).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 9, 10]);
+    }
+
+    #[test]
+    fn duplicates_are_kept() {
+        assert_eq!(kmerge([vec!["cat"], vec!["cat", "dog"]]).collect::<Vec<_>>(), vec!["cat", "cat", "dog"]);
+    }
+
+    #[test]
+    fn first_items_only() {
+        let runs = vec![vec!["act", "cat"], vec!["at", "tact"]];
+        let merged = kmerge(runs);
+        assert_eq!(merged.size_hint(), (4, Some(4)));
+        assert_eq!(merged.take(3).collect::<Vec<_>>(), vec!["act", "at", "cat"]);
+    }
+}
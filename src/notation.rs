@@ -0,0 +1,193 @@
+//! A one-line text notation for the moves of a game, for game logs, replays and test fixtures.
+//!
+//! Each move is written as the rack before the move, the move, the points scored and the rack
+//! after the move, separated by spaces:
+//!
+//! ```text
+//! catdogx cat@8H +3 dogxxxx
+//! dogxxxx -dx +0 ogxxxqe
+//! ogxxxqe - +0 ogxxxqe
+//! ```
+//!
+//! A play is the word, optionally followed by `@` and its position on the board. An exchange is
+//! `-` followed by the tiles put back, and a pass is a lone `-`. Racks are written as [`Rack`]s
+//! are displayed, blanks as `?`, and an empty rack is written `_`.
+//! In a log, blank lines and lines starting with `#` are ignored.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::game::Move;
+use crate::Rack;
+
+/// A move, with the racks around it and the points it scored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayRecord {
+    pub rack_before: Rack,
+    pub action: Move,
+    /// Position of a played word on the board, in whatever notation the board uses.
+    pub position: Option<String>,
+    pub score: u32,
+    pub rack_after: Rack,
+}
+
+/// Error returned when a move cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NotationError {
+    /// The move does not have exactly four fields.
+    WrongFieldCount(usize),
+    InvalidRack(String),
+    InvalidMove(String),
+    InvalidScore(String),
+    /// Error on a line of a log, counting from 1.
+    Line { line: usize, error: Box<NotationError> },
+}
+
+impl fmt::Display for PlayRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", rack_field(&self.rack_before))?;
+        match &self.action {
+            Move::Play(word) => write!(f, "{}", word)?,
+            Move::Exchange(tiles) => write!(f, "-{}", tiles)?,
+            Move::Pass => write!(f, "-")?,
+        }
+        if let (Move::Play(_), Some(position)) = (&self.action, &self.position) {
+            write!(f, "@{}", position)?;
+        }
+        write!(f, " +{} {}", self.score, rack_field(&self.rack_after))
+    }
+}
+
+impl FromStr for PlayRecord {
+    type Err = NotationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let [rack_before, action, score, rack_after] = fields[..] else {
+            return Err(NotationError::WrongFieldCount(fields.len()));
+        };
+        let (action, position) = match action.strip_prefix('-') {
+            Some("") => (Move::Pass, None),
+            Some(tiles) if tiles.parse::<Rack>().is_ok() => (Move::Exchange(tiles.to_string()), None),
+            Some(_) => return Err(NotationError::InvalidMove(action.to_string())),
+            None => {
+                let (word, position) = match action.split_once('@') {
+                    Some((word, position)) if !position.is_empty() => (word, Some(position.to_string())),
+                    Some(_) => return Err(NotationError::InvalidMove(action.to_string())),
+                    None => (action, None),
+                };
+                if !is_letters(word) {
+                    return Err(NotationError::InvalidMove(action.to_string()));
+                }
+                (Move::Play(word.to_string()), position)
+            }
+        };
+        let score = score.strip_prefix('+')
+            .and_then(|score| score.parse().ok())
+            .ok_or_else(|| NotationError::InvalidScore(score.to_string()))?;
+        Ok(PlayRecord {
+            rack_before: parse_rack(rack_before)?,
+            action,
+            position,
+            score,
+            rack_after: parse_rack(rack_after)?,
+        })
+    }
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::WrongFieldCount(count) => write!(f, "expected 4 fields, found {}", count),
+            NotationError::InvalidRack(rack) => write!(f, "invalid rack {:?}", rack),
+            NotationError::InvalidMove(action) => write!(f, "invalid move {:?}", action),
+            NotationError::InvalidScore(score) => write!(f, "invalid score {:?}", score),
+            NotationError::Line { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl Error for NotationError {}
+
+/// Parses a game log, one move per line.
+pub fn parse_log(log: &str) -> Result<Vec<PlayRecord>, NotationError> {
+    log.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(number, line)| line.parse().map_err(|error| NotationError::Line { line: number + 1, error: Box::new(error) }))
+        .collect()
+}
+
+/// Writes a game log, one move per line.
+pub fn write_log(records: &[PlayRecord]) -> String {
+    records.iter().map(|record| format!("{}\n", record)).collect()
+}
+
+fn is_letters(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(char::is_alphabetic)
+}
+
+fn parse_rack(rack: &str) -> Result<Rack, NotationError> {
+    match rack {
+        "_" => Ok(Rack::new()),
+        rack => rack.parse().map_err(|_| NotationError::InvalidRack(rack.to_string())),
+    }
+}
+
+fn rack_field(rack: &Rack) -> String {
+    if rack.is_empty() { "_".to_string() } else { rack.to_string() }
+}
+
+#[cfg(test)]
+mod notation_tests {
+    use crate::game::Move;
+    use crate::Rack;
+
+    use super::{parse_log, write_log, NotationError, PlayRecord};
+
+    fn play() -> PlayRecord {
+        PlayRecord {
+            rack_before: "catdogx".parse().unwrap(),
+            action: Move::Play("cat".to_string()),
+            position: Some("8H".to_string()),
+            score: 3,
+            rack_after: "dogxxxx".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let exchange = PlayRecord { action: Move::Exchange("dx".to_string()), position: None, score: 0, ..play() };
+        let pass = PlayRecord { action: Move::Pass, position: None, score: 0, rack_after: Rack::new(), ..play() };
+        for (record, text) in [(play(), "catdogx cat@8H +3 dogxxxx"), (exchange, "catdogx -dx +0 dogxxxx"), (pass, "catdogx - +0 _")] {
+            assert_eq!(record.to_string(), text);
+            assert_eq!(text.parse::<PlayRecord>(), Ok(record));
+        }
+    }
+
+    #[test]
+    fn play_without_position() {
+        let record: PlayRecord = "catdogx cat +3 dogxxxx".parse().unwrap();
+        assert_eq!(record.position, None);
+    }
+
+    #[test]
+    fn invalid_moves() {
+        assert_eq!("catdogx cat +3".parse::<PlayRecord>(), Err(NotationError::WrongFieldCount(3)));
+        assert_eq!("catdogx ca7 +3 dog".parse::<PlayRecord>(), Err(NotationError::InvalidMove("ca7".to_string())));
+        assert_eq!("catdogx cat@ +3 dog".parse::<PlayRecord>(), Err(NotationError::InvalidMove("cat@".to_string())));
+        assert_eq!("catdogx cat 3 dog".parse::<PlayRecord>(), Err(NotationError::InvalidScore("3".to_string())));
+        assert_eq!("cat1ogx cat +3 dog".parse::<PlayRecord>(), Err(NotationError::InvalidRack("cat1ogx".to_string())));
+    }
+
+    #[test]
+    fn logs() {
+        let log = "# game 1\ncatdogx cat@8H +3 dogxxxx\n\ndogxxxx - +0 dogxxxx\n";
+        let records = parse_log(log).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(write_log(&records), "catdogx cat@8H +3 dogxxxx\ndogxxxx - +0 dogxxxx\n");
+        let error = parse_log("catdogx cat +3 dogxxxx\nbad\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected 4 fields, found 1");
+    }
+}
@@ -0,0 +1,135 @@
+//! Parallel iterators with the `parallel` feature, and sequential stand-ins with the same method
+//! names without it, so that searches compile and give the same results without rayon.
+//!
+//! With the feature, searches run on rayon's global pool unless a [`Parallelism`] says
+//! otherwise, so that an application sizing its own pool keeps the crate's work there. Only
+//! [`get_constructable_words_with`](crate::get_constructable_words_with) takes a `Parallelism`;
+//! every other parallel search, e.g.
+//! [`get_constructable_words_batch`](crate::get_constructable_words_batch),
+//! [`find_top_k`](crate::top::find_top_k) or the searches of a
+//! [`LexiconSet`](crate::lexicon::LexiconSet), is run on a pool of the caller with
+//! [`Parallelism::install`]. The searches of a [`Dictionary`](crate::Dictionary) and its views
+//! walk their indexes on the calling thread.
+
+#[cfg(feature = "parallel")]
+use std::sync::Arc;
+
+#[cfg(feature = "parallel")]
+pub(crate) use rayon::prelude::*;
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) use sequential::*;
+
+/// Where the parallel searches of the crate run.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub enum Parallelism {
+    /// One word after the other on the calling thread.
+    Sequential,
+    /// On rayon's global pool, or on the calling thread without the `parallel` feature.
+    #[default]
+    Global,
+    /// On a pool of the caller, e.g. one sized for the application.
+    #[cfg(feature = "parallel")]
+    Pool(Arc<rayon::ThreadPool>),
+}
+
+impl Parallelism {
+    /// Runs `op`, and any search of the crate it calls, on the pool of a
+    /// [`Parallelism::Pool`]: the supported way to choose where the searches that do not take a
+    /// `Parallelism` run. `op` runs on the calling thread for [`Parallelism::Sequential`] and
+    /// [`Parallelism::Global`], and the searches it calls on rayon's global pool.
+    pub fn install<R: Send, F: FnOnce() -> R + Send>(&self, op: F) -> R {
+        match self {
+            Parallelism::Sequential | Parallelism::Global => op(),
+            #[cfg(feature = "parallel")]
+            Parallelism::Pool(pool) => pool.install(op),
+        }
+    }
+
+    /// Returns `true` if searches run one word after the other.
+    pub fn is_sequential(&self) -> bool {
+        matches!(self, Parallelism::Sequential) || cfg!(not(feature = "parallel"))
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+mod sequential {
+    use std::cmp::Ordering;
+
+    pub(crate) trait IntoParallelIterator: IntoIterator + Sized {
+        fn into_par_iter(self) -> Self::IntoIter {
+            self.into_iter()
+        }
+    }
+
+    impl<T: IntoIterator> IntoParallelIterator for T {}
+
+    pub(crate) trait IntoParallelRefIterator<'a> {
+        type Iter: Iterator;
+
+        fn par_iter(&'a self) -> Self::Iter;
+    }
+
+    impl<'a, T: 'a + ?Sized> IntoParallelRefIterator<'a> for T where &'a T: IntoIterator {
+        type Iter = <&'a T as IntoIterator>::IntoIter;
+
+        fn par_iter(&'a self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+
+    pub(crate) trait ParallelSlice<T> {
+        fn par_chunks(&self, size: usize) -> std::slice::Chunks<'_, T>;
+    }
+
+    impl<T> ParallelSlice<T> for [T] {
+        fn par_chunks(&self, size: usize) -> std::slice::Chunks<'_, T> {
+            self.chunks(size)
+        }
+    }
+
+    pub(crate) trait ParallelSliceMut<T> {
+        fn par_sort_unstable_by<F: Fn(&T, &T) -> Ordering>(&mut self, compare: F);
+    }
+
+    impl<T> ParallelSliceMut<T> for [T] {
+        fn par_sort_unstable_by<F: Fn(&T, &T) -> Ordering>(&mut self, compare: F) {
+            self.sort_unstable_by(compare)
+        }
+    }
+}
+
+#[cfg(test)]
+mod parallelism_tests {
+    use crate::{find_anagrams, get_constructable_words, get_constructable_words_with, get_letters_count, test_words, Dictionary, Word};
+
+    use super::Parallelism;
+
+    fn words() -> Vec<Word> {
+        test_words(&["a", "at", "cat", "dog", "act", "tact", "ta"])
+    }
+
+    #[test]
+    fn every_parallelism_finds_the_same_words() {
+        let list = get_letters_count("tca");
+        let expected = get_constructable_words(words(), &list);
+        let dictionary = Dictionary::new(words());
+        #[cfg(feature = "parallel")]
+        let pool = Parallelism::Pool(std::sync::Arc::new(rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap()));
+        #[cfg(not(feature = "parallel"))]
+        let pool = Parallelism::Global;
+        for parallelism in [Parallelism::Sequential, Parallelism::Global, pool] {
+            assert_eq!(get_constructable_words_with(words(), &list, &parallelism), expected);
+            assert_eq!(parallelism.install(|| find_anagrams(&words(), "tac")), vec!["cat", "act"]);
+            assert_eq!(parallelism.install(|| dictionary.find("tca")), expected);
+        }
+    }
+
+    #[test]
+    fn sequential() {
+        assert!(Parallelism::Sequential.is_sequential());
+        let caller = std::thread::current().id();
+        assert_eq!(Parallelism::Sequential.install(|| std::thread::current().id()), caller);
+    }
+}
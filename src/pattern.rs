@@ -0,0 +1,158 @@
+//! Board patterns, for crossword-style searches: words of a fixed length with some of their
+//! letters already in place.
+//!
+//! A pattern such as `c__t` has one character per letter of the word: a letter is already on
+//! the board and `_` or `.` is an empty square. A word fits a pattern if it has as many letters
+//! as the pattern has squares and agrees with every letter in place, ignoring case. Searches
+//! also take a list of letters, from which the letters a word puts on the empty squares must
+//! be taken: the letters in place are free.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::alphabet::{Alphabet, English};
+use crate::parallel::*;
+use crate::{can_be_constructed, LetterCounts, Word};
+
+/// Characters standing for an empty square in a pattern.
+pub const PLACEHOLDERS: [char; 2] = ['_', '.'];
+
+/// Squares of a pattern, `None` for an empty square, letters in lowercase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    squares: Vec<Option<char>>,
+}
+
+/// Error returned when a pattern cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternError {
+    /// The pattern has no squares.
+    Empty,
+    /// A character that is neither an ASCII letter nor a placeholder, and its position (in
+    /// characters) in the pattern.
+    InvalidChar { c: char, position: usize },
+}
+
+impl Pattern {
+    /// Number of squares, the length of the words fitting the pattern.
+    pub fn len(&self) -> usize {
+        self.squares.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.squares.is_empty()
+    }
+
+    /// Letters in place and their positions.
+    pub fn fixed(&self) -> impl Iterator<Item=(usize, char)> + '_ {
+        self.squares.iter().enumerate().filter_map(|(position, square)| square.map(|letter| (position, letter)))
+    }
+
+    /// The letters `word` puts on the empty squares, or `None` if it does not fit the pattern.
+    /// Characters of `word` that are not ASCII letters are skipped.
+    pub fn fill(&self, word: &str) -> Option<LetterCounts> {
+        let mut letters = word.chars().filter(|c| c.is_ascii_alphabetic()).map(|c| c.to_ascii_lowercase());
+        let mut fill = LetterCounts::new();
+        for square in &self.squares {
+            let letter = letters.next()?;
+            match square {
+                Some(fixed) if *fixed != letter => return None,
+                Some(_) => {}
+                None => fill.increment(English::index(letter)?),
+            }
+        }
+        if letters.next().is_some() { return None; }
+        Some(fill)
+    }
+
+    /// Returns `true` if `word` fits the pattern with the letters of `list` on its empty squares.
+    pub fn matches(&self, word: &str, list: &LetterCounts) -> bool {
+        self.fill(word).is_some_and(|fill| fill.is_empty() || can_be_constructed(&fill, list))
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = PatternError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        let squares = pattern.chars()
+            .enumerate()
+            .map(|(position, c)| match c {
+                c if PLACEHOLDERS.contains(&c) => Ok(None),
+                c if c.is_ascii_alphabetic() => Ok(Some(c.to_ascii_lowercase())),
+                c => Err(PatternError::InvalidChar { c, position }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if squares.is_empty() { return Err(PatternError::Empty); }
+        Ok(Pattern { squares })
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.squares.iter().try_for_each(|square| write!(f, "{}", square.unwrap_or('_')))
+    }
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Empty => write!(f, "pattern is empty"),
+            PatternError::InvalidChar { c, position } =>
+                write!(f, "invalid character {:?} at position {} of the pattern", c, position),
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+/// Retrieve the words fitting `pattern`, the letters on its empty squares taken from `letters`,
+/// in the order of `words`.
+pub fn find_matching_pattern(words: &[Word], letters: &str, pattern: &str) -> Result<Vec<String>, PatternError> {
+    let pattern: Pattern = pattern.parse()?;
+    let list = LetterCounts::from_word::<English>(letters);
+    Ok(words.par_iter()
+        .filter(|word| pattern.matches(&word.value, &list))
+        .map(|word| word.value.clone())
+        .collect())
+}
+
+#[cfg(test)]
+mod pattern_tests {
+    use crate::alphabet::English;
+    use crate::{test_words, Dictionary, LetterCounts};
+
+    use super::{find_matching_pattern, Pattern, PatternError};
+
+    #[test]
+    fn parse() {
+        let pattern: Pattern = "C_.t".parse().unwrap();
+        assert_eq!((pattern.len(), pattern.to_string()), (4, "c__t".to_string()));
+        assert_eq!(pattern.fixed().collect::<Vec<_>>(), vec![(0, 'c'), (3, 't')]);
+        assert_eq!("".parse::<Pattern>(), Err(PatternError::Empty));
+        assert_eq!("c?t".parse::<Pattern>(), Err(PatternError::InvalidChar { c: '?', position: 1 }));
+    }
+
+    #[test]
+    fn fill() {
+        let pattern: Pattern = "c__t".parse().unwrap();
+        assert_eq!(pattern.fill("Cart"), Some(LetterCounts::from_word::<English>("ar")));
+        assert_eq!(pattern.fill("cat"), None);
+        assert_eq!(pattern.fill("carts"), None);
+        assert_eq!(pattern.fill("dart"), None);
+        assert!(pattern.matches("cart", &LetterCounts::from_word::<English>("rax")));
+        assert!(!pattern.matches("cast", &LetterCounts::from_word::<English>("rax")));
+    }
+
+    #[test]
+    fn search() {
+        let words = test_words(&["cart", "cast", "coat", "cat", "Colt", "tact", "c-a-r-t"]);
+        assert_eq!(find_matching_pattern(&words, "aorx", "c__t"), Ok(vec!["cart".to_string(), "coat".to_string(), "c-a-r-t".to_string()]));
+        assert_eq!(find_matching_pattern(&words, "", "cat"), Ok(vec!["cat".to_string()]));
+        assert_eq!(find_matching_pattern(&words, "al", ".__."), Ok(vec![]));
+        assert!(find_matching_pattern(&words, "a", "c 3").is_err());
+        let dictionary: Dictionary = words.into();
+        assert_eq!(dictionary.find_matching_pattern("aorx", &"c__t".parse().unwrap()), vec!["cart", "coat", "c-a-r-t"]);
+    }
+}
@@ -0,0 +1,183 @@
+//! Phrase anagrams: sequences of words of a dictionary using every letter of a phrase exactly.
+//!
+//! The search space grows quickly with the number of words of a phrase, so
+//! [`find_phrases`] is best-effort: it hands every phrase to a visitor as soon as it is found,
+//! stops at a soft deadline, and returns a [`PhraseCoverage`] telling how much of the search
+//! space it covered. Phrases are found as multisets of words, so that "dirty room" and
+//! "room dirty" are found once, with the longest words first.
+
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+
+use crate::alphabet::English;
+use crate::dictionary::DictionaryView;
+use crate::{can_be_constructed, LetterCounts};
+
+/// Bounds of a phrase anagram search.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhraseOptions {
+    /// Most words in a phrase.
+    pub max_words: usize,
+    /// Time after which the search stops, however much of the search space is left.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for PhraseOptions {
+    fn default() -> Self {
+        PhraseOptions { max_words: 3, timeout: None }
+    }
+}
+
+/// How much of the search space a phrase anagram search covered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PhraseCoverage {
+    /// Phrases found.
+    pub phrases: usize,
+    /// Words that can start a phrase: the words of the view the phrase can make.
+    pub first_words: usize,
+    /// First words whose phrases were all searched.
+    pub first_words_searched: usize,
+    /// Partial phrases tried.
+    pub nodes: u64,
+    /// The search stopped at the deadline.
+    pub timed_out: bool,
+    pub elapsed: Duration,
+}
+
+impl PhraseCoverage {
+    /// Returns `true` if every phrase was searched.
+    pub fn is_complete(&self) -> bool {
+        self.first_words_searched == self.first_words
+    }
+
+    /// Share of the first words whose phrases were all searched, from 0 to 1.
+    pub fn fraction(&self) -> f64 {
+        if self.first_words == 0 { return 1.0; }
+        self.first_words_searched as f64 / self.first_words as f64
+    }
+}
+
+/// Searches the phrases of at most `options.max_words` words of `view` using every letter of
+/// `phrase` exactly, ignoring case and any character that is not an English letter. Each
+/// phrase is handed to `visit`, longest words first, as soon as it is found; the search stops
+/// when `visit` breaks or at the deadline.
+pub fn find_phrases<'a, F>(view: &DictionaryView<'a>, phrase: &str, options: &PhraseOptions, visit: F) -> PhraseCoverage
+where
+    F: FnMut(&[&'a str]) -> ControlFlow<()>,
+{
+    let start = Instant::now();
+    let letters = LetterCounts::from_word::<English>(phrase);
+    let mut words: Vec<(&'a str, LetterCounts)> = view.find_counts(&letters).into_iter()
+        .map(|word| (word, LetterCounts::from_word::<English>(word)))
+        .filter(|(_, counts)| !counts.is_empty())
+        .collect();
+    words.sort_by_key(|(_, counts)| std::cmp::Reverse(counts.len()));
+    let mut search = PhraseSearch {
+        words: &words,
+        max_words: options.max_words,
+        deadline: options.timeout.map(|timeout| start + timeout),
+        visit,
+        phrase: vec![],
+        coverage: PhraseCoverage { first_words: words.len(), ..PhraseCoverage::default() },
+    };
+    if !letters.is_empty() && options.max_words > 0 {
+        let _ = search.descend(&(0..words.len()).collect::<Vec<_>>(), &letters);
+    }
+    search.coverage.elapsed = start.elapsed();
+    search.coverage
+}
+
+/// State of a depth-first phrase search.
+struct PhraseSearch<'a, 'w, F> {
+    words: &'w [(&'a str, LetterCounts)],
+    max_words: usize,
+    deadline: Option<Instant>,
+    visit: F,
+    phrase: Vec<&'a str>,
+    coverage: PhraseCoverage,
+}
+
+impl<'a, F: FnMut(&[&'a str]) -> ControlFlow<()>> PhraseSearch<'a, '_, F> {
+    /// Extends the phrase with the candidates, indexes of words the remaining letters can make,
+    /// each followed only by itself and the candidates after it.
+    fn descend(&mut self, candidates: &[usize], remaining: &LetterCounts) -> ControlFlow<()> {
+        let top = self.phrase.is_empty();
+        for (position, &index) in candidates.iter().enumerate() {
+            self.coverage.nodes += 1;
+            if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                self.coverage.timed_out = true;
+                return ControlFlow::Break(());
+            }
+            let (word, counts) = &self.words[index];
+            let left = remaining.checked_sub(counts).expect("candidates can be made from the remaining letters");
+            self.phrase.push(word);
+            if left.is_empty() {
+                self.coverage.phrases += 1;
+                (self.visit)(&self.phrase)?;
+            } else if self.phrase.len() < self.max_words {
+                let next: Vec<usize> = candidates[position..].iter()
+                    .copied()
+                    .filter(|&next| can_be_constructed(&self.words[next].1, &left))
+                    .collect();
+                self.descend(&next, &left)?;
+            }
+            self.phrase.pop();
+            if top { self.coverage.first_words_searched += 1; }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod phrase_tests {
+    use std::ops::ControlFlow;
+    use std::time::Duration;
+
+    use crate::{test_words, Dictionary};
+
+    use super::{find_phrases, PhraseOptions};
+
+    fn dictionary() -> Dictionary {
+        test_words(&["dirty", "room", "dormitory", "moor", "rid", "toy", "rod", "dry", "my", "it", "or"]).into()
+    }
+
+    fn phrases(dictionary: &Dictionary, phrase: &str, max_words: usize) -> Vec<String> {
+        let mut found = vec![];
+        let options = PhraseOptions { max_words, timeout: None };
+        let coverage = find_phrases(&dictionary.view(), phrase, &options, |words| {
+            found.push(words.join(" "));
+            ControlFlow::Continue(())
+        });
+        assert!(coverage.is_complete() && !coverage.timed_out);
+        assert_eq!(coverage.phrases, found.len());
+        found
+    }
+
+    #[test]
+    fn phrases_use_every_letter_once() {
+        let dictionary = dictionary();
+        assert_eq!(phrases(&dictionary, "Dirty room!", 1), ["dormitory"]);
+        assert_eq!(phrases(&dictionary, "dirty room", 2), ["dormitory", "dirty room", "dirty moor"]);
+        assert_eq!(phrases(&dictionary, "dirty room", 3), ["dormitory", "dirty room", "dirty moor", "room dry it", "moor dry it"]);
+        assert!(phrases(&dictionary, "dirty rooms", 3).is_empty());
+        assert!(phrases(&dictionary, "", 3).is_empty());
+    }
+
+    #[test]
+    fn words_can_repeat() {
+        let dictionary: Dictionary = test_words(&["ab", "ba"]).into();
+        assert_eq!(phrases(&dictionary, "abab", 2), ["ab ab", "ab ba", "ba ba"]);
+    }
+
+    #[test]
+    fn visitor_and_deadline_stop_the_search() {
+        let dictionary = dictionary();
+        let options = PhraseOptions { max_words: 3, timeout: None };
+        let coverage = find_phrases(&dictionary.view(), "dirty room", &options, |_| ControlFlow::Break(()));
+        assert_eq!((coverage.phrases, coverage.first_words_searched, coverage.timed_out), (1, 0, false));
+        assert!(!coverage.is_complete());
+        let options = PhraseOptions { max_words: 3, timeout: Some(Duration::ZERO) };
+        let coverage = find_phrases(&dictionary.view(), "dirty room", &options, |_| ControlFlow::Continue(()));
+        assert!(coverage.timed_out && coverage.fraction() == 0.0);
+    }
+}
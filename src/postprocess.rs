@@ -0,0 +1,170 @@
+//! Transformations of the words found, applied in order before they are returned.
+//!
+//! A [`PostProcessor`] changes [`Matches`] in place: it can leave words out, as [`Dedupe`], a
+//! [`DenyList`] and a [`TagFilter`] do, or annotate them, as [`ScoreAnnotator`] does. A
+//! [`FindOptions`] runs a search and then every post-processor registered with it, so that the
+//! same pipeline serves every call site.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::deny::DenyList;
+use crate::matches::Matches;
+use crate::scoring::Scorer;
+use crate::DictionaryView;
+
+/// A transformation of the words found by a search.
+pub trait PostProcessor {
+    fn process(&self, matches: &mut Matches);
+}
+
+/// Leaves out the words equal to an earlier word, ignoring case.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Dedupe;
+
+/// Keeps the words according to their tags, e.g. leaving out the words tagged `slang`. Tags are
+/// given per word, compared ignoring case; a word without tags has none of them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TagFilter {
+    tags: HashMap<String, HashSet<String>>,
+    required: HashSet<String>,
+    excluded: HashSet<String>,
+}
+
+/// Scores every word, see [`Matches::scores`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScoreAnnotator<S>(pub S);
+
+/// A search and the post-processors applied to its words, in the order they were added.
+#[derive(Default)]
+pub struct FindOptions {
+    processors: Vec<Box<dyn PostProcessor>>,
+}
+
+impl PostProcessor for Dedupe {
+    fn process(&self, matches: &mut Matches) {
+        let mut seen = HashSet::new();
+        matches.retain(|word| seen.insert(word.to_lowercase()));
+    }
+}
+
+/// Leaves out the denied words, e.g. profanity.
+impl PostProcessor for DenyList {
+    fn process(&self, matches: &mut Matches) {
+        matches.retain(|word| !self.is_denied(word));
+    }
+}
+
+impl TagFilter {
+    /// A filter knowing the tags of some words, keeping every word until tags are required or
+    /// excluded.
+    pub fn new<I: IntoIterator<Item=(String, Vec<String>)>>(tags: I) -> Self {
+        let tags = tags.into_iter()
+            .map(|(word, tags)| (word.to_lowercase(), tags.iter().map(|tag| tag.to_lowercase()).collect()))
+            .collect();
+        TagFilter { tags, ..TagFilter::default() }
+    }
+
+    /// Keeps only the words with the tag.
+    pub fn require(mut self, tag: &str) -> Self {
+        self.required.insert(tag.to_lowercase());
+        self
+    }
+
+    /// Leaves out the words with the tag.
+    pub fn exclude(mut self, tag: &str) -> Self {
+        self.excluded.insert(tag.to_lowercase());
+        self
+    }
+
+    fn keeps(&self, word: &str) -> bool {
+        let none = HashSet::new();
+        let tags = self.tags.get(&word.to_lowercase()).unwrap_or(&none);
+        self.required.is_subset(tags) && self.excluded.is_disjoint(tags)
+    }
+}
+
+impl PostProcessor for TagFilter {
+    fn process(&self, matches: &mut Matches) {
+        matches.retain(|word| self.keeps(word));
+    }
+}
+
+impl<S: Scorer> PostProcessor for ScoreAnnotator<S> {
+    fn process(&self, matches: &mut Matches) {
+        matches.annotate_scores(&|word| self.0.score(word));
+    }
+}
+
+impl FindOptions {
+    pub fn new() -> Self {
+        FindOptions::default()
+    }
+
+    /// Adds a post-processor, applied after those already added.
+    pub fn with<P: PostProcessor + 'static>(mut self, processor: P) -> Self {
+        self.processors.push(Box::new(processor));
+        self
+    }
+
+    /// Adds [`Dedupe`].
+    pub fn dedupe(self) -> Self {
+        self.with(Dedupe)
+    }
+
+    /// Leaves out the words of a deny-list.
+    pub fn deny(self, list: DenyList) -> Self {
+        self.with(list)
+    }
+
+    /// Adds a [`ScoreAnnotator`].
+    pub fn score<S: Scorer + 'static>(self, scorer: S) -> Self {
+        self.with(ScoreAnnotator(scorer))
+    }
+
+    /// Applies every post-processor to the words, in order.
+    pub fn apply(&self, mut matches: Matches) -> Matches {
+        for processor in &self.processors {
+            processor.process(&mut matches);
+        }
+        matches
+    }
+
+    /// Words of `view` that can be constructed from the list of letters, in dictionary order,
+    /// after every post-processor.
+    pub fn find(&self, view: &DictionaryView<'_>, letters: &str) -> Matches {
+        self.apply(view.find(letters).into())
+    }
+}
+
+#[cfg(test)]
+mod post_processor_tests {
+    use crate::deny::DenyList;
+    use crate::scoring::Scrabble;
+    use crate::{test_words, Dictionary};
+
+    use super::{FindOptions, TagFilter};
+
+    fn dictionary() -> Dictionary {
+        test_words(&["cat", "act", "Cat", "tac", "at", "CAT"]).into()
+    }
+
+    #[test]
+    fn pipeline() {
+        let dictionary = dictionary();
+        let options = FindOptions::new().dedupe().deny(DenyList::new(["tac"])).score(Scrabble);
+        let matches = options.find(&dictionary.view(), "tca");
+        assert_eq!(matches.words(), ["cat", "act", "at"]);
+        assert_eq!(matches.scores(), Some(&[5, 5, 2][..]));
+        assert_eq!(FindOptions::new().find(&dictionary.view(), "tca").len(), 6);
+    }
+
+    #[test]
+    fn tags() {
+        let dictionary = dictionary();
+        let tags = TagFilter::new([("CAT".to_string(), vec!["Animal".to_string()]), ("tac".to_string(), vec!["slang".to_string()])]);
+        let find = |filter: TagFilter| FindOptions::new().with(filter).find(&dictionary.view(), "tca").words().to_vec();
+        assert_eq!(find(tags.clone().require("animal")), ["cat", "Cat", "CAT"]);
+        assert_eq!(find(tags.clone().exclude("SLANG")), ["cat", "act", "Cat", "at", "CAT"]);
+        assert_eq!(find(tags.require("animal").exclude("animal")), [] as [&str; 0]);
+    }
+}
@@ -0,0 +1,392 @@
+//! Spelling-Bee-style puzzles: a fixed list of letters, an optional required letter
+//! and the set of dictionary words that solve it.
+//!
+//! A puzzle knows how to score each of its answers and how to turn a score into a
+//! [`Rank`], so game frontends only need to keep track of the words a player has found.
+//! Guesses can be checked one at a time with [`adjudicate`], which also records accepted words,
+//! or a whole round of submissions at once with [`adjudicate_many`].
+//!
+//! A puzzle built from a [`Dictionary`] remembers the dictionary's [`Fingerprint`], so that a
+//! server adjudicating guesses can check that it uses the same lexicon as the puzzle's author.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::parallel::*;
+use crate::{get_letters_count, map_can_be_constructed, missing_letters, word_len, Dictionary, Fingerprint, Word};
+
+/// Bonus awarded on top of the word score for a pangram.
+pub const PANGRAM_BONUS: u32 = 7;
+
+/// A puzzle made from a count-sensitive list of letters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Puzzle {
+    pub letters: String,
+    pub required: Option<char>,
+    pub min_len: usize,
+    /// Fingerprint of the dictionary the puzzle was built from, if it was built from one.
+    pub lexicon: Option<Fingerprint>,
+    letters_count: HashMap<char, u8>,
+    answers: Vec<String>,
+    found: HashSet<String>,
+}
+
+/// Rank reached by a player, from lowest to highest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rank {
+    Beginner,
+    GoodStart,
+    MovingUp,
+    Good,
+    Solid,
+    Nice,
+    Great,
+    Amazing,
+    Genius,
+    QueenBee,
+}
+
+/// Outcome of a single guess, see [`adjudicate`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Accepted,
+    TooShort,
+    NotInDictionary,
+    MissingRequiredLetter,
+    LettersNotAvailable { missing: HashMap<char, u8> },
+    AlreadyFound,
+}
+
+/// Outcome of a batch of guesses, see [`adjudicate_many`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchVerdict {
+    /// Verdict of every guess, in the order of the guesses.
+    pub verdicts: Vec<Verdict>,
+    /// Sum of the scores of the accepted guesses.
+    pub score: u32,
+}
+
+/// Score and rank of a set of found words.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    pub score: u32,
+    pub max_score: u32,
+    pub rank: Rank,
+    /// The next rank along with the score needed to reach it, `None` once the top rank is reached.
+    pub next: Option<(Rank, u32)>,
+}
+
+impl Rank {
+    /// Every rank in ascending order.
+    pub const ALL: [Rank; 10] = [
+        Rank::Beginner,
+        Rank::GoodStart,
+        Rank::MovingUp,
+        Rank::Good,
+        Rank::Solid,
+        Rank::Nice,
+        Rank::Great,
+        Rank::Amazing,
+        Rank::Genius,
+        Rank::QueenBee,
+    ];
+
+    /// Percentage of the maximum score needed to reach the rank.
+    pub fn percentage(self) -> u32 {
+        match self {
+            Rank::Beginner => 0,
+            Rank::GoodStart => 2,
+            Rank::MovingUp => 5,
+            Rank::Good => 8,
+            Rank::Solid => 15,
+            Rank::Nice => 25,
+            Rank::Great => 40,
+            Rank::Amazing => 50,
+            Rank::Genius => 70,
+            Rank::QueenBee => 100,
+        }
+    }
+}
+
+impl Puzzle {
+    /// Builds a puzzle from a list of words, keeping those that can be constructed from `letters`,
+    /// contain the `required` letter (if any) and are at least `min_len` letters long.
+    pub fn new(words: &[Word], letters: &str, required: Option<char>, min_len: usize) -> Puzzle {
+        let letters_count = get_letters_count(letters);
+        let required = required.map(|c| c.to_ascii_lowercase());
+        let answers = words.iter()
+            .filter(|word| word_len(&word.letters) >= min_len)
+            .filter(|word| required.is_none_or(|c| word.letters.contains_key(&c)))
+            .filter(|word| map_can_be_constructed(&word.letters, &letters_count))
+            .map(|word| word.value.clone())
+            .collect();
+        Puzzle {
+            letters: letters.to_string(),
+            required,
+            min_len,
+            lexicon: None,
+            letters_count,
+            answers,
+            found: HashSet::new(),
+        }
+    }
+
+    /// Same as [`new`](Puzzle::new), with the words of a dictionary, recording its fingerprint.
+    pub fn from_dictionary(dictionary: &Dictionary, letters: &str, required: Option<char>, min_len: usize) -> Puzzle {
+        Puzzle { lexicon: Some(dictionary.fingerprint()), ..Puzzle::new(dictionary.words(), letters, required, min_len) }
+    }
+
+    /// Returns `true` unless the puzzle was built from a dictionary with other words than
+    /// `dictionary`.
+    pub fn uses_lexicon(&self, dictionary: &Dictionary) -> bool {
+        self.lexicon.is_none_or(|lexicon| lexicon == dictionary.fingerprint())
+    }
+
+    /// Words that solve the puzzle, in dictionary order.
+    pub fn answers(&self) -> &[String] {
+        &self.answers
+    }
+
+    /// Returns `true` if the word is one of the puzzle's answers.
+    pub fn is_answer(&self, word: &str) -> bool {
+        self.answers.iter().any(|answer| answer == word)
+    }
+
+    /// Answers accepted so far by [`adjudicate`].
+    pub fn found(&self) -> &HashSet<String> {
+        &self.found
+    }
+
+    /// Progress of the answers accepted so far by [`adjudicate`].
+    pub fn progress(&self) -> Progress {
+        let found: Vec<&str> = self.found.iter().map(|word| word.as_str()).collect();
+        self.evaluate(&found)
+    }
+
+    /// Returns `true` if the word uses every distinct letter of the puzzle.
+    pub fn is_pangram(&self, word: &str) -> bool {
+        let letters = get_letters_count(word);
+        !letters.is_empty() && self.letters_count.keys().all(|c| letters.contains_key(c))
+    }
+
+    /// Score of a single word: 1 point for a word of the minimum length, otherwise one
+    /// point per letter, plus [`PANGRAM_BONUS`] for pangrams.
+    pub fn word_score(&self, word: &str) -> u32 {
+        let len = word_len(&get_letters_count(word));
+        let score = if len <= self.min_len { 1 } else { len as u32 };
+        if self.is_pangram(word) { score + PANGRAM_BONUS } else { score }
+    }
+
+    /// Sum of the scores of every answer.
+    pub fn max_score(&self) -> u32 {
+        self.answers.iter().map(|answer| self.word_score(answer)).sum()
+    }
+
+    /// Minimum score needed for each rank, in ascending order.
+    pub fn rank_thresholds(&self) -> Vec<(Rank, u32)> {
+        let max_score = self.max_score();
+        Rank::ALL.iter()
+            .map(|&rank| (rank, (max_score * rank.percentage() + 50) / 100))
+            .collect()
+    }
+
+    /// Evaluates the words found by a player. Words that are not answers and duplicates are ignored.
+    pub fn evaluate(&self, found: &[&str]) -> Progress {
+        let score = found.iter()
+            .filter(|word| self.is_answer(word))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|word| self.word_score(word))
+            .sum();
+        let thresholds = self.rank_thresholds();
+        let rank = thresholds.iter()
+            .rev()
+            .find(|(_, threshold)| score >= *threshold)
+            .map_or(Rank::Beginner, |&(rank, _)| rank);
+        let next = thresholds.into_iter().find(|&(_, threshold)| threshold > score);
+        Progress { score, max_score: self.max_score(), rank, next }
+    }
+}
+
+/// Checks a guess against the puzzle, recording it as found when it is accepted.
+///
+/// Checks are made in order, so a guess that is both too short and not in the dictionary
+/// is reported as [`Verdict::TooShort`]. Guesses are matched case-insensitively.
+pub fn adjudicate(word: &str, puzzle: &mut Puzzle) -> Verdict {
+    let answer = match check(puzzle, word, |word| puzzle.answers.iter().find(|answer| answer.eq_ignore_ascii_case(word))) {
+        Ok(answer) => answer.clone(),
+        Err(verdict) => return verdict,
+    };
+    if puzzle.found.insert(answer) { Verdict::Accepted } else { Verdict::AlreadyFound }
+}
+
+/// Checks a batch of guesses like [`adjudicate`] would one after the other, recording the
+/// accepted ones, so a guess repeated within the batch is [`Verdict::AlreadyFound`] the second
+/// time. Guesses are checked in parallel, each normalized and looked up in a hash of the answers
+/// once; only recording the accepted guesses is sequential.
+pub fn adjudicate_many(guesses: &[&str], puzzle: &mut Puzzle) -> BatchVerdict {
+    let mut answers: HashMap<String, &String> = HashMap::new();
+    for answer in &puzzle.answers {
+        answers.entry(answer.to_ascii_lowercase()).or_insert(answer);
+    }
+    let checked: Vec<Result<&String, Verdict>> = guesses.par_iter()
+        .map(|guess| check(puzzle, guess, |word| answers.get(&word.to_ascii_lowercase()).copied()))
+        .collect();
+
+    let mut found = puzzle.found.clone();
+    let mut score = 0;
+    let verdicts = checked.into_iter()
+        .map(|checked| match checked {
+            Ok(answer) if found.insert(answer.clone()) => {
+                score += puzzle.word_score(answer);
+                Verdict::Accepted
+            }
+            Ok(_) => Verdict::AlreadyFound,
+            Err(verdict) => verdict,
+        })
+        .collect();
+    puzzle.found = found;
+    BatchVerdict { verdicts, score }
+}
+
+/// Every check of [`adjudicate`] but the last, returning the answer the guess stands for.
+/// `find_answer` looks up the trimmed guess among the answers, ignoring case.
+fn check<'p, F: Fn(&str) -> Option<&'p String>>(puzzle: &Puzzle, word: &str, find_answer: F) -> Result<&'p String, Verdict> {
+    let letters = get_letters_count(word);
+    if word_len(&letters) < puzzle.min_len {
+        return Err(Verdict::TooShort);
+    }
+    if puzzle.required.is_some_and(|c| !letters.contains_key(&c)) {
+        return Err(Verdict::MissingRequiredLetter);
+    }
+    if !map_can_be_constructed(&letters, &puzzle.letters_count) {
+        return Err(Verdict::LettersNotAvailable { missing: missing_letters(&letters, &puzzle.letters_count) });
+    }
+    find_answer(word.trim()).ok_or(Verdict::NotInDictionary)
+}
+
+#[cfg(test)]
+mod puzzle_tests {
+    use crate::{test_words, Dictionary};
+
+    use super::{Puzzle, Rank, PANGRAM_BONUS};
+
+    fn puzzle() -> Puzzle {
+        let words = test_words(&["at", "tar", "rat", "star", "tsar", "arts", "start", "trams", "smart", "mast"]);
+        Puzzle::new(&words, "smartt", Some('r'), 3)
+    }
+
+    #[test]
+    fn answers_respect_letters_required_and_min_len() {
+        let expected = vec!["tar", "rat", "star", "tsar", "arts", "start", "trams", "smart"];
+        assert_eq!(puzzle().answers(), expected);
+    }
+
+    #[test]
+    fn lexicon_fingerprint() {
+        let dictionary: Dictionary = test_words(&["at", "tar", "rat", "star"]).into();
+        let puzzle = Puzzle::from_dictionary(&dictionary, "star", None, 3);
+        assert_eq!(puzzle.answers(), vec!["tar", "rat", "star"]);
+        assert_eq!(puzzle.lexicon, Some(dictionary.fingerprint()));
+        assert!(puzzle.uses_lexicon(&dictionary));
+        assert!(!puzzle.uses_lexicon(&test_words(&["at", "tar"]).into()));
+        assert!(Puzzle::new(dictionary.words(), "star", None, 3).uses_lexicon(&Dictionary::default()));
+    }
+
+    #[test]
+    fn word_scores() {
+        let puzzle = puzzle();
+        assert_eq!(puzzle.word_score("tar"), 1);
+        assert_eq!(puzzle.word_score("star"), 4);
+        assert_eq!(puzzle.word_score("smart"), 5 + PANGRAM_BONUS);
+    }
+
+    #[test]
+    fn max_score() {
+        assert_eq!(puzzle().max_score(), 1 + 1 + 4 + 4 + 4 + 5 + 12 + 12);
+    }
+
+    #[test]
+    fn rank_thresholds_are_ascending() {
+        let thresholds = puzzle().rank_thresholds();
+        assert_eq!(thresholds.first(), Some(&(Rank::Beginner, 0)));
+        assert_eq!(thresholds.last(), Some(&(Rank::QueenBee, 43)));
+        assert!(thresholds.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn evaluate_ignores_invalid_and_duplicate_words() {
+        let progress = puzzle().evaluate(&["star", "star", "mast", "xyz"]);
+        assert_eq!(progress.score, 4);
+        assert_eq!(progress.rank, Rank::Good);
+        assert_eq!(progress.next, Some((Rank::Solid, 6)));
+    }
+
+    #[test]
+    fn evaluate_all_answers() {
+        let puzzle = puzzle();
+        let found: Vec<&str> = puzzle.answers().iter().map(|s| s.as_str()).collect();
+        let progress = puzzle.evaluate(&found);
+        assert_eq!(progress.score, progress.max_score);
+        assert_eq!(progress.rank, Rank::QueenBee);
+        assert_eq!(progress.next, None);
+    }
+}
+
+#[cfg(test)]
+mod adjudicate_tests {
+    use std::collections::HashMap;
+
+    use crate::test_words;
+
+    use super::{adjudicate, adjudicate_many, Puzzle, Verdict, PANGRAM_BONUS};
+
+    fn puzzle() -> Puzzle {
+        Puzzle::new(&test_words(&["tar", "star", "smart", "trams"]), "smartt", Some('r'), 3)
+    }
+
+    #[test]
+    fn accepted_then_already_found() {
+        let mut puzzle = puzzle();
+        assert_eq!(adjudicate("star", &mut puzzle), Verdict::Accepted);
+        assert_eq!(adjudicate("STAR", &mut puzzle), Verdict::AlreadyFound);
+        assert_eq!(puzzle.progress().score, 4);
+    }
+
+    #[test]
+    fn too_short() {
+        assert_eq!(adjudicate("ar", &mut puzzle()), Verdict::TooShort);
+    }
+
+    #[test]
+    fn missing_required_letter() {
+        assert_eq!(adjudicate("mast", &mut puzzle()), Verdict::MissingRequiredLetter);
+    }
+
+    #[test]
+    fn letters_not_available() {
+        let output = adjudicate("terror", &mut puzzle());
+        let expected = Verdict::LettersNotAvailable { missing: HashMap::from([('r', 2), ('e', 1), ('o', 1)]) };
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn not_in_dictionary() {
+        assert_eq!(adjudicate("rats", &mut puzzle()), Verdict::NotInDictionary);
+    }
+
+    #[test]
+    fn batches_match_one_at_a_time() {
+        let guesses = ["star", "ar", "Trams ", "mast", "STAR", "rats", "terror", "tar"];
+        let mut one_at_a_time = puzzle();
+        adjudicate("tar", &mut one_at_a_time);
+        let expected: Vec<Verdict> = guesses.iter().map(|guess| adjudicate(guess, &mut one_at_a_time)).collect();
+
+        let mut batched = puzzle();
+        adjudicate("tar", &mut batched);
+        let batch = adjudicate_many(&guesses, &mut batched);
+        assert_eq!(batch.verdicts, expected);
+        assert_eq!(batch.score, 4 + 5 + PANGRAM_BONUS);
+        assert_eq!(batched.found(), one_at_a_time.found());
+        assert_eq!(adjudicate_many(&[], &mut batched).score, 0);
+    }
+}
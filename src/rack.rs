@@ -0,0 +1,339 @@
+//! Tiles and racks of tiles, as held by a player.
+//!
+//! A [`Tile`] is a letter and its point value, or a blank. Blanks are worth nothing and stand
+//! for any letter: once played, a blank is designated as the letter it stands for, and is
+//! displayed as that letter in uppercase, so that `caT` is "cat" played with a blank `t`.
+//!
+//! A [`Rack`] keeps its tiles in the order they were drawn, so that it is displayed the way the
+//! player sees it, and counts them as [`LetterCounts`] for searches. Blanks are written `?`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "random")]
+use rand::Rng;
+
+use crate::alphabet::{Alphabet, English};
+use crate::letters::can_be_constructed_with_blanks;
+#[cfg(feature = "random")]
+use crate::random_string;
+use crate::{get_letters_count, missing_letters, LetterCounts, LetterKey, ParseError};
+
+/// How a blank tile is written before it is designated.
+pub const BLANK: char = '?';
+
+/// Point value of each English letter, in the proportions of Scrabble.
+pub(crate) const ENGLISH_VALUES: [u32; 26] = [
+    1, 3, 3, 2, 1, 4, 2, 4, 1, 8, 5, 1, 3, 1, 1, 3, 10, 1, 1, 1, 1, 4, 4, 8, 4, 10,
+];
+
+/// A tile: a lowercase letter and its value, or a blank.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Tile {
+    /// The letter, [`BLANK`] for a blank that is not designated yet.
+    pub letter: char,
+    pub value: u32,
+    pub is_blank: bool,
+}
+
+/// What [`Rack::random`] draws.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RackSpec {
+    /// Number of letter tiles, or `None` for a random number from 1 to 200.
+    pub letters: Option<u8>,
+    pub blanks: usize,
+}
+
+/// Tiles of a player, in order. Blanks on a rack are never designated.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rack {
+    tiles: Vec<Tile>,
+}
+
+impl Tile {
+    /// A letter tile. Panics if `letter` is not an English letter.
+    pub fn new(letter: char, value: u32) -> Tile {
+        let letter = letter.to_ascii_lowercase();
+        assert!(English::index(letter).is_some(), "{:?} is not a letter", letter);
+        Tile { letter, value, is_blank: false }
+    }
+
+    /// A letter tile with its English value, or a blank for [`BLANK`].
+    pub fn english(letter: char) -> Tile {
+        if letter == BLANK { return Tile::blank(); }
+        let value = English::index(letter.to_ascii_lowercase()).map_or(0, |index| ENGLISH_VALUES[index]);
+        Tile::new(letter, value)
+    }
+
+    /// A blank that is not designated yet.
+    pub fn blank() -> Tile {
+        Tile { letter: BLANK, value: 0, is_blank: true }
+    }
+
+    /// The blank, standing for `letter`. Panics if the tile is not a blank.
+    pub fn designate(self, letter: char) -> Tile {
+        assert!(self.is_blank, "only blanks can be designated");
+        Tile { letter: Tile::new(letter, 0).letter, ..self }
+    }
+
+    /// Returns `true` for a blank standing for a letter.
+    pub fn is_designated(&self) -> bool {
+        self.is_blank && self.letter != BLANK
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_designated() {
+            write!(f, "{}", self.letter.to_ascii_uppercase())
+        } else {
+            write!(f, "{}", self.letter)
+        }
+    }
+}
+
+/// Sum of the English tile values of the letters of a word, ignoring any other character.
+pub fn word_score(word: &str) -> u32 {
+    word.chars().filter_map(|c| English::index(c.to_ascii_lowercase())).map(|index| ENGLISH_VALUES[index]).sum()
+}
+
+/// Sum of the values of the tiles, blanks counting for nothing.
+pub fn tile_score(tiles: &[Tile]) -> u32 {
+    tiles.iter().map(|tile| tile.value).sum()
+}
+
+impl RackSpec {
+    /// `count` letter tiles and no blank.
+    pub fn letters(count: u8) -> Self {
+        RackSpec { letters: Some(count), blanks: 0 }
+    }
+
+    pub fn with_blanks(mut self, blanks: usize) -> Self {
+        self.blanks = blanks;
+        self
+    }
+}
+
+impl Rack {
+    /// An empty rack.
+    pub fn new() -> Rack {
+        Rack::default()
+    }
+
+    /// A rack of letters drawn uniformly from a to z, with English tile values, followed by the
+    /// blanks of the spec. Unlike a [`TileBag`](crate::game::TileBag) draw, letters are drawn with
+    /// replacement, like [`generate_random_string`](crate::generate_random_string) does.
+    #[cfg(feature = "random")]
+    pub fn random<R: Rng + ?Sized>(spec: RackSpec, rng: &mut R) -> Rack {
+        let len = spec.letters.unwrap_or_else(|| rng.gen_range(1..=200));
+        let mut rack = Rack::new();
+        rack.extend(random_string(rng, len).chars().map(Tile::english));
+        rack.extend(std::iter::repeat_n(Tile::blank(), spec.blanks));
+        rack
+    }
+
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Number of tiles, blanks included.
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Number of blank tiles.
+    pub fn blanks(&self) -> usize {
+        self.tiles.iter().filter(|tile| tile.is_blank).count()
+    }
+
+    /// The letter tiles, in order, without the blanks.
+    pub fn letters(&self) -> String {
+        self.tiles.iter().filter(|tile| !tile.is_blank).map(|tile| tile.letter).collect()
+    }
+
+    /// Adds a tile at the end of the rack, undoing the designation of a blank.
+    pub fn push(&mut self, tile: Tile) {
+        self.tiles.push(if tile.is_blank { Tile::blank() } else { tile });
+    }
+
+    /// Removes a tile with the letter, ignoring case, or a blank for [`BLANK`]. Returns `None`
+    /// if the rack does not have one.
+    pub fn remove(&mut self, letter: char) -> Option<Tile> {
+        let letter = letter.to_ascii_lowercase();
+        let position = self.tiles.iter().position(|tile| tile.letter == letter)?;
+        Some(self.tiles.remove(position))
+    }
+
+    /// Removes the tiles needed to play `word`, using blanks for the letters the rack does not
+    /// have, and returns them in the order of the word, blanks designated. Returns `None`,
+    /// leaving the rack unchanged, if the word cannot be made.
+    pub fn remove_word(&mut self, word: &str) -> Option<Vec<Tile>> {
+        let mut rest = self.clone();
+        let played = word.chars()
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|letter| rest.remove(letter).or_else(|| rest.remove(BLANK).map(|blank| blank.designate(letter))))
+            .collect::<Option<Vec<Tile>>>()?;
+        *self = rest;
+        Some(played)
+    }
+
+    /// Returns `true` if the tiles can make `word`, blanks standing for any letter. An empty
+    /// word can never be made.
+    pub fn can_make(&self, word: &str) -> bool {
+        can_be_constructed_with_blanks(&LetterCounts::from_word::<English>(word), &self.counts(), self.blanks())
+    }
+
+    /// The letters (and how many of each) that `word` needs but the rack cannot supply, even with
+    /// its blanks. Empty if the rack can make the word.
+    pub fn missing(&self, word: &str) -> HashMap<char, u8> {
+        if self.can_make(word) { return HashMap::new(); }
+        missing_letters(&get_letters_count(word), &self.counts().to_map::<English>())
+    }
+
+    /// Counts of the letter tiles, without the blanks.
+    pub fn counts(&self) -> LetterCounts {
+        LetterCounts::from_word::<English>(&self.letters())
+    }
+
+    /// Canonical key of the letter tiles, without the blanks.
+    pub fn key(&self) -> LetterKey {
+        LetterKey::from(&self.counts())
+    }
+}
+
+impl FromStr for Rack {
+    type Err = ParseError;
+
+    /// Parses tiles written one character each, e.g. `"aert?s"`, ignoring whitespace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rack = Rack::new();
+        for (position, c) in s.chars().enumerate() {
+            if c.is_whitespace() { continue; }
+            if c != BLANK && English::index(c).is_none() {
+                return Err(ParseError::InvalidChar { c, position });
+            }
+            rack.push(Tile::english(c));
+        }
+        Ok(rack)
+    }
+}
+
+impl fmt::Display for Rack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tiles.iter().try_for_each(|tile| write!(f, "{}", tile))
+    }
+}
+
+impl Extend<Tile> for Rack {
+    fn extend<I: IntoIterator<Item=Tile>>(&mut self, tiles: I) {
+        for tile in tiles {
+            self.push(tile);
+        }
+    }
+}
+
+impl IntoIterator for Rack {
+    type Item = Tile;
+    type IntoIter = std::vec::IntoIter<Tile>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tiles.into_iter()
+    }
+}
+
+impl From<&Rack> for LetterCounts {
+    fn from(rack: &Rack) -> Self {
+        rack.counts()
+    }
+}
+
+#[cfg(test)]
+mod rack_tests {
+    use std::collections::HashMap;
+
+    use crate::alphabet::English;
+    use crate::{LetterCounts, LetterKey, ParseError};
+
+    use super::{tile_score, word_score, Rack, Tile};
+
+    fn rack(tiles: &str) -> Rack {
+        tiles.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let rack = rack("Ca t?");
+        assert_eq!(rack.to_string(), "cat?");
+        assert_eq!((rack.len(), rack.blanks()), (4, 1));
+        assert_eq!(rack.letters(), "cat");
+        assert_eq!("ca1".parse::<Rack>(), Err(ParseError::InvalidChar { c: '1', position: 2 }));
+        assert!("".parse::<Rack>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn add_and_remove_tiles() {
+        let mut rack = rack("cat");
+        rack.push(Tile::english('S'));
+        rack.extend([Tile::blank().designate('x'), Tile::new('e', 2)]);
+        assert_eq!(rack.to_string(), "cats?e");
+        assert_eq!(rack.remove('T'), Some(Tile::english('t')));
+        assert_eq!(rack.remove('x'), None);
+        assert_eq!(rack.to_string(), "cas?e");
+    }
+
+    #[test]
+    fn words_with_blanks() {
+        let mut rack = rack("ca?");
+        assert!(rack.can_make("cat"));
+        assert!(!rack.can_make("tact"));
+        assert!(!rack.can_make(""));
+        assert_eq!(rack.missing("tact"), HashMap::from([('t', 2)]));
+        assert_eq!(rack.remove_word("tact"), None);
+        assert_eq!(rack.to_string(), "ca?");
+        let played = rack.remove_word("at").unwrap();
+        assert_eq!(played.iter().map(Tile::to_string).collect::<String>(), "aT");
+        assert_eq!(tile_score(&played), 1);
+        assert_eq!(rack.to_string(), "c");
+    }
+
+    #[test]
+    fn tiles() {
+        assert_eq!(Tile::english('Q'), Tile { letter: 'q', value: 10, is_blank: false });
+        assert_eq!(Tile::english('?'), Tile::blank());
+        let blank = Tile::blank().designate('E');
+        assert_eq!((blank.letter, blank.value, blank.to_string()), ('e', 0, "E".to_string()));
+        assert!(blank.is_designated() && !Tile::blank().is_designated());
+        assert_eq!(tile_score(&rack("quiz").tiles), 22);
+        assert_eq!(word_score("Qu-iz"), 22);
+    }
+
+    #[test]
+    fn conversions() {
+        let rack = rack("tac?");
+        assert_eq!(LetterCounts::from(&rack), LetterCounts::from_word::<English>("cat"));
+        assert_eq!(rack.key(), LetterKey::from_word("act"));
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn random_racks() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        use super::RackSpec;
+
+        let mut rng = StdRng::seed_from_u64(4);
+        let rack = Rack::random(RackSpec::letters(7).with_blanks(2), &mut rng);
+        assert_eq!((rack.len(), rack.blanks(), rack.letters().len()), (9, 2, 7));
+        assert!(rack.to_string().ends_with("??"));
+        assert_eq!(rack.counts(), LetterCounts::from_word::<English>(&rack.letters()));
+        assert!(rack.tiles().iter().all(|tile| tile.is_blank || tile.value == Tile::english(tile.letter).value));
+        let len = Rack::random(RackSpec::default(), &mut rng).len();
+        assert!((1..=200).contains(&len));
+    }
+}
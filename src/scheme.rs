@@ -0,0 +1,224 @@
+//! Letter values and tile distributions of word games.
+//!
+//! A [`TileScheme`] gives every English letter a value and a number of tiles, plus a number of
+//! blanks. It scores words, fills a [`TileBag`] and generates random letters in the proportions
+//! of its bag. The [`Scheme`] presets cover common games, and a [`SchemeRegistry`] looks schemes
+//! up by name, so that applications can let users pick one, including schemes they add at runtime.
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "random")]
+use rand::Rng;
+
+use crate::alphabet::{Alphabet, English};
+use crate::distribution::LetterFrequencies;
+use crate::game::{TileBag, ENGLISH_TILES};
+use crate::rack::{ENGLISH_VALUES, BLANK};
+use crate::{Tile, Word};
+
+/// Preset schemes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scheme {
+    /// English Scrabble: 98 letter tiles and 2 blanks.
+    Scrabble,
+    /// Words With Friends: 102 letter tiles and 2 blanks.
+    Wwf,
+    /// French Scrabble: 100 letter tiles and 2 blanks.
+    LettersFr,
+}
+
+/// Value and number of tiles of every letter (0 for a, 25 for z) of a game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TileScheme {
+    pub name: String,
+    pub values: [u32; 26],
+    pub counts: [usize; 26],
+    pub blanks: usize,
+}
+
+/// Schemes by name, starting with the presets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemeRegistry {
+    schemes: BTreeMap<String, TileScheme>,
+}
+
+const WWF_VALUES: [u32; 26] = [
+    1, 4, 4, 2, 1, 4, 3, 3, 1, 10, 5, 2, 4, 2, 1, 4, 10, 1, 1, 1, 2, 5, 4, 8, 3, 10,
+];
+
+const WWF_COUNTS: [usize; 26] = [
+    9, 2, 2, 5, 13, 2, 3, 4, 8, 1, 1, 4, 2, 5, 8, 2, 1, 6, 5, 7, 4, 2, 2, 1, 2, 1,
+];
+
+const FRENCH_VALUES: [u32; 26] = [
+    1, 3, 3, 2, 1, 4, 2, 4, 1, 8, 10, 1, 2, 1, 1, 3, 8, 1, 1, 1, 1, 4, 10, 10, 10, 10,
+];
+
+const FRENCH_COUNTS: [usize; 26] = [
+    9, 2, 2, 3, 15, 2, 2, 2, 8, 1, 1, 5, 3, 6, 6, 2, 1, 6, 6, 6, 6, 2, 1, 1, 1, 1,
+];
+
+impl Scheme {
+    pub const ALL: [Scheme; 3] = [Scheme::Scrabble, Scheme::Wwf, Scheme::LettersFr];
+
+    /// Name of the preset in a [`SchemeRegistry`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Scheme::Scrabble => "scrabble",
+            Scheme::Wwf => "wwf",
+            Scheme::LettersFr => "letters-fr",
+        }
+    }
+
+    /// Values and tiles of the preset.
+    pub fn tiles(self) -> TileScheme {
+        let (values, counts) = match self {
+            Scheme::Scrabble => (ENGLISH_VALUES, ENGLISH_TILES.map(|(_, count)| count)),
+            Scheme::Wwf => (WWF_VALUES, WWF_COUNTS),
+            Scheme::LettersFr => (FRENCH_VALUES, FRENCH_COUNTS),
+        };
+        TileScheme { name: self.name().to_string(), values, counts, blanks: 2 }
+    }
+}
+
+impl TileScheme {
+    /// A tile of the scheme, or a blank for [`BLANK`]. Panics if `letter` is neither an English
+    /// letter nor a blank.
+    pub fn tile(&self, letter: char) -> Tile {
+        if letter == BLANK { return Tile::blank(); }
+        let index = English::index(letter).unwrap_or_else(|| panic!("{:?} is not a letter", letter));
+        Tile::new(letter, self.values[index])
+    }
+
+    /// Sum of the values of the letters of a word, ignoring any other character.
+    pub fn word_score(&self, word: &str) -> u32 {
+        word.chars().filter_map(English::index).map(|index| self.values[index]).sum()
+    }
+
+    /// Number of tiles, blanks included.
+    pub fn tile_count(&self) -> usize {
+        self.counts.iter().sum::<usize>() + self.blanks
+    }
+
+    /// A full bag of the scheme's tiles, blanks included.
+    pub fn bag(&self) -> TileBag {
+        let tiles = self.counts.iter()
+            .enumerate()
+            .flat_map(|(index, &count)| std::iter::repeat_n(self.tile(English::letter(index)), count))
+            .collect();
+        TileBag::new(tiles).with_blanks(self.blanks)
+    }
+
+    /// The proportions of the letter tiles, for generating letters.
+    pub fn letter_frequencies(&self) -> LetterFrequencies {
+        LetterFrequencies::from_counts(self.counts.map(|count| count as u64))
+    }
+
+    /// A random string of `len` letters, each drawn independently in the proportions of the
+    /// letter tiles.
+    #[cfg(feature = "random")]
+    pub fn random_letters<R: Rng + ?Sized>(&self, rng: &mut R, len: usize) -> String {
+        self.letter_frequencies().random_letters::<English, R>(rng, len)
+    }
+
+    /// A scheme named `name` with the values and proportions of the letters of `words`, see
+    /// [`LetterFrequencies::tile_counts`]: letters are worth more the fewer tiles they have.
+    pub fn from_words(name: &str, words: &[Word], letter_tiles: usize, blanks: usize) -> TileScheme {
+        let counts = LetterFrequencies::<26>::from_words::<English>(words).tile_counts(letter_tiles);
+        let most = counts.iter().copied().max().unwrap_or(0);
+        let values = counts.map(|count| most.checked_div(count).map_or(0, |value| value.clamp(1, 10) as u32));
+        TileScheme { name: name.to_string(), values, counts, blanks }
+    }
+}
+
+impl From<Scheme> for TileScheme {
+    fn from(scheme: Scheme) -> Self {
+        scheme.tiles()
+    }
+}
+
+impl SchemeRegistry {
+    /// A registry of the presets.
+    pub fn new() -> SchemeRegistry {
+        let mut registry = SchemeRegistry { schemes: BTreeMap::new() };
+        for scheme in Scheme::ALL {
+            registry.register(scheme.tiles());
+        }
+        registry
+    }
+
+    /// Adds a scheme under its name, returning the scheme it replaces, if any.
+    pub fn register(&mut self, scheme: TileScheme) -> Option<TileScheme> {
+        self.schemes.insert(scheme.name.clone(), scheme)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TileScheme> {
+        self.schemes.get(name)
+    }
+
+    /// Names of the schemes, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item=&str> {
+        self.schemes.keys().map(String::as_str)
+    }
+}
+
+impl Default for SchemeRegistry {
+    fn default() -> Self {
+        SchemeRegistry::new()
+    }
+}
+
+#[cfg(test)]
+mod scheme_tests {
+    #[cfg(feature = "random")]
+    use rand::rngs::StdRng;
+    #[cfg(feature = "random")]
+    use rand::SeedableRng;
+
+    use crate::game::TileBag;
+    use crate::rack::word_score;
+    use crate::test_words;
+
+    use super::{Scheme, SchemeRegistry, TileScheme};
+
+    #[test]
+    fn presets() {
+        let sizes: Vec<usize> = Scheme::ALL.iter().map(|scheme| scheme.tiles().tile_count()).collect();
+        assert_eq!(sizes, vec![100, 104, 102]);
+        let scrabble = Scheme::Scrabble.tiles();
+        assert_eq!(scrabble.word_score("Quiz"), word_score("quiz"));
+        assert_eq!(scrabble.bag().len(), TileBag::english().with_blanks(2).len());
+        assert_eq!(Scheme::Wwf.tiles().word_score("quiz"), 10 + 2 + 1 + 10);
+        assert_eq!(Scheme::LettersFr.tiles().tile('k').value, 10);
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn bags_use_the_scheme_values() {
+        let mut bag = Scheme::Wwf.tiles().bag();
+        let rack = bag.draw(&mut StdRng::seed_from_u64(1), 104);
+        let h = rack.tiles().iter().find(|tile| tile.letter == 'h').unwrap();
+        assert_eq!(h.value, 3);
+        assert_eq!(rack.blanks(), 2);
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn generated_letters() {
+        let letters = Scheme::LettersFr.tiles().random_letters(&mut StdRng::seed_from_u64(1), 200);
+        assert_eq!(letters.len(), 200);
+        assert!(letters.matches('e').count() > letters.matches('z').count());
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = SchemeRegistry::new();
+        assert_eq!(registry.names().collect::<Vec<_>>(), vec!["letters-fr", "scrabble", "wwf"]);
+        let custom = TileScheme::from_words("mine", &test_words(&["aaa", "ab", "ac"]), 10, 0);
+        assert_eq!((custom.counts[0], custom.values[0], custom.values[1]), (6, 1, 3));
+        assert_eq!(registry.register(custom.clone()), None);
+        assert_eq!(registry.get("mine"), Some(&custom));
+        assert_eq!(registry.register(TileScheme { blanks: 1, ..custom }).map(|old| old.blanks), Some(0));
+        assert!(registry.get("zzz").is_none());
+    }
+}
@@ -0,0 +1,92 @@
+//! Scoring of words, and results ranked by score.
+//!
+//! A [`Scorer`] gives a word its points. [`Scrabble`] scores the tile values of English Scrabble,
+//! a [`TileScheme`] the values of its own game, and any `Fn(&str) -> u32` can be used as well, e.g.
+//! [`Puzzle::word_score`](crate::puzzle::Puzzle::word_score) wrapped in a closure.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::rack::word_score;
+use crate::scheme::TileScheme;
+use crate::{get_constructable_words, Dictionary, Word};
+
+/// Gives a word its points.
+pub trait Scorer {
+    fn score(&self, word: &str) -> u32;
+}
+
+/// The tile values of English Scrabble, ignoring any character that is not a letter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Scrabble;
+
+impl Scorer for Scrabble {
+    fn score(&self, word: &str) -> u32 {
+        word_score(word)
+    }
+}
+
+impl Scorer for TileScheme {
+    fn score(&self, word: &str) -> u32 {
+        self.word_score(word)
+    }
+}
+
+impl<F: Fn(&str) -> u32> Scorer for F {
+    fn score(&self, word: &str) -> u32 {
+        self(word)
+    }
+}
+
+/// Same as [`get_constructable_words`], with the score of every word, highest first and in the
+/// order of `words` among equal scores.
+pub fn get_scored_words<S: Scorer + ?Sized>(words: Vec<Word>, list: &HashMap<char, u8>, scorer: &S) -> Vec<(String, u32)> {
+    rank(get_constructable_words(words, list), scorer)
+}
+
+/// Words of the dictionary that can be constructed from the list of letters, with their score,
+/// highest first and in dictionary order among equal scores.
+pub fn find_scored<'a, S: Scorer + ?Sized>(dictionary: &'a Dictionary, list: &str, scorer: &S) -> Vec<(&'a str, u32)> {
+    rank(dictionary.find(list), scorer)
+}
+
+fn rank<W: AsRef<str>, S: Scorer + ?Sized>(words: Vec<W>, scorer: &S) -> Vec<(W, u32)> {
+    let mut scored: Vec<(W, u32)> = words.into_iter().map(|word| {
+        let score = scorer.score(word.as_ref());
+        (word, score)
+    }).collect();
+    scored.sort_by_key(|&(_, score)| Reverse(score));
+    scored
+}
+
+#[cfg(test)]
+mod scoring_tests {
+    use crate::scheme::Scheme;
+    use crate::{get_letters_count, test_words, Dictionary};
+
+    use super::{find_scored, get_scored_words, Scorer, Scrabble};
+
+    #[test]
+    fn scrabble_values() {
+        assert_eq!(Scrabble.score("Quiz"), 22);
+        assert_eq!(Scrabble.score("co-op"), 8);
+        assert_eq!(Scheme::Wwf.tiles().score("quiz"), 23);
+        assert_eq!((|word: &str| word.len() as u32).score("cat"), 3);
+    }
+
+    #[test]
+    fn scored_words_highest_first() {
+        let words = test_words(&["at", "cat", "a", "act", "tact", "dog"]);
+        let output = get_scored_words(words, &get_letters_count("tact"), &Scrabble);
+        let expected: Vec<(String, u32)> = [("tact", 6), ("cat", 5), ("act", 5), ("at", 2)]
+            .iter().map(|&(word, score)| (word.to_string(), score)).collect();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn scored_dictionary_search() {
+        let dictionary: Dictionary = test_words(&["at", "cat", "a", "act"]).into();
+        let scorer = |word: &str| if word.starts_with('a') { 10 } else { 0 };
+        assert_eq!(find_scored(&dictionary, "tca", &scorer), vec![("at", 10), ("act", 10), ("cat", 0)]);
+    }
+}
@@ -0,0 +1,147 @@
+//! Searches with constraints on the words found, built step by step.
+//!
+//! A [`Search`] holds a list of letters along with bounds on the length of the words, letters
+//! they must contain and a prefix or suffix they must have, e.g.
+//! `Search::new("aeqrstu").min_len(4).must_contain('q').starts_with("re")`. The constraints are
+//! checked while the words are scanned, together with the letters, instead of filtering the
+//! results of a search afterwards. Prefixes, suffixes and required letters ignore case and any
+//! character of a word that is not an ASCII letter.
+
+use crate::alphabet::{Alphabet, English};
+use crate::parallel::*;
+use crate::{can_be_constructed, lowercase_letters, word_len, Dictionary, LetterCounts, Word, DEFAULT_MIN_WORD_LEN};
+
+/// A list of letters and the constraints on the words to find for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Search {
+    list: LetterCounts,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    required: LetterCounts,
+    prefix: String,
+    suffix: String,
+}
+
+impl Search {
+    /// A search of the words that can be constructed from `letters`, ignoring case and any
+    /// character that is not an English letter, without other constraints.
+    pub fn new(letters: &str) -> Search {
+        Search {
+            list: LetterCounts::from_word::<English>(letters),
+            min_len: None,
+            max_len: None,
+            required: LetterCounts::new(),
+            prefix: String::new(),
+            suffix: String::new(),
+        }
+    }
+
+    /// Keeps the words of at least `len` letters. Without it, searches skip the words shorter
+    /// than [`DEFAULT_MIN_WORD_LEN`].
+    pub fn min_len(mut self, len: usize) -> Self {
+        self.min_len = Some(len);
+        self
+    }
+
+    /// Keeps the words of at most `len` letters.
+    pub fn max_len(mut self, len: usize) -> Self {
+        self.max_len = Some(len);
+        self
+    }
+
+    /// Keeps the words containing the letter. Requiring a letter several times keeps the words
+    /// containing it at least that many times. Characters that are not English letters are
+    /// ignored.
+    pub fn must_contain(mut self, letter: char) -> Self {
+        if let Some(index) = English::index(letter) {
+            self.required.increment(index);
+        }
+        self
+    }
+
+    /// Keeps the words starting with the letters of `prefix`.
+    pub fn starts_with(mut self, prefix: &str) -> Self {
+        self.prefix = lowercase_letters(prefix).collect();
+        self
+    }
+
+    /// Keeps the words ending with the letters of `suffix`.
+    pub fn ends_with(mut self, suffix: &str) -> Self {
+        self.suffix = lowercase_letters(suffix).collect();
+        self
+    }
+
+    /// Words of `dictionary` found by the search, in dictionary order. The length bounds and the
+    /// first letter of the prefix select the words checked with the dictionary's indexes.
+    pub fn find<'a>(&self, dictionary: &'a Dictionary) -> Vec<&'a str> {
+        let mut view = match (self.min_len, self.max_len) {
+            (Some(min_len), Some(max_len)) => dictionary.view().lengths(min_len..=max_len),
+            (Some(min_len), None) => dictionary.view().lengths(min_len..),
+            (None, Some(max_len)) => dictionary.view().lengths(..=max_len),
+            (None, None) => dictionary.view(),
+        };
+        if let Some(letter) = self.prefix.chars().next() {
+            view = view.starting_with(letter);
+        }
+        let search = self.clone();
+        view.filter(move |word| search.accepts(word)).find_counts(&self.list)
+    }
+
+    /// Words of `words` found by the search, in order, as [`get_constructable_words`](crate::get_constructable_words)
+    /// would find them without the constraints.
+    pub fn find_in(&self, words: &[Word]) -> Vec<String> {
+        let min_len = self.min_len.unwrap_or(DEFAULT_MIN_WORD_LEN);
+        let max_len = self.max_len.unwrap_or(usize::MAX);
+        words.par_iter()
+            .filter(|word| (min_len..=max_len).contains(&word_len(&word.letters)))
+            .filter(|word| self.accepts(word) && can_be_constructed(&LetterCounts::from(&word.letters), &self.list))
+            .map(|word| word.value.clone())
+            .collect()
+    }
+
+    /// Returns `true` if the word has the required letters, the prefix and the suffix.
+    fn accepts(&self, word: &Word) -> bool {
+        if !self.required.is_empty() && !can_be_constructed(&self.required, &LetterCounts::from(&word.letters)) { return false; }
+        let (mut front, mut back) = (lowercase_letters(&word.value), lowercase_letters(&word.value));
+        self.prefix.chars().all(|letter| front.next() == Some(letter))
+            && self.suffix.chars().rev().all(|letter| back.next_back() == Some(letter))
+    }
+}
+
+#[cfg(test)]
+mod search_tests {
+    use crate::{get_constructable_words, get_letters_count, test_words, Dictionary, Word};
+
+    use super::Search;
+
+    fn words() -> Vec<Word> {
+        test_words(&["quest", "request", "return", "Re-use", "rue", "true", "sure", "at", "queue"])
+    }
+
+    #[test]
+    fn constraints_are_combined() {
+        let dictionary: Dictionary = words().into();
+        let letters = "qrtuesenu";
+        assert_eq!(Search::new(letters).find(&dictionary), vec!["quest", "request", "Re-use", "rue", "true", "sure", "queue"]);
+        assert_eq!(Search::new(letters).min_len(4).must_contain('q').find(&dictionary), vec!["quest", "request", "queue"]);
+        assert_eq!(Search::new(letters).starts_with("RE").find(&dictionary), vec!["request", "Re-use"]);
+        assert_eq!(Search::new(letters).ends_with("ue").max_len(4).find(&dictionary), vec!["rue", "true"]);
+        assert_eq!(Search::new(letters).starts_with("r").ends_with("ue").find(&dictionary), vec!["rue"]);
+        assert_eq!(Search::new(letters).must_contain('u').must_contain('u').find(&dictionary), vec!["queue"]);
+        assert_eq!(Search::new("ta").min_len(1).find(&dictionary), vec!["at"]);
+    }
+
+    #[test]
+    fn prefix_and_suffix_can_overlap() {
+        let dictionary: Dictionary = words().into();
+        assert_eq!(Search::new("rue").starts_with("ru").ends_with("ue").find(&dictionary), vec!["rue"]);
+        assert!(Search::new("rue").starts_with("rues").find(&dictionary).is_empty());
+    }
+
+    #[test]
+    fn word_lists() {
+        let search = Search::new("qrtuesenu").min_len(4).must_contain('q');
+        assert_eq!(search.find_in(&words()), vec!["quest", "request", "queue"]);
+        assert_eq!(Search::new("qrtuesenu").find_in(&words()), get_constructable_words(words(), &get_letters_count("qrtuesenu")));
+    }
+}
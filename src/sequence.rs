@@ -0,0 +1,209 @@
+//! Order-sensitive search modes, where words must appear in the input string with their letters
+//! in order, rather than be constructed from a multiset of letters.
+//!
+//! The input is reduced to its lowercase ASCII letters first, so spaces and punctuation do not
+//! break a match. Both modes build an automaton over the input once, after which checking a word
+//! takes time proportional to the length of the word.
+
+use crate::parallel::*;
+use crate::Word;
+
+/// How a word must appear in the input string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderMode {
+    /// The letters of the word appear in order, not necessarily next to each other.
+    Subsequence,
+    /// The letters of the word appear in order and next to each other.
+    Substring,
+}
+
+/// Automaton recognizing every substring of a text.
+#[derive(Clone, Debug)]
+pub struct SuffixAutomaton {
+    states: Vec<State>,
+}
+
+#[derive(Clone, Debug)]
+struct State {
+    len: usize,
+    link: Option<usize>,
+    /// Transitions on letters a to z. State 0 is the initial state, which is never a target, so
+    /// 0 marks a missing transition.
+    next: [usize; 26],
+}
+
+/// Automaton recognizing every subsequence of a text: for each position, the position following
+/// the next occurrence of each letter.
+#[derive(Clone, Debug)]
+pub struct SubsequenceAutomaton {
+    next: Vec<[usize; 26]>,
+}
+
+impl SuffixAutomaton {
+    /// Builds the automaton of the letters of `text`.
+    pub fn new(text: &str) -> Self {
+        let mut states = vec![State { len: 0, link: None, next: [0; 26] }];
+        let mut last = 0;
+        for letter in letter_indexes(text) {
+            let current = states.len();
+            states.push(State { len: states[last].len + 1, link: None, next: [0; 26] });
+            let mut p = Some(last);
+            while let Some(state) = p {
+                if states[state].next[letter] != 0 { break; }
+                states[state].next[letter] = current;
+                p = states[state].link;
+            }
+            match p {
+                None => states[current].link = Some(0),
+                Some(state) => {
+                    let q = states[state].next[letter];
+                    if states[state].len + 1 == states[q].len {
+                        states[current].link = Some(q);
+                    } else {
+                        let clone = states.len();
+                        states.push(State { len: states[state].len + 1, ..states[q].clone() });
+                        let mut p = Some(state);
+                        while let Some(state) = p {
+                            if states[state].next[letter] != q { break; }
+                            states[state].next[letter] = clone;
+                            p = states[state].link;
+                        }
+                        states[q].link = Some(clone);
+                        states[current].link = Some(clone);
+                    }
+                }
+            }
+            last = current;
+        }
+        SuffixAutomaton { states }
+    }
+
+    /// Returns `true` if the letters of `word` appear next to each other in the text.
+    pub fn contains(&self, word: &str) -> bool {
+        let mut state = 0;
+        for letter in letter_indexes(word) {
+            state = self.states[state].next[letter];
+            if state == 0 { return false; }
+        }
+        true
+    }
+}
+
+impl SubsequenceAutomaton {
+    /// Builds the automaton of the letters of `text`.
+    pub fn new(text: &str) -> Self {
+        let letters: Vec<usize> = letter_indexes(text).collect();
+        let mut next = vec![[usize::MAX; 26]; letters.len() + 1];
+        for (position, &letter) in letters.iter().enumerate().rev() {
+            next[position] = next[position + 1];
+            next[position][letter] = position + 1;
+        }
+        SubsequenceAutomaton { next }
+    }
+
+    /// Returns `true` if the letters of `word` appear in order in the text.
+    pub fn contains(&self, word: &str) -> bool {
+        let mut position = 0;
+        for letter in letter_indexes(word) {
+            position = self.next[position][letter];
+            if position == usize::MAX { return false; }
+        }
+        true
+    }
+}
+
+/// Retrieve the words appearing in `text` according to `mode`. Words without letters never match.
+pub fn find_ordered_words(words: &[Word], text: &str, mode: OrderMode) -> Vec<String> {
+    let contains: Box<dyn Fn(&str) -> bool + Sync> = match mode {
+        OrderMode::Subsequence => {
+            let automaton = SubsequenceAutomaton::new(text);
+            Box::new(move |word| automaton.contains(word))
+        }
+        OrderMode::Substring => {
+            let automaton = SuffixAutomaton::new(text);
+            Box::new(move |word| automaton.contains(word))
+        }
+    };
+    words.par_iter()
+        .filter(|word| !word.letters.is_empty() && contains(&word.value))
+        .map(|word| word.value.clone())
+        .collect()
+}
+
+/// Indexes (0 for a, 25 for z) of the ASCII letters of a string, ignoring everything else.
+pub(crate) fn letter_indexes(text: &str) -> impl Iterator<Item=usize> + '_ {
+    text.chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| (c.to_ascii_lowercase() as u8 - b'a') as usize)
+}
+
+#[cfg(test)]
+mod suffix_automaton_tests {
+    use super::SuffixAutomaton;
+
+    #[test]
+    fn every_substring_is_recognized() {
+        let text = "abcbcab";
+        let automaton = SuffixAutomaton::new(text);
+        for start in 0..text.len() {
+            for end in start..=text.len() {
+                assert!(automaton.contains(&text[start..end]));
+            }
+        }
+    }
+
+    #[test]
+    fn non_substrings_are_rejected() {
+        let automaton = SuffixAutomaton::new("abcbcab");
+        assert!(!automaton.contains("ac"));
+        assert!(!automaton.contains("bb"));
+        assert!(!automaton.contains("abcbcabc"));
+    }
+
+    #[test]
+    fn ignores_case_and_punctuation() {
+        let automaton = SuffixAutomaton::new("Cele-bration!");
+        assert!(automaton.contains("BRAT"));
+        assert!(automaton.contains("lebr"));
+    }
+}
+
+#[cfg(test)]
+mod subsequence_automaton_tests {
+    use super::SubsequenceAutomaton;
+
+    #[test]
+    fn subsequences_are_recognized() {
+        let automaton = SubsequenceAutomaton::new("celebration");
+        assert!(automaton.contains("cat"));
+        assert!(automaton.contains("lion"));
+        assert!(automaton.contains(""));
+    }
+
+    #[test]
+    fn letters_out_of_order_are_rejected() {
+        let automaton = SubsequenceAutomaton::new("celebration");
+        assert!(!automaton.contains("tac"));
+        assert!(!automaton.contains("cc"));
+    }
+}
+
+#[cfg(test)]
+mod find_ordered_words_tests {
+    use crate::test_words;
+
+    use super::{find_ordered_words, OrderMode};
+
+    #[test]
+    fn substring() {
+        let words = test_words(&["brat", "ratio", "cat", "bet", ""]);
+        assert_eq!(find_ordered_words(&words, "celebration", OrderMode::Substring), vec!["brat", "ratio"]);
+    }
+
+    #[test]
+    fn subsequence() {
+        let words = test_words(&["brat", "ratio", "cat", "bet", "tab", ""]);
+        let output = find_ordered_words(&words, "celebration", OrderMode::Subsequence);
+        assert_eq!(output, vec!["brat", "ratio", "cat"]);
+    }
+}
@@ -0,0 +1,214 @@
+//! Solitaire play: a rack, the words it can make, and words played from it one after another.
+//!
+//! A [`Session`] searches the dictionary once, for the words its rack can make. Playing a word
+//! takes its tiles off the rack, after which the rack can only make some of the words it could
+//! make before, so the results are updated by checking them again rather than by searching the
+//! dictionary. A play can be undone, and [`Session::diff_from_previous`] tells which words the
+//! last play or undo removed or added, so that a front end can update the list it shows.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::dictionary::DictionaryView;
+use crate::{Rack, Tile};
+
+/// A rack and the words of a view of a dictionary it can make.
+#[derive(Clone, Debug)]
+pub struct Session<'a> {
+    rack: Rack,
+    words: Vec<&'a str>,
+    played: Vec<(String, Vec<Tile>)>,
+    /// Words the rack could make before each play, to undo it.
+    history: Vec<Vec<&'a str>>,
+    last_change: Change<'a>,
+}
+
+/// The last change of the words of a session.
+#[derive(Clone, Debug)]
+enum Change<'a> {
+    Start,
+    Play,
+    /// An undo, and the words before it.
+    Undo(Vec<&'a str>),
+}
+
+/// Words the rack can make that a change of the rack added and removed, in dictionary order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WordsDiff<'a> {
+    pub added: Vec<&'a str>,
+    pub removed: Vec<&'a str>,
+}
+
+/// Error returned when a word cannot be played.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlayError {
+    /// The word is not one of the words of the dictionary the rack can make.
+    NotAWord(String),
+    /// The rack does not have the tiles for the word, and the letters it is missing.
+    MissingLetters { word: String, missing: HashMap<char, u8> },
+}
+
+impl<'a> Session<'a> {
+    /// Starts a session with the words of `view` that `rack` can make, blanks included.
+    pub fn new(view: &DictionaryView<'a>, rack: Rack) -> Self {
+        let words = view.find_rack(&rack);
+        Session { rack, words, played: vec![], history: vec![], last_change: Change::Start }
+    }
+
+    /// Tiles left on the rack.
+    pub fn rack(&self) -> &Rack {
+        &self.rack
+    }
+
+    /// Words the tiles left can make, in dictionary order.
+    pub fn words(&self) -> &[&'a str] {
+        &self.words
+    }
+
+    /// Words played so far, in order, with the tiles each took off the rack, blanks designated.
+    pub fn played(&self) -> &[(String, Vec<Tile>)] {
+        &self.played
+    }
+
+    /// Plays one of the words the rack can make, ignoring case: takes its tiles off the rack and
+    /// returns the words the tiles left can make. The session is unchanged on error.
+    pub fn play(&mut self, word: &str) -> Result<&[&'a str], PlayError> {
+        if !self.words.iter().any(|found| found.eq_ignore_ascii_case(word)) {
+            let missing = self.rack.missing(word);
+            if missing.is_empty() { return Err(PlayError::NotAWord(word.to_string())); }
+            return Err(PlayError::MissingLetters { word: word.to_string(), missing });
+        }
+        let tiles = self.rack.remove_word(word).expect("the rack can make every word found");
+        let rack = &self.rack;
+        self.history.push(self.words.clone());
+        self.words.retain(|found| rack.can_make(found));
+        self.played.push((word.to_string(), tiles));
+        self.last_change = Change::Play;
+        Ok(&self.words)
+    }
+
+    /// Takes back the last word played, putting its tiles back on the rack, blanks undesignated.
+    /// Returns the word, or `None` if no word is left to take back.
+    pub fn undo(&mut self) -> Option<String> {
+        let (word, tiles) = self.played.pop()?;
+        let words = self.history.pop().expect("the words before every play are kept");
+        self.rack.extend(tiles.into_iter().map(|tile| if tile.is_blank { Tile::blank() } else { tile }));
+        self.last_change = Change::Undo(std::mem::replace(&mut self.words, words));
+        Some(word)
+    }
+
+    /// Words the last play or undo removed or added, every word found being added when nothing
+    /// was played yet. A play only removes words and an undo only adds words, so the words before
+    /// and after are compared in a single pass.
+    pub fn diff_from_previous(&self) -> WordsDiff<'a> {
+        match &self.last_change {
+            Change::Start => WordsDiff { added: self.words.clone(), removed: vec![] },
+            Change::Play => {
+                let before = self.history.last().expect("a play keeps the words before it");
+                WordsDiff { added: vec![], removed: missing_from(before, &self.words) }
+            }
+            Change::Undo(before) => WordsDiff { added: missing_from(&self.words, before), removed: vec![] },
+        }
+    }
+}
+
+/// Words of `words` that are not in `kept`, a subsequence of them. Words are compared by address,
+/// so that a word listed twice by the dictionary is told apart from its copy.
+fn missing_from<'a>(words: &[&'a str], kept: &[&'a str]) -> Vec<&'a str> {
+    let mut kept = kept.iter().peekable();
+    words.iter()
+        .filter(|&&word| {
+            if !kept.peek().is_some_and(|&&next| std::ptr::eq(next, word)) { return true; }
+            kept.next();
+            false
+        })
+        .copied()
+        .collect()
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayError::NotAWord(word) => write!(f, "{:?} is not a word that can be played", word),
+            PlayError::MissingLetters { word, missing } => {
+                let mut letters: Vec<String> = missing.iter().map(|(letter, count)| letter.to_string().repeat(*count as usize)).collect();
+                letters.sort();
+                write!(f, "{:?} needs letters the rack does not have: {}", word, letters.concat())
+            }
+        }
+    }
+}
+
+impl Error for PlayError {}
+
+#[cfg(test)]
+mod session_tests {
+    use std::collections::HashMap;
+
+    use crate::{test_words, Dictionary};
+
+    use super::{PlayError, Session, WordsDiff};
+
+    fn dictionary() -> Dictionary {
+        test_words(&["cat", "act", "at", "tea", "eat", "ate", "ta", "tact"]).into()
+    }
+
+    #[test]
+    fn play_updates_the_results() {
+        let dictionary = dictionary();
+        let mut session = Session::new(&dictionary.view(), "cattea".parse().unwrap());
+        assert_eq!(session.words(), ["cat", "act", "at", "tea", "eat", "ate", "ta", "tact"]);
+        assert_eq!(session.play("TEA").unwrap(), ["cat", "act", "at", "ta"]);
+        assert_eq!(session.rack().to_string(), "cta");
+        assert_eq!(session.play("act").unwrap(), [] as [&str; 0]);
+        assert!(session.rack().is_empty());
+        let played: Vec<&str> = session.played().iter().map(|(word, _)| word.as_str()).collect();
+        assert_eq!(played, ["TEA", "act"]);
+    }
+
+    #[test]
+    fn blanks() {
+        let dictionary = dictionary();
+        let mut session = Session::new(&dictionary.view(), "tata?".parse().unwrap());
+        assert_eq!(session.play("tea").unwrap(), ["at", "ta"]);
+        assert_eq!(session.played()[0].1.iter().map(|tile| tile.is_blank).collect::<Vec<_>>(), [false, true, false]);
+    }
+
+    #[test]
+    fn diffs() {
+        let dictionary = dictionary();
+        let mut session = Session::new(&dictionary.view(), "cattea".parse().unwrap());
+        assert_eq!(session.diff_from_previous().added.len(), 8);
+        session.play("tea").unwrap();
+        assert_eq!(session.diff_from_previous(), WordsDiff { added: vec![], removed: vec!["tea", "eat", "ate", "tact"] });
+        session.play("at").unwrap();
+        assert_eq!(session.diff_from_previous().removed, ["cat", "act", "at", "ta"]);
+        assert_eq!(session.undo().as_deref(), Some("at"));
+        assert_eq!(session.diff_from_previous(), WordsDiff { added: vec!["cat", "act", "at", "ta"], removed: vec![] });
+        assert_eq!(session.undo().as_deref(), Some("tea"));
+        assert_eq!(session.words().len(), 8);
+        assert_eq!((session.rack().len(), session.undo()), (6, None));
+    }
+
+    #[test]
+    fn undo_undesignates_blanks() {
+        let dictionary = dictionary();
+        let mut session = Session::new(&dictionary.view(), "tata?".parse().unwrap());
+        session.play("tea").unwrap();
+        session.undo();
+        assert_eq!(session.rack().blanks(), 1);
+        assert_eq!(session.play("tea").unwrap(), ["at", "ta"]);
+    }
+
+    #[test]
+    fn invalid_plays() {
+        let dictionary = dictionary();
+        let mut session = Session::new(&dictionary.view(), "cat".parse().unwrap());
+        assert_eq!(session.play("tac"), Err(PlayError::NotAWord("tac".to_string())));
+        let error = session.play("tact").unwrap_err();
+        assert_eq!(error, PlayError::MissingLetters { word: "tact".to_string(), missing: HashMap::from([('t', 1)]) });
+        assert_eq!(error.to_string(), r#""tact" needs letters the rack does not have: t"#);
+        assert_eq!((session.rack().len(), session.words().len(), session.played().len()), (3, 4, 0));
+    }
+}
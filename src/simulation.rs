@@ -0,0 +1,212 @@
+//! Monte Carlo statistics on racks drawn from a tile bag.
+//!
+//! Every trial draws a rack from a fresh copy of the bag, finds the words it can make in the
+//! dictionary and scores them. Trials run in parallel, each with its own generator seeded from
+//! the configuration's seed, so a simulation gives the same results on every run.
+//!
+//! Trials also give a value to leaves, the tiles kept on a rack: [`leave_value`] completes a leave
+//! with tiles drawn from the bag, and [`advise_exchange`] compares every leave of a rack to
+//! recommend which tiles to exchange. Every leave is completed with the same draws, so that
+//! differences between leaves are not just noise.
+
+use std::collections::{BTreeMap, HashSet};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::game::TileBag;
+use crate::parallel::*;
+use crate::{letter_count, Dictionary, Rack};
+
+/// What to simulate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulationConfig {
+    /// Number of racks drawn for each rack size.
+    pub trials: usize,
+    pub rack_sizes: Vec<usize>,
+    pub seed: u64,
+}
+
+/// Statistics of the racks of one size.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RackStats {
+    pub rack_size: usize,
+    pub trials: usize,
+    /// Average score of the best word of a rack, counting 0 for racks without words.
+    pub average_best_score: f64,
+    /// Number of racks without any word.
+    pub dead_racks: usize,
+    /// For each number of bingos (words using every tile of the rack), the number of racks having
+    /// that many.
+    pub bingos: BTreeMap<usize, usize>,
+}
+
+/// An exchange and the expected quality of the rack it leads to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExchangeAdvice {
+    /// Tiles to put back in the bag.
+    pub exchange: Rack,
+    /// Tiles kept on the rack.
+    pub leave: Rack,
+    /// Average score of the best word of the rack after drawing.
+    pub expected_score: f64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        SimulationConfig { trials: 1000, rack_sizes: vec![7], seed: 0 }
+    }
+}
+
+impl RackStats {
+    /// Fraction of the racks with at least one bingo.
+    pub fn bingo_rate(&self) -> f64 {
+        if self.trials == 0 { return 0.0; }
+        let racks: usize = self.bingos.iter().filter(|(&count, _)| count > 0).map(|(_, &racks)| racks).sum();
+        racks as f64 / self.trials as f64
+    }
+}
+
+/// Runs the simulation, returning one [`RackStats`] per rack size, in the configuration's order.
+pub fn simulate(dictionary: &Dictionary, bag: &TileBag, scorer: &(dyn Fn(&str) -> u32 + Sync), config: &SimulationConfig) -> Vec<RackStats> {
+    config.rack_sizes.iter()
+        .enumerate()
+        .map(|(size_index, &rack_size)| {
+            let trials: Vec<(u32, usize, bool)> = (0..config.trials).into_par_iter()
+                .map(|trial| {
+                    let seed = config.seed.wrapping_add((size_index * config.trials + trial) as u64);
+                    let mut rng = StdRng::seed_from_u64(seed);
+                    let rack = bag.clone().draw(&mut rng, rack_size);
+                    let words = dictionary.find_rack(&rack);
+                    let best = words.iter().map(|word| scorer(word)).max().unwrap_or(0);
+                    let bingos = words.iter().filter(|word| letter_count(word) == rack.len()).count();
+                    (best, bingos, words.is_empty())
+                })
+                .collect();
+            let mut bingos = BTreeMap::new();
+            for &(_, count, _) in &trials {
+                *bingos.entry(count).or_insert(0) += 1;
+            }
+            RackStats {
+                rack_size,
+                trials: config.trials,
+                average_best_score: if trials.is_empty() { 0.0 } else {
+                    trials.iter().map(|&(best, _, _)| best as f64).sum::<f64>() / trials.len() as f64
+                },
+                dead_racks: trials.iter().filter(|&&(_, _, dead)| dead).count(),
+                bingos,
+            }
+        })
+        .collect()
+}
+
+/// Average score of the best word of a rack of `rack_size` tiles made of `leave` and tiles drawn
+/// from `bag`, over `config.trials` draws. The configuration's rack sizes are not used.
+pub fn leave_value(dictionary: &Dictionary, bag: &TileBag, leave: &Rack, rack_size: usize, scorer: &(dyn Fn(&str) -> u32 + Sync), config: &SimulationConfig) -> f64 {
+    if config.trials == 0 { return 0.0; }
+    let total: u32 = (0..config.trials).into_par_iter()
+        .map(|trial| {
+            let mut rng = StdRng::seed_from_u64(config.seed.wrapping_add(trial as u64));
+            let mut rack = leave.clone();
+            rack.extend(bag.clone().draw(&mut rng, rack_size.saturating_sub(leave.len())));
+            dictionary.find_rack(&rack).iter().map(|word| scorer(word)).max().unwrap_or(0)
+        })
+        .sum();
+    total as f64 / config.trials as f64
+}
+
+/// Every exchange of at least one tile of `rack`, the best first: the highest expected score of
+/// the next rack, then the fewest tiles exchanged. `bag` holds the tiles that can be drawn, the
+/// exchanged tiles only going back to the bag after drawing. Racks with the same tiles are only
+/// listed once, and a rack of `n` tiles has up to `2^n - 1` exchanges, so this is meant for
+/// racks of a game, not long lists of letters.
+pub fn advise_exchange(dictionary: &Dictionary, rack: &Rack, bag: &TileBag, scorer: &(dyn Fn(&str) -> u32 + Sync), config: &SimulationConfig) -> Vec<ExchangeAdvice> {
+    let tiles = rack.tiles();
+    let mut seen = HashSet::new();
+    let mut advice: Vec<ExchangeAdvice> = (0..(1u64 << tiles.len()) - 1)
+        .filter_map(|kept| {
+            let (mut leave, mut exchange) = (Rack::new(), Rack::new());
+            for (index, &tile) in tiles.iter().enumerate() {
+                if kept & (1 << index) != 0 { leave.push(tile) } else { exchange.push(tile) }
+            }
+            seen.insert((leave.key(), leave.blanks())).then_some((leave, exchange))
+        })
+        .map(|(leave, exchange)| {
+            let expected_score = leave_value(dictionary, bag, &leave, tiles.len(), scorer, config);
+            ExchangeAdvice { exchange, leave, expected_score }
+        })
+        .collect();
+    advice.sort_by(|a, b| b.expected_score.total_cmp(&a.expected_score)
+        .then(a.exchange.len().cmp(&b.exchange.len()))
+        .then(a.exchange.to_string().cmp(&b.exchange.to_string())));
+    advice
+}
+
+#[cfg(test)]
+mod simulation_tests {
+    use std::collections::BTreeMap;
+
+    use crate::game::TileBag;
+    use crate::{test_words, Dictionary};
+
+    use super::{advise_exchange, leave_value, simulate, SimulationConfig};
+
+    fn length(word: &str) -> u32 {
+        word.len() as u32
+    }
+
+    #[test]
+    fn statistics_per_rack_size() {
+        let dictionary: Dictionary = test_words(&["a", "at", "ta", "att"]).into();
+        let bag = TileBag::from_letters("attt");
+        let config = SimulationConfig { trials: 200, rack_sizes: vec![3, 4, 2], seed: 42 };
+        let stats = simulate(&dictionary, &bag, &length, &config);
+        assert_eq!(stats.iter().map(|stats| stats.rack_size).collect::<Vec<_>>(), vec![3, 4, 2]);
+
+        let full = &stats[1];
+        assert_eq!(full.average_best_score, 3.0);
+        assert_eq!(full.dead_racks, 0);
+        assert_eq!(full.bingos, BTreeMap::from([(0, 200)]));
+
+        let triples = &stats[0];
+        assert_eq!(triples.dead_racks + triples.bingos.get(&1).copied().unwrap_or(0), 200);
+        assert!(triples.dead_racks < triples.bingos[&1]);
+        assert!((triples.bingo_rate() - triples.bingos[&1] as f64 / 200.0).abs() < 1e-9);
+
+        let pairs = &stats[2];
+        assert!(pairs.bingos.get(&2).copied().unwrap_or(0) > 0);
+        assert_eq!(pairs.dead_racks, pairs.bingos[&0]);
+    }
+
+    #[test]
+    fn leave_values() {
+        let dictionary: Dictionary = test_words(&["a", "at", "tat"]).into();
+        let bag = TileBag::from_letters("aaaaaaaa");
+        let config = SimulationConfig { trials: 10, ..SimulationConfig::default() };
+        assert_eq!(leave_value(&dictionary, &bag, &"t".parse().unwrap(), 3, &length, &config), 2.0);
+        assert_eq!(leave_value(&dictionary, &bag, &"q".parse().unwrap(), 3, &length, &config), 0.0);
+    }
+
+    #[test]
+    fn exchange_advice() {
+        let dictionary: Dictionary = test_words(&["a", "at", "tat"]).into();
+        let bag = TileBag::from_letters("aaaaaaaa");
+        let config = SimulationConfig { trials: 10, ..SimulationConfig::default() };
+        let advice = advise_exchange(&dictionary, &"tqz".parse().unwrap(), &bag, &length, &config);
+        let exchanges: Vec<(String, f64)> = advice.iter().map(|advice| (advice.exchange.to_string(), advice.expected_score)).collect();
+        assert_eq!(exchanges[..3], [("q".to_string(), 2.0), ("z".to_string(), 2.0), ("qz".to_string(), 2.0)]);
+        assert_eq!(advice.len(), 7);
+        assert_eq!(advice[2].leave.to_string(), "t");
+        assert_eq!(advice.last().unwrap().expected_score, 0.0);
+        assert_eq!(advise_exchange(&dictionary, &"tt".parse().unwrap(), &bag, &length, &config).len(), 2);
+    }
+
+    #[test]
+    fn simulations_are_reproducible() {
+        let dictionary: Dictionary = test_words(&["cat", "act", "at", "dog"]).into();
+        let config = SimulationConfig { trials: 50, ..SimulationConfig::default() };
+        let first = simulate(&dictionary, &TileBag::english(), &length, &config);
+        let second = simulate(&dictionary, &TileBag::english(), &length, &config);
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,196 @@
+//! A soak test of a loaded dictionary: generated racks searched at a steady rate for a long time,
+//! as a server would search them, to check that latencies and memory stay flat.
+//!
+//! [`run_soak`] schedules `qps` searches per second for the whole duration, spread over worker
+//! threads sharing the dictionary, each with its own [`QueryCache`]. Racks are drawn at random
+//! from a pool of generated racks, so that the caches get hits, and the dictionary can be
+//! reloaded on a schedule, which gives it a new generation and drops every cached result, as a
+//! hot reload of the word list would. A search's latency is measured from the time it was
+//! scheduled, not from the time it started, so that a harness falling behind shows in the
+//! percentiles instead of lowering the rate.
+
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+use crate::cache::{QueryCache, QueryOptions};
+use crate::generator::{LetterGenerator, Weighting};
+use crate::Dictionary;
+
+/// Load and shape of a soak test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoakConfig {
+    /// Searches scheduled per second.
+    pub qps: u32,
+    pub duration: Duration,
+    /// Worker threads sharing the dictionary.
+    pub threads: usize,
+    /// Number of tiles of the generated racks.
+    pub rack_len: usize,
+    /// Number of racks generated, searches drawing their rack from them at random.
+    pub rack_pool: usize,
+    /// Capacity of the cache of every worker, 0 to search every rack.
+    pub cache_capacity: usize,
+    /// Time between reloads of the dictionary, `None` to never reload it.
+    pub reload_every: Option<Duration>,
+    /// Seed of the racks and of the draws, so that runs can be compared.
+    pub seed: u64,
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        SoakConfig {
+            qps: 100,
+            duration: Duration::from_secs(10),
+            threads: 1,
+            rack_len: 7,
+            rack_pool: 1000,
+            cache_capacity: 256,
+            reload_every: None,
+            seed: 0,
+        }
+    }
+}
+
+/// Results of a soak test.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SoakReport {
+    /// Latency of every search, sorted.
+    pub latencies: Vec<Duration>,
+    /// Searches answered from a cache.
+    pub cache_hits: u64,
+    pub reloads: u64,
+    /// Time from the start of the test to the end of the last search.
+    pub elapsed: Duration,
+    /// Resident memory of the process at the start, at the end and at its highest, in bytes, on
+    /// systems where it can be read.
+    pub memory_start: Option<u64>,
+    pub memory_end: Option<u64>,
+    pub memory_peak: Option<u64>,
+}
+
+impl SoakReport {
+    /// Number of searches run.
+    pub fn queries(&self) -> usize {
+        self.latencies.len()
+    }
+
+    /// Searches run per second, over the whole test.
+    pub fn achieved_qps(&self) -> f64 {
+        if self.elapsed.is_zero() { return 0.0; }
+        self.queries() as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Latency that `percentile` percent of the searches did not exceed, zero without searches.
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.latencies.is_empty() { return Duration::ZERO; }
+        let rank = (percentile / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        self.latencies[rank.clamp(1, self.latencies.len()) - 1]
+    }
+
+    /// Growth of the resident memory from the start to the end, in bytes.
+    pub fn memory_growth(&self) -> Option<i64> {
+        Some(self.memory_end? as i64 - self.memory_start? as i64)
+    }
+}
+
+/// Runs a soak test against `dictionary`, returning once every scheduled search ran.
+pub fn run_soak(dictionary: Dictionary, config: &SoakConfig) -> SoakReport {
+    let mut generator = LetterGenerator::from_seed(config.seed).weighting(Weighting::TileBag).min_vowels(2);
+    let racks: Vec<String> = (0..config.rack_pool.max(1)).map(|_| generator.generate(config.rack_len)).collect();
+    let total = (config.qps as f64 * config.duration.as_secs_f64()) as u64;
+    let threads = config.threads.max(1) as u64;
+    let shared = RwLock::new(Arc::new(dictionary));
+    let mut report = SoakReport { memory_start: resident_memory(), ..SoakReport::default() };
+    report.memory_peak = report.memory_start;
+    let start = Instant::now();
+    let results: Vec<(Vec<Duration>, u64)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|worker| {
+                let (shared, racks) = (&shared, &racks);
+                scope.spawn(move || {
+                    let mut rng = StdRng::seed_from_u64(config.seed.wrapping_add(worker));
+                    let mut cache = QueryCache::new(config.cache_capacity);
+                    let options = QueryOptions::default();
+                    let mut latencies = vec![];
+                    for query in (worker..total).step_by(threads as usize) {
+                        let due = start + Duration::from_secs_f64(query as f64 / config.qps as f64);
+                        thread::sleep(due.saturating_duration_since(Instant::now()));
+                        let dictionary = Arc::clone(&shared.read().expect("no worker panics holding the lock"));
+                        cache.find(&dictionary, racks.choose(&mut rng).expect("the pool has racks"), &options);
+                        latencies.push(due.elapsed());
+                    }
+                    (latencies, cache.hits())
+                })
+            })
+            .collect();
+        let mut next_reload = config.reload_every.map(|every| start + every);
+        while !workers.iter().all(|worker| worker.is_finished()) {
+            thread::sleep(Duration::from_millis(10));
+            report.memory_peak = report.memory_peak.max(resident_memory());
+            if next_reload.is_some_and(|reload| Instant::now() >= reload && start.elapsed() < config.duration) {
+                let words = shared.read().expect("no worker panics holding the lock").words().to_vec();
+                *shared.write().expect("no worker panics holding the lock") = Arc::new(Dictionary::new(words));
+                report.reloads += 1;
+                next_reload = next_reload.zip(config.reload_every).map(|(reload, every)| reload + every);
+            }
+        }
+        workers.into_iter().map(|worker| worker.join().expect("soak workers do not panic")).collect()
+    });
+    report.elapsed = start.elapsed();
+    for (latencies, hits) in results {
+        report.latencies.extend(latencies);
+        report.cache_hits += hits;
+    }
+    report.latencies.sort_unstable();
+    report.memory_end = resident_memory();
+    report.memory_peak = report.memory_peak.max(report.memory_end);
+    report
+}
+
+/// Resident memory of the process in bytes, read from `/proc/self/status` where there is one.
+fn resident_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes: u64 = line.trim_start_matches("VmRSS:").trim().trim_end_matches("kB").trim().parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(test)]
+mod soak_tests {
+    use std::time::Duration;
+
+    use crate::{test_words, Dictionary};
+
+    use super::{run_soak, SoakConfig, SoakReport};
+
+    #[test]
+    fn every_scheduled_search_runs() {
+        let dictionary: Dictionary = test_words(&["cat", "act", "at", "tea", "eat", "rate", "tear"]).into();
+        let config = SoakConfig {
+            qps: 400,
+            duration: Duration::from_millis(150),
+            threads: 2,
+            rack_pool: 4,
+            reload_every: Some(Duration::from_millis(40)),
+            ..SoakConfig::default()
+        };
+        let report = run_soak(dictionary, &config);
+        assert_eq!(report.queries(), 60);
+        assert!(report.cache_hits > 0 && report.cache_hits < 60, "{}", report.cache_hits);
+        assert!(report.reloads >= 1, "{}", report.reloads);
+        assert!(report.elapsed >= Duration::from_millis(145));
+        assert!(report.percentile(50.0) <= report.percentile(99.0) && report.percentile(99.0) <= report.percentile(100.0));
+    }
+
+    #[test]
+    fn percentiles() {
+        let report = SoakReport { latencies: (1..=100).map(Duration::from_millis).collect(), ..SoakReport::default() };
+        assert_eq!((report.percentile(50.0), report.percentile(99.0), report.percentile(0.0)), (Duration::from_millis(50), Duration::from_millis(99), Duration::from_millis(1)));
+        assert_eq!(SoakReport::default().percentile(50.0), Duration::ZERO);
+        assert_eq!(SoakReport { memory_start: Some(10), memory_end: Some(4), ..SoakReport::default() }.memory_growth(), Some(-6));
+    }
+}
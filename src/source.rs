@@ -0,0 +1,133 @@
+//! Sources of tiles for a list of letters.
+//!
+//! A [`RackSource`] produces [`TileToken`]s, each carrying the recognized letter and how confident
+//! the recognizer was. Strings and byte streams are sources out of the box; an OCR crate reading
+//! tiles from a photo only has to implement the trait (or hand over a `Vec<TileToken>`).
+//! Tiles below a confidence threshold become wildcards in the resulting list of letters.
+
+use std::io::{Error, Read};
+
+use crate::limits::WILDCARDS;
+
+/// A single recognized tile.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TileToken {
+    /// The recognized letter, or one of the [`WILDCARDS`] for a blank tile.
+    pub letter: char,
+    /// Confidence of the recognition, from 0.0 to 1.0.
+    pub confidence: f32,
+}
+
+/// Anything that can produce the tiles of a rack.
+pub trait RackSource {
+    fn tiles(&mut self) -> Result<Vec<TileToken>, Error>;
+}
+
+/// Reads tiles from a byte stream of text, one tile per letter.
+#[derive(Debug)]
+pub struct ByteRackSource<R: Read> {
+    reader: R,
+}
+
+impl TileToken {
+    /// A tile recognized with full confidence.
+    pub fn certain(letter: char) -> Self {
+        TileToken { letter, confidence: 1.0 }
+    }
+
+    /// Returns `true` if the tile is blank or was recognized with less than `min_confidence`.
+    pub fn is_wildcard(&self, min_confidence: f32) -> bool {
+        WILDCARDS.contains(&self.letter) || self.confidence < min_confidence
+    }
+}
+
+impl<R: Read> ByteRackSource<R> {
+    pub fn new(reader: R) -> Self {
+        ByteRackSource { reader }
+    }
+}
+
+impl RackSource for &str {
+    fn tiles(&mut self) -> Result<Vec<TileToken>, Error> {
+        Ok(tokenize(self))
+    }
+}
+
+impl RackSource for String {
+    fn tiles(&mut self) -> Result<Vec<TileToken>, Error> {
+        Ok(tokenize(self))
+    }
+}
+
+impl RackSource for Vec<TileToken> {
+    fn tiles(&mut self) -> Result<Vec<TileToken>, Error> {
+        Ok(self.clone())
+    }
+}
+
+impl<R: Read> RackSource for ByteRackSource<R> {
+    /// Reads the stream to the end. Invalid UTF-8 is an error.
+    fn tiles(&mut self) -> Result<Vec<TileToken>, Error> {
+        let mut text = String::new();
+        self.reader.read_to_string(&mut text)?;
+        Ok(tokenize(&text))
+    }
+}
+
+/// Reads the tiles of a source and turns them into a list of letters, replacing the tiles
+/// recognized with less than `min_confidence` with the `?` wildcard.
+pub fn letters_from_source<S: RackSource>(source: &mut S, min_confidence: f32) -> Result<String, Error> {
+    Ok(source.tiles()?
+        .into_iter()
+        .map(|tile| if tile.is_wildcard(min_confidence) { '?' } else { tile.letter.to_ascii_lowercase() })
+        .collect())
+}
+
+fn tokenize(text: &str) -> Vec<TileToken> {
+    text.chars()
+        .filter(|c| c.is_ascii_alphabetic() || WILDCARDS.contains(c))
+        .map(TileToken::certain)
+        .collect()
+}
+
+#[cfg(test)]
+mod rack_source_tests {
+    use std::io::Cursor;
+
+    use super::{letters_from_source, ByteRackSource, RackSource, TileToken};
+
+    #[test]
+    fn string_source() {
+        let tiles = "a B?".tiles().unwrap();
+        assert_eq!(tiles, vec![TileToken::certain('a'), TileToken::certain('B'), TileToken::certain('?')]);
+    }
+
+    #[test]
+    fn byte_source() {
+        let mut source = ByteRackSource::new(Cursor::new(b"do g\n".to_vec()));
+        assert_eq!(letters_from_source(&mut source, 0.5).unwrap(), "dog");
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut source = ByteRackSource::new(Cursor::new(vec![0xff, 0xfe]));
+        assert!(source.tiles().is_err());
+    }
+
+    #[test]
+    fn low_confidence_tiles_become_wildcards() {
+        let mut tiles = vec![
+            TileToken::certain('C'),
+            TileToken { letter: 'a', confidence: 0.3 },
+            TileToken { letter: 't', confidence: 0.8 },
+        ];
+        assert_eq!(letters_from_source(&mut tiles, 0.5).unwrap(), "c?t");
+        assert_eq!(letters_from_source(&mut tiles, 0.9).unwrap(), "c??");
+    }
+
+    #[test]
+    fn blank_tiles_are_wildcards() {
+        assert!(TileToken::certain('*').is_wildcard(0.0));
+        assert!(!TileToken::certain('a').is_wildcard(1.0));
+    }
+}
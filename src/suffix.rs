@@ -0,0 +1,98 @@
+//! Reverse-suffix index: the words of a dictionary sorted by their reversed letters, so that all
+//! the words ending with a given suffix sit next to each other.
+
+use std::collections::BTreeMap;
+
+use crate::{lowercase_letters, Word};
+
+/// Words sorted by their lowercase ASCII letters read backwards.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SuffixIndex {
+    entries: Vec<(String, usize)>,
+}
+
+impl SuffixIndex {
+    /// Builds the index of a list of words. Words without letters are left out.
+    pub fn new(words: &[Word]) -> SuffixIndex {
+        let mut entries: Vec<(String, usize)> = words.iter()
+            .enumerate()
+            .map(|(index, word)| (lowercase_letters(&word.value).rev().collect::<String>(), index))
+            .filter(|(key, _)| !key.is_empty())
+            .collect();
+        entries.sort_unstable();
+        SuffixIndex { entries }
+    }
+
+    /// Indexes of the words ending with `suffix` (compared on letters only, ignoring case),
+    /// in no particular order. An empty suffix matches every word.
+    pub fn ending_with(&self, suffix: &str) -> impl Iterator<Item=usize> + '_ {
+        let key: String = lowercase_letters(suffix).rev().collect();
+        let start = self.entries.partition_point(|(other, _)| other.as_str() < key.as_str());
+        self.entries[start..].iter()
+            .take_while(move |(other, _)| other.starts_with(&key))
+            .map(|&(_, index)| index)
+    }
+}
+
+/// A word containing another one at its start or at its end, see
+/// [`Dictionary::extensions_of`](crate::Dictionary::extensions_of).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Extension<'a> {
+    pub word: &'a str,
+    /// Letters added in front of the other word, lowercased, empty for a back extension.
+    pub front: String,
+    /// Letters added after the other word, lowercased, empty for a front extension.
+    pub back: String,
+}
+
+/// Groups words by their last `len` letters (lowercased). Words shorter than `len` letters are
+/// grouped under all of their letters.
+pub fn group_by_suffix<'a>(words: &[&'a str], len: usize) -> BTreeMap<String, Vec<&'a str>> {
+    let mut groups: BTreeMap<String, Vec<&'a str>> = BTreeMap::new();
+    for &word in words {
+        let reversed: Vec<char> = lowercase_letters(word).rev().take(len).collect();
+        groups.entry(reversed.into_iter().rev().collect()).or_default().push(word);
+    }
+    groups
+}
+
+/// Number of final letters two words have in common, ignoring case.
+pub(crate) fn shared_suffix_len(a: &str, b: &str) -> usize {
+    lowercase_letters(a).rev().zip(lowercase_letters(b).rev()).take_while(|(a, b)| a == b).count()
+}
+
+#[cfg(test)]
+mod suffix_index_tests {
+    use crate::test_words;
+
+    use super::{group_by_suffix, shared_suffix_len, SuffixIndex};
+
+    #[test]
+    fn ending_with() {
+        let words = test_words(&["nation", "station", "cation", "ratio", "Elation", "on"]);
+        let index = SuffixIndex::new(&words);
+        let mut output: Vec<usize> = index.ending_with("ATION").collect();
+        output.sort_unstable();
+        assert_eq!(output, vec![0, 1, 2, 4]);
+        assert_eq!(index.ending_with("on").count(), 5);
+        assert_eq!(index.ending_with("").count(), 6);
+        assert_eq!(index.ending_with("xyz").count(), 0);
+    }
+
+    #[test]
+    fn grouping() {
+        let groups = group_by_suffix(&["nation", "ratio", "station", "patio", "go"], 3);
+        let expected: Vec<(&str, Vec<&str>)> = vec![
+            ("go", vec!["go"]),
+            ("ion", vec!["nation", "station"]),
+            ("tio", vec!["ratio", "patio"]),
+        ];
+        assert_eq!(groups.iter().map(|(k, v)| (k.as_str(), v.clone())).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn shared_suffix() {
+        assert_eq!(shared_suffix_len("nation", "Station"), 5);
+        assert_eq!(shared_suffix_len("cat", "dog"), 0);
+    }
+}
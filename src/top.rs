@@ -0,0 +1,113 @@
+//! The best few words a list of letters can make, without collecting every word it can make.
+//!
+//! A generous list of letters can make tens of thousands of words of a big dictionary, most of
+//! which a caller showing the ten best would throw away after sorting them. [`find_top_k`]
+//! keeps a heap of at most `k` words for every chunk of the dictionary searched in parallel,
+//! borrowing the words, and only copies the `k` best it is left with.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::parallel::*;
+use crate::rack::word_score;
+use crate::{can_be_constructed, word_len, LetterCounts, Word, DEFAULT_MIN_WORD_LEN};
+
+/// Words searched by each heap.
+const CHUNK_LEN: usize = 4096;
+
+/// Which words are the best ones, ties broken by the order of the words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Ranking {
+    /// Most letters first.
+    Length,
+    /// Highest English tile score first.
+    Score,
+    /// Alphabetical order of the words.
+    Alphabetical,
+}
+
+/// Ordering key of a word, the best words being the smallest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Key<'w> {
+    Highest(Reverse<usize>),
+    First(&'w str),
+}
+
+impl Ranking {
+    fn key<'w>(&self, word: &'w Word) -> Key<'w> {
+        match self {
+            Ranking::Length => Key::Highest(Reverse(word_len(&word.letters))),
+            Ranking::Score => Key::Highest(Reverse(word_score(&word.value) as usize)),
+            Ranking::Alphabetical => Key::First(&word.value),
+        }
+    }
+}
+
+/// The `k` best words of `words` by `ranking` that [`get_constructable_words`](crate::get_constructable_words)
+/// finds for `list`, best first, in the order of `words` among words ranked the same.
+pub fn find_top_k(words: &[Word], list: &HashMap<char, u8>, k: usize, ranking: Ranking) -> Vec<String> {
+    if k == 0 { return vec![]; }
+    let list = LetterCounts::from(list);
+    let heaps: Vec<BinaryHeap<(Key, usize)>> = words.par_chunks(CHUNK_LEN)
+        .enumerate()
+        .map(|(chunk, words)| {
+            let mut heap = BinaryHeap::with_capacity(k.min(CHUNK_LEN).saturating_add(1));
+            for (index, word) in words.iter().enumerate() {
+                if word_len(&word.letters) < DEFAULT_MIN_WORD_LEN || !can_be_constructed(&LetterCounts::from(&word.letters), &list) { continue; }
+                push_bounded(&mut heap, (ranking.key(word), chunk * CHUNK_LEN + index), k);
+            }
+            heap
+        })
+        .collect();
+    let mut best = BinaryHeap::with_capacity(k.min(words.len()).saturating_add(1));
+    for entry in heaps.into_iter().flatten() {
+        push_bounded(&mut best, entry, k);
+    }
+    best.into_sorted_vec().into_iter().map(|(_, index)| words[index].value.clone()).collect()
+}
+
+/// Pushes the entry, dropping the worst entry (the greatest) when the heap has more than `k`.
+fn push_bounded<T: Ord>(heap: &mut BinaryHeap<T>, entry: T, k: usize) {
+    if heap.len() == k && heap.peek().is_some_and(|worst| &entry >= worst) { return; }
+    heap.push(entry);
+    if heap.len() > k { heap.pop(); }
+}
+
+#[cfg(test)]
+mod top_k_tests {
+    use crate::rack::word_score;
+    use crate::{get_constructable_words, get_letters_count, test_words, Word};
+
+    use super::{find_top_k, Ranking};
+
+    fn words() -> Vec<Word> {
+        test_words(&["tea", "cat", "act", "tact", "zeta", "at", "a", "ace", "cafe", "face"])
+    }
+
+    #[test]
+    fn best_words_first() {
+        let list = get_letters_count("aacefttz");
+        assert_eq!(find_top_k(&words(), &list, 3, Ranking::Length), ["tact", "zeta", "cafe"]);
+        assert_eq!(find_top_k(&words(), &list, 2, Ranking::Score), ["zeta", "cafe"]);
+        assert_eq!(find_top_k(&words(), &list, 4, Ranking::Alphabetical), ["ace", "act", "at", "cafe"]);
+        assert!(find_top_k(&words(), &list, 0, Ranking::Length).is_empty());
+    }
+
+    #[test]
+    fn same_words_as_a_full_sort() {
+        let words: Vec<Word> = (0..10_000).map(|index| Word::new(["cat", "act", "tact", "zeta", "at", "face"][index % 6])).collect();
+        let list = get_letters_count("aacefttz");
+        let mut all = get_constructable_words(words.clone(), &list);
+        all.sort_by_key(|word| std::cmp::Reverse(word_score(word)));
+        assert_eq!(find_top_k(&words, &list, 5000, Ranking::Score), all[..5000]);
+        assert_eq!(find_top_k(&words, &list, 100_000, Ranking::Score), all);
+        assert_eq!(find_top_k(&words, &list, usize::MAX, Ranking::Score), all);
+    }
+
+    #[test]
+    fn unbounded_k() {
+        let list = get_letters_count("aacefttz");
+        assert_eq!(find_top_k(&words(), &list, usize::MAX, Ranking::Length), ["tact", "zeta", "cafe", "face", "tea", "cat", "act", "ace", "at"]);
+        assert!(find_top_k(&[], &list, usize::MAX, Ranking::Length).is_empty());
+    }
+}
@@ -0,0 +1,253 @@
+//! A prefix tree over the letters of the words of a dictionary.
+//!
+//! Words are keyed by their lowercase ASCII letters, so "Brat" and "brat" end on the same node.
+//! Every node keeps the indexes of the words ending there.
+//!
+//! A [`TrieDictionary`] searches the trie itself instead of checking the words one by one: the
+//! search only descends into the children of a node whose letter is still left in the list, so
+//! every word sharing a prefix the list cannot make is skipped at once. It finds the words a
+//! [`Dictionary`](crate::Dictionary) finds, in the same order, and the benchmarks compare both.
+
+use crate::alphabet::English;
+use crate::sequence::letter_indexes;
+use crate::{LetterCounts, Word, DEFAULT_MIN_WORD_LEN};
+
+/// Prefix tree of a list of words.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trie {
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Node {
+    /// Child nodes, sorted by letter (0 for a, 25 for z).
+    children: Vec<(u8, usize)>,
+    /// Indexes of the words ending at this node.
+    words: Vec<usize>,
+}
+
+/// A list of words searched through its trie.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrieDictionary {
+    words: Vec<Word>,
+    trie: Trie,
+}
+
+/// A dictionary word found inside a text, see [`Dictionary::find_embedded_words`](crate::Dictionary::find_embedded_words).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmbeddedWord {
+    pub word: String,
+    /// Byte offset of the first letter of the word in the text.
+    pub start: usize,
+    /// Byte offset just after the last letter of the word in the text.
+    pub end: usize,
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Trie { nodes: vec![Node::default()] }
+    }
+}
+
+impl Trie {
+    /// Index of the root node.
+    pub(crate) const ROOT: usize = 0;
+
+    /// Builds the trie of a list of words. Words without letters are left out.
+    pub fn new(words: &[Word]) -> Trie {
+        let mut trie = Trie::default();
+        for (index, word) in words.iter().enumerate() {
+            let mut node = Trie::ROOT;
+            let mut letters = letter_indexes(&word.value).peekable();
+            if letters.peek().is_none() { continue; }
+            for letter in letters {
+                node = match trie.child(node, letter as u8) {
+                    Some(child) => child,
+                    None => trie.add_child(node, letter as u8),
+                };
+            }
+            trie.nodes[node].words.push(index);
+        }
+        trie
+    }
+
+    /// Returns `true` if some word has exactly the letters of `word`, in order.
+    pub fn contains(&self, word: &str) -> bool {
+        letter_indexes(word)
+            .try_fold(Trie::ROOT, |node, letter| self.child(node, letter as u8))
+            .is_some_and(|node| node != Trie::ROOT && !self.nodes[node].words.is_empty())
+    }
+
+    /// Indexes of the words whose letters start with the letters of `prefix`, in no particular
+    /// order. An empty prefix matches every word.
+    pub fn starting_with(&self, prefix: &str) -> Vec<usize> {
+        let Some(start) = letter_indexes(prefix).try_fold(Trie::ROOT, |node, letter| self.child(node, letter as u8)) else {
+            return vec![];
+        };
+        let (mut found, mut pending) = (vec![], vec![start]);
+        while let Some(node) = pending.pop() {
+            found.extend_from_slice(&self.nodes[node].words);
+            pending.extend(self.nodes[node].children.iter().map(|&(_, child)| child));
+        }
+        found
+    }
+
+    /// Number of nodes, including the root.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Child of a node for a letter (0 for a, 25 for z).
+    pub(crate) fn child(&self, node: usize, letter: u8) -> Option<usize> {
+        let children = &self.nodes[node].children;
+        children.binary_search_by_key(&letter, |&(letter, _)| letter)
+            .ok()
+            .map(|position| children[position].1)
+    }
+
+    /// Indexes of the words ending at a node.
+    pub(crate) fn words_at(&self, node: usize) -> &[usize] {
+        &self.nodes[node].words
+    }
+
+    fn add_child(&mut self, node: usize, letter: u8) -> usize {
+        let child = self.nodes.len();
+        self.nodes.push(Node::default());
+        let children = &mut self.nodes[node].children;
+        let position = children.partition_point(|&(other, _)| other < letter);
+        children.insert(position, (letter, child));
+        child
+    }
+}
+
+impl TrieDictionary {
+    pub fn new(words: Vec<Word>) -> Self {
+        let trie = Trie::new(&words);
+        TrieDictionary { words, trie }
+    }
+
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Words that can be constructed from the list of letters, ignoring case and any character
+    /// that is not an ASCII letter, in dictionary order, skipping words shorter than
+    /// [`DEFAULT_MIN_WORD_LEN`], as [`Dictionary::find`](crate::Dictionary::find).
+    pub fn find(&self, letters: &str) -> Vec<&str> {
+        self.find_counts(&LetterCounts::from_word::<English>(letters))
+    }
+
+    /// Same as [`find`](TrieDictionary::find), with the letters already counted.
+    pub fn find_counts(&self, list: &LetterCounts) -> Vec<&str> {
+        let mut left = *list.as_array();
+        let mut found = vec![];
+        self.collect(Trie::ROOT, 0, DEFAULT_MIN_WORD_LEN, &mut left, &mut found);
+        found.sort_unstable();
+        found.into_iter().map(|index| self.words[index].value.as_str()).collect()
+    }
+
+    /// Adds the words ending at `node` or under it whose remaining letters are `left`.
+    fn collect(&self, node: usize, depth: usize, min_len: usize, left: &mut [u8; 26], found: &mut Vec<usize>) {
+        if depth >= min_len.max(1) {
+            found.extend_from_slice(self.trie.words_at(node));
+        }
+        for &(letter, child) in &self.trie.nodes[node].children {
+            if left[letter as usize] == 0 { continue; }
+            left[letter as usize] -= 1;
+            self.collect(child, depth + 1, min_len, left, found);
+            left[letter as usize] += 1;
+        }
+    }
+}
+
+impl From<Vec<Word>> for TrieDictionary {
+    fn from(words: Vec<Word>) -> Self {
+        TrieDictionary::new(words)
+    }
+}
+
+/// Finds every word of the trie appearing as a run of consecutive letters of `text`, ignoring
+/// anything in between letters. Results are sorted by start and then by length.
+pub(crate) fn find_embedded(trie: &Trie, words: &[Word], text: &str, min_len: usize) -> Vec<EmbeddedWord> {
+    let letters: Vec<(usize, usize, u8)> = text.char_indices()
+        .filter(|(_, c)| c.is_ascii_alphabetic())
+        .map(|(offset, c)| (offset, offset + c.len_utf8(), c.to_ascii_lowercase() as u8 - b'a'))
+        .collect();
+
+    let mut found = vec![];
+    for start in 0..letters.len() {
+        let mut node = Trie::ROOT;
+        for (len, &(_, end, letter)) in letters[start..].iter().enumerate().map(|(i, letter)| (i + 1, letter)) {
+            node = match trie.child(node, letter) {
+                Some(child) => child,
+                None => break,
+            };
+            if len < min_len { continue; }
+            found.extend(trie.words_at(node).iter().map(|&index| EmbeddedWord {
+                word: words[index].value.clone(),
+                start: letters[start].0,
+                end,
+            }));
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod trie_tests {
+    use crate::{test_words, Dictionary};
+
+    use super::{Trie, TrieDictionary};
+
+    #[test]
+    fn contains() {
+        let trie = Trie::new(&test_words(&["cat", "Cattle", "dog"]));
+        assert!(trie.contains("cat"));
+        assert!(trie.contains("CATTLE"));
+        assert!(!trie.contains("catt"));
+        assert!(!trie.contains("ca"));
+        assert!(!trie.contains(""));
+    }
+
+    #[test]
+    fn starting_with() {
+        let trie = Trie::new(&test_words(&["cat", "Cattle", "dog", "ca-t"]));
+        let mut output = trie.starting_with("CAT");
+        output.sort_unstable();
+        assert_eq!(output, vec![0, 1, 3]);
+        assert_eq!(trie.starting_with("").len(), 4);
+        assert!(trie.starting_with("cog").is_empty());
+    }
+
+    #[test]
+    fn shares_prefixes() {
+        let trie = Trie::new(&test_words(&["cat", "cattle", "car"]));
+        assert_eq!(trie.node_count(), 1 + 3 + 3 + 1);
+    }
+
+    #[test]
+    fn words_without_letters_are_skipped() {
+        let trie = Trie::new(&test_words(&["123", ""]));
+        assert_eq!(trie.node_count(), 1);
+        assert!(!trie.contains("123"));
+    }
+
+    #[test]
+    fn search() {
+        let words = test_words(&["cat", "Cattle", "act", "a", "at", "ta-t", "dog", "tact", "123"]);
+        let trie: TrieDictionary = words.clone().into();
+        let dictionary: Dictionary = words.into();
+        for letters in ["tca", "ttac", "CATTLE", "dog", "", "zz"] {
+            assert_eq!(trie.find(letters), dictionary.find(letters), "{}", letters);
+        }
+        assert_eq!(trie.find("tatc"), vec!["cat", "act", "at", "ta-t", "tact"]);
+    }
+}
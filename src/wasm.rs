@@ -0,0 +1,60 @@
+//! JavaScript bindings, for word games running in a browser.
+//!
+//! The crate builds for `wasm32-unknown-unknown` without its default features, searches being
+//! sequential without `parallel`, and without `fs` and `random`, which need a file system and an
+//! operating system's random numbers:
+//!
+//! ```sh
+//! cargo build --target wasm32-unknown-unknown --no-default-features --features wasm
+//! ```
+//!
+//! A [`WasmDictionary`] is built from the text of a word list, one word per line, e.g. fetched
+//! by the page, and searched like a [`Dictionary`].
+
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+use crate::{Dictionary, Word};
+
+/// A [`Dictionary`] exported to JavaScript.
+#[wasm_bindgen]
+pub struct WasmDictionary {
+    dictionary: Dictionary,
+}
+
+#[wasm_bindgen]
+impl WasmDictionary {
+    /// Indexes the words of `words_text`, one per line.
+    #[wasm_bindgen(constructor)]
+    pub fn new(words_text: &str) -> WasmDictionary {
+        WasmDictionary { dictionary: Dictionary::new(words_text.lines().map(Word::new).collect()) }
+    }
+
+    /// Number of words.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.dictionary.len()
+    }
+
+    /// Returns `true` if the dictionary has a word with the letters of `word`, in order.
+    pub fn contains(&self, word: &str) -> bool {
+        self.dictionary.contains(word)
+    }
+
+    /// The words that can be constructed from `letters`, as an array of strings in dictionary order.
+    pub fn find(&self, letters: &str) -> JsValue {
+        self.dictionary.find(letters).into_iter().map(JsValue::from_str).collect::<Array>().into()
+    }
+}
+
+#[cfg(test)]
+mod wasm_dictionary_tests {
+    use super::WasmDictionary;
+
+    #[test]
+    fn words_text() {
+        let dictionary = WasmDictionary::new("cat\r\nact\n\ndog\n");
+        assert_eq!(dictionary.length(), 4);
+        assert!(dictionary.contains("Dog") && !dictionary.contains("god"));
+    }
+}
@@ -0,0 +1,197 @@
+//! Wordle assistant: feedback computation, candidate filtering and guess ranking.
+//!
+//! Guesses are ranked by the entropy of the feedback they would produce over the remaining
+//! candidates, i.e. by how much they are expected to narrow the candidates down.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::parallel::*;
+use crate::Dictionary;
+
+/// Feedback for a single letter of a guess.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mark {
+    /// The letter is not in the answer (or not as many times as it is in the guess).
+    Absent,
+    /// The letter is in the answer, at another position.
+    Present,
+    /// The letter is in the answer at this position.
+    Correct,
+}
+
+/// Candidate answers of a game, narrowed down as feedback comes in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WordleSolver {
+    len: usize,
+    candidates: Vec<String>,
+}
+
+/// Feedback of a guess for a given answer. Repeated letters are marked present at most as many
+/// times as they appear in the answer, `Correct` marks taking precedence.
+pub fn feedback(guess: &str, answer: &str) -> Vec<Mark> {
+    let guess: Vec<u8> = guess.bytes().map(|b| b.to_ascii_lowercase()).collect();
+    let answer: Vec<u8> = answer.bytes().map(|b| b.to_ascii_lowercase()).collect();
+    let mut marks = vec![Mark::Absent; guess.len()];
+    let mut unmatched: HashMap<u8, usize> = HashMap::new();
+    for (position, &letter) in answer.iter().enumerate() {
+        if guess.get(position) == Some(&letter) {
+            marks[position] = Mark::Correct;
+        } else {
+            *unmatched.entry(letter).or_insert(0) += 1;
+        }
+    }
+    for (position, letter) in guess.iter().enumerate() {
+        if marks[position] == Mark::Correct { continue; }
+        if let Some(count) = unmatched.get_mut(letter).filter(|count| **count > 0) {
+            *count -= 1;
+            marks[position] = Mark::Present;
+        }
+    }
+    marks
+}
+
+/// Parses feedback written one character per letter: `g` for correct, `y` for present and
+/// `b`, `.` or `-` for absent, case-insensitively. Returns `None` on any other character.
+pub fn parse_feedback(pattern: &str) -> Option<Vec<Mark>> {
+    pattern.chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            'g' => Some(Mark::Correct),
+            'y' => Some(Mark::Present),
+            'b' | '.' | '-' => Some(Mark::Absent),
+            _ => None,
+        })
+        .collect()
+}
+
+impl WordleSolver {
+    /// Starts a game with every word of `len` letters of the dictionary as a candidate.
+    /// Candidates are lowercased and deduplicated.
+    pub fn new(dictionary: &Dictionary, len: usize) -> Self {
+        let candidates: BTreeSet<String> = dictionary.words_of_length(len)
+            .filter(|word| word.value.chars().all(|c| c.is_ascii_alphabetic()))
+            .map(|word| word.value.to_ascii_lowercase())
+            .collect();
+        WordleSolver { len, candidates: candidates.into_iter().collect() }
+    }
+
+    /// Remaining candidates, in alphabetical order.
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    /// Keeps only the candidates that would have produced `marks` for `guess`.
+    pub fn apply(&mut self, guess: &str, marks: &[Mark]) {
+        self.candidates.retain(|candidate| feedback(guess, candidate) == marks);
+    }
+
+    /// Ranks `guesses` (the remaining candidates if `None`) by the entropy, in bits, of the
+    /// feedback they produce over the remaining candidates, and returns the `top` best.
+    /// Ties are broken by preferring candidates, then alphabetically.
+    pub fn rank_guesses(&self, guesses: Option<&[String]>, top: usize) -> Vec<(String, f64)> {
+        let guesses = guesses.unwrap_or(&self.candidates);
+        let mut ranked: Vec<(String, f64, bool)> = guesses.par_iter()
+            .filter(|guess| guess.len() == self.len)
+            .map(|guess| {
+                let guess = guess.to_ascii_lowercase();
+                let entropy = self.entropy(&guess);
+                let is_candidate = self.candidates.binary_search(&guess).is_ok();
+                (guess, entropy, is_candidate)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(b.2.cmp(&a.2)).then(a.0.cmp(&b.0)));
+        ranked.dedup_by(|a, b| a.0 == b.0);
+        ranked.into_iter().take(top).map(|(guess, entropy, _)| (guess, entropy)).collect()
+    }
+
+    /// Entropy of the partition of the candidates by the feedback of a guess.
+    fn entropy(&self, guess: &str) -> f64 {
+        let mut partitions: HashMap<u32, usize> = HashMap::new();
+        for candidate in &self.candidates {
+            *partitions.entry(encode(&feedback(guess, candidate))).or_insert(0) += 1;
+        }
+        let total = self.candidates.len() as f64;
+        partitions.values()
+            .map(|&count| {
+                let p = count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+}
+
+/// Packs feedback into a base-3 number.
+fn encode(marks: &[Mark]) -> u32 {
+    marks.iter().fold(0, |code, mark| code * 3 + *mark as u32)
+}
+
+#[cfg(test)]
+mod feedback_tests {
+    use super::{feedback, parse_feedback, Mark};
+
+    #[test]
+    fn all_correct() {
+        assert_eq!(feedback("crane", "CRANE"), vec![Mark::Correct; 5]);
+    }
+
+    #[test]
+    fn present_and_absent() {
+        assert_eq!(feedback("trace", "crate"), parse_feedback("yggyg").unwrap());
+        assert_eq!(feedback("fghij", "crate"), vec![Mark::Absent; 5]);
+    }
+
+    #[test]
+    fn repeated_letters() {
+        assert_eq!(feedback("speed", "abide"), parse_feedback("..y.y").unwrap());
+        assert_eq!(feedback("eerie", "crane"), parse_feedback("..y.g").unwrap());
+        assert_eq!(feedback("geese", "eerie"), parse_feedback(".gy.g").unwrap());
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        assert_eq!(parse_feedback("gyx"), None);
+    }
+}
+
+#[cfg(test)]
+mod wordle_solver_tests {
+    use crate::{test_words, Dictionary};
+
+    use super::{feedback, WordleSolver};
+
+    fn dictionary() -> Dictionary {
+        test_words(&["crane", "crate", "trace", "slate", "CRATE", "cat", "it's", "react"]).into()
+    }
+
+    #[test]
+    fn candidates() {
+        let solver = WordleSolver::new(&dictionary(), 5);
+        assert_eq!(solver.candidates(), ["crane", "crate", "react", "slate", "trace"]);
+    }
+
+    #[test]
+    fn apply_narrows_candidates() {
+        let mut solver = WordleSolver::new(&dictionary(), 5);
+        solver.apply("crane", &feedback("crane", "crate"));
+        assert_eq!(solver.candidates(), ["crate"]);
+    }
+
+    #[test]
+    fn rank_guesses() {
+        let solver = WordleSolver::new(&dictionary(), 5);
+        let ranked = solver.rank_guesses(None, 2);
+        assert_eq!(ranked.len(), 2);
+        assert!(ranked[0].1 >= ranked[1].1);
+        let best = (5.0f64).log2();
+        assert!(ranked.iter().all(|(_, entropy)| *entropy <= best + 1e-9));
+    }
+
+    #[test]
+    fn rank_outside_guesses() {
+        let solver = WordleSolver::new(&dictionary(), 5);
+        let guesses = vec!["zzzzz".to_string(), "crate".to_string(), "toolong".to_string()];
+        let ranked = solver.rank_guesses(Some(&guesses), 10);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].0, "crate");
+        assert_eq!(ranked[1], ("zzzzz".to_string(), 0.0));
+    }
+}
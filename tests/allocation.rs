@@ -0,0 +1,29 @@
+//! Allocation counts, with a counting global allocator. Run with `--features alloc-count`.
+#![cfg(feature = "alloc-count")]
+
+use find_words::allocation::{count, CountingAllocator};
+use find_words::alphabet::English;
+use find_words::{can_be_constructed, Dictionary, LetterCounts};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator::system();
+
+const LEXICON: &str = "tests/golden/lexicons/tiny.txt";
+
+/// A single test, so that no other test allocates while counting.
+#[test]
+fn allocation_counts() {
+    let word = LetterCounts::from_word::<English>("crate");
+    let list = LetterCounts::from_word::<English>("tracer");
+    let (constructable, checked) = count(|| can_be_constructed(&word, &list));
+    assert!(constructable);
+    assert_eq!(checked.allocations, 0);
+
+    let (dictionary, load) = count(|| Dictionary::from_file(LEXICON).unwrap());
+    assert!(load.allocations >= dictionary.len() as u64);
+    assert!(load.bytes > 0);
+
+    let (words, query) = count(|| dictionary.find("tracer").len());
+    assert!(words > 0);
+    assert!(query.allocations > 0);
+}
@@ -0,0 +1,224 @@
+#![cfg(all(feature = "fs", feature = "random"))]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn find_words(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_find_words"))
+        .args(args)
+        .output()
+        .expect("cannot run find_words")
+}
+
+/// Runs the program with `input` on stdin.
+fn find_words_with_input<I: AsRef<[u8]>>(args: &[&str], input: I) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_find_words"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("cannot run find_words");
+    child.stdin.take().unwrap().write_all(input.as_ref()).unwrap();
+    child.wait_with_output().expect("cannot run find_words")
+}
+
+#[test]
+fn json_error_for_invalid_letters() {
+    let output = find_words(&["--errors", "json", "ab1"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with(r#"{"error":{"code":"invalid_letters","message":""#), "{}", stderr);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn pattern() {
+    let output = find_words(&["--dict", "tests/golden/lexicons/tiny.txt", "--pattern", "c__t", "ar"]);
+    assert!(String::from_utf8(output.stdout).unwrap().ends_with("Words that can be constructed\ncart\n"));
+    let output = find_words(&["--errors", "json", "--dict", "tests/golden/lexicons/tiny.txt", "--pattern", "c-t", "ar"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with(r#"{"error":{"code":"invalid_pattern","message":""#), "{}", stderr);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn json_error_for_missing_dictionary() {
+    let output = find_words(&["--errors", "json", "--dictionary", "missing.txt", "abc"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with(r#"{"error":{"code":"dictionary_unreadable","message":""#), "{}", stderr);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn text_error_by_default() {
+    let output = find_words(&["ab1"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr, "Invalid list of letters: invalid character '1' at position 2\n");
+}
+
+#[test]
+fn exit_code_when_words_are_found() {
+    assert_eq!(find_words(&["--dictionary", "tests/golden/lexicons/tiny.txt", "tca"]).status.code(), Some(0));
+}
+
+#[test]
+fn exit_code_when_no_words_are_found() {
+    let output = find_words(&["--dictionary", "tests/golden/lexicons/tiny.txt", "cmbl"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn exit_code_for_usage_errors() {
+    assert_eq!(find_words(&["--bogus"]).status.code(), Some(2));
+    assert_eq!(find_words(&["abc", "def"]).status.code(), Some(2));
+}
+
+#[test]
+fn families_export() {
+    let output = find_words(&["--dictionary", "tests/golden/lexicons/tiny.txt", "--families", "csv"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("alphagram,size,members,total_score\n"), "{}", stdout);
+    assert!(stdout.contains("\nact,2,act cat,10\n"), "{}", stdout);
+    assert!(stdout.contains("\ndgo,2,dog god,10\n"), "{}", stdout);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn stripped_characters_are_reported() {
+    let output = find_words(&["--dictionary", "tests/golden/lexicons/tiny.txt", "--strip", "t1c a!"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Removed 2 characters: '1' at position 1, '!' at position 5\nLetters used: tca\n"), "{}", stdout);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(find_words(&["--dictionary", "tests/golden/lexicons/tiny.txt", "t c a"]).stdout).unwrap();
+    assert!(!stdout.contains("Removed"), "{}", stdout);
+}
+
+#[test]
+fn letters_length_and_sort_options() {
+    let output = find_words(&["--dict", "tests/golden/lexicons/tiny.txt", "--letters", "tcagod", "--min-len", "3", "--sort", "alpha"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let words: Vec<&str> = stdout.lines().skip(2).collect();
+    assert!(words.windows(2).all(|pair| pair[0] <= pair[1]), "{}", stdout);
+    assert!(words.contains(&"cat") && words.iter().all(|word| word.len() >= 3), "{}", stdout);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn json_output() {
+    let output = find_words(&["--dict", "tests/golden/lexicons/tiny.txt", "--output", "json", "--max-len", "3", "--sort", "score", "tcab"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, concat!(
+        "{\"letters\":\"tcab\",\"words\":[\"cab\",\"act\",\"bat\",\"cat\",\"tab\",\"at\"],\"matches\":[",
+        "{\"value\":\"cab\",\"length\":3,\"score\":7,\"letters_used\":\"abc\"},",
+        "{\"value\":\"act\",\"length\":3,\"score\":5,\"letters_used\":\"act\"},",
+        "{\"value\":\"bat\",\"length\":3,\"score\":5,\"letters_used\":\"abt\"},",
+        "{\"value\":\"cat\",\"length\":3,\"score\":5,\"letters_used\":\"act\"},",
+        "{\"value\":\"tab\",\"length\":3,\"score\":5,\"letters_used\":\"abt\"},",
+        "{\"value\":\"at\",\"length\":2,\"score\":2,\"letters_used\":\"at\"}]}\n",
+    ));
+    let output = find_words(&["--dict", "tests/golden/lexicons/tiny.txt", "--output", "json", "--min-len", "1", "--max-len", "2", "tcab"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), concat!(
+        "{\"letters\":\"tcab\",\"words\":[\"a\",\"at\"],\"matches\":[",
+        "{\"value\":\"a\",\"length\":1,\"score\":1,\"letters_used\":\"a\"},",
+        "{\"value\":\"at\",\"length\":2,\"score\":2,\"letters_used\":\"at\"}]}\n",
+    ));
+}
+
+#[test]
+fn csv_output() {
+    let output = find_words(&["--dict", "tests/golden/lexicons/tiny.txt", "--output", "csv", "--sort", "alpha", "tca"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "value,length,score,letters_used\nact,3,5,act\nat,2,2,at\ncat,3,5,act\n");
+    assert_eq!(find_words(&["--output", "xml", "tca"]).status.code(), Some(2));
+}
+
+#[test]
+fn random_list_of_given_length() {
+    let output = find_words(&["--dict", "tests/golden/lexicons/tiny.txt", "--random", "7"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let list = stdout.lines().next().unwrap().trim_start_matches("List of letters: ");
+    assert_eq!(list.len(), 7, "{}", stdout);
+    assert_eq!(find_words(&["--random", "7", "abc"]).status.code(), Some(2));
+    assert_eq!(find_words(&["--min-len", "5", "--max-len", "3"]).status.code(), Some(2));
+    assert_eq!(find_words(&["--sort", "size"]).status.code(), Some(2));
+}
+
+#[test]
+fn doctor() {
+    let output = find_words(&["doctor", "--dict", "tests/golden/lexicons/tiny.txt"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Dictionary: tests/golden/lexicons/tiny.txt\nLoaded 26 words in "), "{}", stdout);
+    assert!(stdout.contains("\nData-quality warnings: none\n"), "{}", stdout);
+    assert!(stdout.ends_with("\nOK: the reference query found the 3 words of a full scan\n"), "{}", stdout);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn doctor_failures() {
+    let empty = std::env::temp_dir().join(format!("find-words-doctor-{}.txt", std::process::id()));
+    std::fs::write(&empty, "").unwrap();
+    let output = find_words(&["doctor", "--errors", "json", "--dict", empty.to_str().unwrap()]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with(r#"{"error":{"code":"check_failed","message":""#), "{}", stderr);
+    assert_eq!(output.status.code(), Some(4));
+    std::fs::remove_file(empty).unwrap();
+    assert_eq!(find_words(&["doctor", "--dict", "missing.txt"]).status.code(), Some(3));
+    assert_eq!(find_words(&["doctor", "abc"]).status.code(), Some(2));
+}
+
+#[test]
+fn repl() {
+    let input = "tca\n:minlen 3\ntca\n:sort alpha\n\ntcab\nab1\n:bogus\n:dict missing.txt\n:quit\ndog\n";
+    let output = find_words_with_input(&["repl", "--dict", "tests/golden/lexicons/tiny.txt"], input);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "act\nat\ncat\nact\ncat\nact\nbat\ncab\ncat\ntab\n");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr.lines().count(), 3, "{}", stderr);
+    assert!(stderr.starts_with("Invalid list of letters: invalid character '1' at position 2\nunknown command :bogus"), "{}", stderr);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn repl_json_error_for_unreadable_input() {
+    let output = find_words_with_input(&["repl", "--errors", "json", "--dict", "tests/golden/lexicons/tiny.txt"], b"tca\n\xff\n");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "act\nat\ncat\n");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with(r#"{"error":{"code":"input_unreadable","message":"Cannot read input: "#), "{}", stderr);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn repl_json_output() {
+    let output = find_words_with_input(&["repl", "--dict", "tests/golden/lexicons/tiny.txt", "--output", "json", "--min-len", "3"], "tca\n");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), concat!(
+        "{\"letters\":\"tca\",\"words\":[\"act\",\"cat\"],\"matches\":[",
+        "{\"value\":\"act\",\"length\":3,\"score\":5,\"letters_used\":\"act\"},",
+        "{\"value\":\"cat\",\"length\":3,\"score\":5,\"letters_used\":\"act\"}]}\n",
+    ));
+}
+
+#[test]
+fn anagram() {
+    let output = find_words(&["anagram", "--dict", "tests/golden/lexicons/tiny.txt", "--max-words", "2", "Tab, cat"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "act bat\nact tab\nbat cat\ncat tab\n");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains(" complete: searched 7 of 7 first words (100%)"), "{}", stderr);
+    assert_eq!(output.status.code(), Some(0));
+    let output = find_words(&["anagram", "--dict", "tests/golden/lexicons/tiny.txt", "--timeout", "0s", "tab cat"]);
+    assert!(String::from_utf8(output.stderr).unwrap().contains("stopped at the deadline: searched 0 of 7 first words"));
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(find_words(&["anagram", "--dict", "tests/golden/lexicons/tiny.txt"]).status.code(), Some(2));
+    assert_eq!(find_words(&["anagram", "--timeout", "soon", "tab cat"]).status.code(), Some(2));
+}
+
+#[test]
+fn soak() {
+    let output = find_words(&["soak", "--dict", "tests/golden/lexicons/tiny.txt", "--qps", "200", "--duration", "100ms", "--threads", "2"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("Soak: 200 searches per second for 0.1s on 2 threads, 1000 racks of 7 tiles\nRan 20 searches in "), "{}", stdout);
+    assert!(stdout.contains("\nLatency: p50 ") && stdout.contains("\nMemory: "), "{}", stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(find_words(&["soak", "--qps", "0"]).status.code(), Some(2));
+    assert_eq!(find_words(&["soak", "--duration", "later"]).status.code(), Some(2));
+    assert_eq!(find_words(&["soak", "abc"]).status.code(), Some(2));
+}
@@ -0,0 +1,70 @@
+//! Semver guards: the free functions of the first releases must keep their signatures and
+//! their behavior as the engine behind `Dictionary` changes, except for the single-letter words
+//! that `get_constructable_words` skips under the default minimum word length.
+
+#![cfg(all(feature = "fs", feature = "random"))]
+#![allow(deprecated)]
+
+use std::collections::HashMap;
+use std::io::Error;
+
+use find_words::{generate_random_string, get_constructable_words, get_constructable_words_min_len, get_letters_count, read_words_from_file, Dictionary, Word};
+
+const LEXICON: &str = "tests/golden/lexicons/tiny.txt";
+
+/// Words are built with `rank: None` since 0.2 added the field, which breaks the literals of 0.1,
+/// see the compile-fail example of the Compatibility section of the crate documentation.
+fn words(values: &[&str]) -> Vec<Word> {
+    values.iter().map(|&value| Word { value: value.to_string(), letters: get_letters_count(value), rank: None }).collect()
+}
+
+#[test]
+fn signatures() {
+    let _: fn(&str) -> Result<Vec<Word>, Error> = read_words_from_file;
+    let _: fn(&str) -> HashMap<char, u8> = get_letters_count;
+    let _: fn(Vec<Word>, &HashMap<char, u8>) -> Vec<String> = get_constructable_words;
+    let _: fn(Option<u8>) -> String = generate_random_string;
+}
+
+#[test]
+fn read_keeps_lines_verbatim() {
+    let words = read_words_from_file(LEXICON).unwrap();
+    assert_eq!(words.len(), 26);
+    assert_eq!(words[1], Word { value: "act".to_string(), letters: HashMap::from([('a', 1), ('c', 1), ('t', 1)]), rank: None });
+    assert!(read_words_from_file("missing.txt").is_err());
+}
+
+#[test]
+fn letters_count_ignores_case_and_non_letters() {
+    assert_eq!(get_letters_count("Do-dge 2"), HashMap::from([('d', 2), ('o', 1), ('g', 1), ('e', 1)]));
+    assert_eq!(get_letters_count("é!"), HashMap::new());
+}
+
+#[test]
+fn constructable_words_keep_order_case_and_duplicates() {
+    let output = get_constructable_words(words(&["Cat", "", "tact", "act", "act", "123"]), &get_letters_count("tac"));
+    assert_eq!(output, vec!["Cat", "act", "act"]);
+    assert_eq!(get_constructable_words(words(&["a"]), &HashMap::new()), Vec::<String>::new());
+}
+
+#[test]
+fn single_letter_words_need_a_minimum_of_1() {
+    assert_eq!(get_constructable_words(words(&["a", "at"]), &get_letters_count("ta")), vec!["at"]);
+    assert_eq!(get_constructable_words_min_len(words(&["a", "at"]), &get_letters_count("ta"), 1), vec!["a", "at"]);
+}
+
+#[test]
+fn constructable_words_agree_with_dictionary() {
+    let lexicon = read_words_from_file(LEXICON).unwrap();
+    let dictionary = Dictionary::new(lexicon.clone());
+    for _ in 0..50 {
+        let list = generate_random_string(Some(12));
+        assert_eq!(get_constructable_words(lexicon.clone(), &get_letters_count(&list)), dictionary.find(&list), "list {:?}", list);
+    }
+}
+
+#[test]
+fn random_strings_are_lowercase_letters() {
+    assert!(generate_random_string(Some(30)).chars().all(|c| c.is_ascii_lowercase()));
+    assert!((1..=200).contains(&generate_random_string(None).len()));
+}
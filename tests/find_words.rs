@@ -1,9 +1,14 @@
-use find_words::{get_constructable_words, get_letters_count, read_words_from_file};
+#![cfg(feature = "fs")]
+
+use find_words::{Dictionary, get_constructable_words, get_letters_count, read_words_from_file};
+
+/// Small bundled lexicon, so that changes to words.txt do not break the tests.
+const LEXICON: &str = "tests/golden/lexicons/tiny.txt";
 
 #[test]
 fn find_words_in_4_letter_list() {
     let list = "cmbl";
-    let words = read_words_from_file("words.txt");
+    let words = read_words_from_file(LEXICON);
     let expected: Vec<String> = vec![];
     let output = match words {
         Ok(words) => get_constructable_words(words, &get_letters_count(list)),
@@ -15,8 +20,8 @@ fn find_words_in_4_letter_list() {
 #[test]
 fn find_words_in_8_letter_list() {
     let list = "wartsmrf";
-    let words = read_words_from_file("words.txt");
-    let expected: Vec<String> = vec!["arm", "art"]
+    let words = read_words_from_file(LEXICON);
+    let expected: Vec<String> = vec!["arm", "art", "at", "mart", "warts"]
         .into_iter()
         .map(|s| s.to_string())
         .collect();
@@ -30,8 +35,8 @@ fn find_words_in_8_letter_list() {
 #[test]
 fn find_words_in_20_letter_list() {
     let list = "fsucwcaumvxvkfvpbkjw";
-    let words = read_words_from_file("words.txt");
-    let expected: Vec<String> = vec!["back", "camp", "cap", "cub", "cup"]
+    let words = read_words_from_file(LEXICON);
+    let expected: Vec<String> = vec!["back", "cab", "camp", "cap", "cub", "cup"]
         .into_iter()
         .map(|s| s.to_string())
         .collect();
@@ -40,4 +45,16 @@ fn find_words_in_20_letter_list() {
         _err => panic!("problem reading file")
     };
     assert_eq!(output, expected);
-}
\ No newline at end of file
+}
+
+#[test]
+fn dictionary_find_matches_linear_scan() {
+    let words = read_words_from_file(LEXICON).expect("problem reading file");
+    let dictionary = Dictionary::new(words.clone());
+    for list in ["cmbl", "wartsmrf", "fsucwcaumvxvkfvpbkjw", "yxmiasdaegwyxmiasdaegwyxmiasdaegw"] {
+        let expected = get_constructable_words(words.clone(), &get_letters_count(list));
+        let (output, stats) = dictionary.find_with_stats(list);
+        assert_eq!(output, expected);
+        assert!(stats.candidates < stats.words);
+    }
+}
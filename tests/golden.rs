@@ -0,0 +1,142 @@
+//! Golden-file tests: every feature has a file of cases under `tests/golden`, run against the
+//! small bundled lexicons of `tests/golden/lexicons` rather than the words.txt of the repository.
+//!
+//! A case is a line of ` | `-separated inputs followed by ` => ` and the expected output. Lines
+//! starting with `#` and blank lines are comments. Run the tests with `UPDATE_GOLDEN=1` to
+//! rewrite the expected outputs from the current results, then review the diff.
+
+#![cfg(feature = "fs")]
+
+use std::env;
+use std::fs;
+use std::ops::ControlFlow;
+
+use find_words::mapping::{find_keypad_words, KeypadMode};
+use find_words::pattern::find_matching_pattern;
+use find_words::phrase::{find_phrases, PhraseOptions};
+use find_words::puzzle::Puzzle;
+use find_words::sequence::{find_ordered_words, OrderMode};
+use find_words::{read_words_from_file, Dictionary, Word};
+
+const GOLDEN_DIR: &str = "tests/golden";
+
+fn lexicon(name: &str) -> Vec<Word> {
+    let path = format!("{}/lexicons/{}.txt", GOLDEN_DIR, name);
+    read_words_from_file(&path).unwrap_or_else(|error| panic!("cannot read {}: {}", path, error))
+}
+
+/// Runs every case of `<name>.golden` through `run`, and fails listing every case whose
+/// output differs from the expected one.
+fn check_golden<F: Fn(&[&str]) -> String>(name: &str, run: F) {
+    let path = format!("{}/{}.golden", GOLDEN_DIR, name);
+    let contents = fs::read_to_string(&path).unwrap_or_else(|error| panic!("cannot read {}: {}", path, error));
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+    let mut updated = vec![];
+    let mut failures = vec![];
+    for (number, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            updated.push(line.to_string());
+            continue;
+        }
+        let (inputs, expected) = line.split_once("=>")
+            .unwrap_or_else(|| panic!("{}:{}: missing `=>`", path, number + 1));
+        let inputs: Vec<&str> = inputs.split('|').map(str::trim).collect();
+        let output = run(&inputs);
+        if output != expected.trim() {
+            failures.push(format!("{}:{}: {}\n  expected: {}\n  actual:   {}", path, number + 1, inputs.join(" | "), expected.trim(), output));
+        }
+        updated.push(format!("{}=>{}{}", line.split_once("=>").unwrap().0, if output.is_empty() { "" } else { " " }, output));
+    }
+    if update {
+        fs::write(&path, updated.join("\n") + "\n").unwrap_or_else(|error| panic!("cannot write {}: {}", path, error));
+        return;
+    }
+    assert!(failures.is_empty(), "{} golden case(s) failed:\n{}", failures.len(), failures.join("\n"));
+}
+
+fn join<S: AsRef<str>>(words: &[S]) -> String {
+    words.iter().map(|word| word.as_ref()).collect::<Vec<_>>().join(" ")
+}
+
+#[test]
+fn search() {
+    let dictionary = Dictionary::new(lexicon("tiny"));
+    check_golden("search", |inputs| join(&dictionary.find(inputs[0])));
+}
+
+#[test]
+fn constraints() {
+    let dictionary = Dictionary::new(lexicon("tiny"));
+    check_golden("constraints", |inputs| {
+        let min_len: usize = inputs[1].parse().unwrap();
+        let max_len: usize = inputs[2].parse().unwrap();
+        let mut view = dictionary.view().lengths(min_len..=max_len);
+        if inputs[3] != "-" {
+            view = view.starting_with(inputs[3].chars().next().unwrap());
+        }
+        join(&view.find(inputs[0]))
+    });
+}
+
+#[test]
+fn scoring() {
+    let words = lexicon("tiny");
+    check_golden("scoring", |inputs| {
+        let required = inputs[1].chars().next().filter(|&c| c != '-');
+        let puzzle = Puzzle::new(&words, inputs[0], required, inputs[2].parse().unwrap());
+        let scores: Vec<String> = puzzle.answers().iter().map(|answer| puzzle.word_score(answer).to_string()).collect();
+        format!("{} : {} / {}", join(puzzle.answers()), join(&scores), puzzle.max_score())
+    });
+}
+
+#[test]
+fn keypad() {
+    let words = lexicon("tiny");
+    check_golden("keypad", |inputs| {
+        let mode = match inputs[1] {
+            "any" => KeypadMode::AnyOrder,
+            "exact" => KeypadMode::Exact,
+            mode => panic!("unknown keypad mode {}", mode),
+        };
+        join(&find_keypad_words(&words, inputs[0], mode))
+    });
+}
+
+#[test]
+fn ordered() {
+    let words = lexicon("tiny");
+    check_golden("ordered", |inputs| {
+        let mode = match inputs[1] {
+            "substring" => OrderMode::Substring,
+            "subsequence" => OrderMode::Subsequence,
+            mode => panic!("unknown order mode {}", mode),
+        };
+        join(&find_ordered_words(&words, inputs[0], mode))
+    });
+}
+
+#[test]
+fn pattern() {
+    let words = lexicon("tiny");
+    check_golden("pattern", |inputs| join(&find_matching_pattern(&words, inputs[0], inputs[1]).unwrap()));
+}
+
+#[test]
+fn wildcards() {
+    let dictionary = Dictionary::new(lexicon("phrases"));
+    check_golden("wildcards", |inputs| join(&dictionary.find_with_wildcards(inputs[0], inputs[1].parse().unwrap())));
+}
+
+#[test]
+fn phrase() {
+    let dictionary = Dictionary::new(lexicon("phrases"));
+    check_golden("phrase", |inputs| {
+        let options = PhraseOptions { max_words: inputs[1].parse().unwrap(), timeout: None };
+        let mut phrases = vec![];
+        find_phrases(&dictionary.view(), inputs[0], &options, |words| {
+            phrases.push(words.join(" "));
+            ControlFlow::Continue(())
+        });
+        phrases.join(", ")
+    });
+}
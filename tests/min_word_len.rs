@@ -0,0 +1,28 @@
+//! The minimum word length is the default unless a query asks for another.
+
+use find_words::cache::{QueryCache, QueryOptions};
+use find_words::{get_constructable_words, get_constructable_words_min_len, get_letters_count, Dictionary, Word, DEFAULT_MIN_WORD_LEN};
+
+fn words() -> Vec<Word> {
+    ["a", "I", "at", "tic"].into_iter().map(Word::new).collect()
+}
+
+#[test]
+fn single_letters_only_with_a_minimum_of_1() {
+    let dictionary = Dictionary::new(words());
+    assert_eq!(dictionary.find("tica"), vec!["at", "tic"]);
+    assert_eq!(get_constructable_words(words(), &get_letters_count("tica")), vec!["at", "tic"]);
+    assert_eq!(dictionary.view().lengths(1..).find("tica"), vec!["a", "I", "at", "tic"]);
+    assert_eq!(get_constructable_words_min_len(words(), &get_letters_count("tica"), 1), vec!["a", "I", "at", "tic"]);
+    assert_eq!(dictionary.view().lengths(3..).find("tica"), vec!["tic"]);
+    assert_eq!(get_constructable_words_min_len(words(), &get_letters_count("tica"), 3), vec!["tic"]);
+
+    let mut cache = QueryCache::new(10);
+    assert_eq!(cache.find(&dictionary, "tica", &QueryOptions::default()), vec!["at", "tic"]);
+    let single = QueryOptions { min_len: Some(1), ..QueryOptions::default() };
+    assert_eq!(cache.find(&dictionary, "tica", &single), vec!["a", "I", "at", "tic"]);
+    assert_eq!((cache.hits(), cache.misses()), (0, 2));
+    let default = QueryOptions { min_len: Some(DEFAULT_MIN_WORD_LEN), ..QueryOptions::default() };
+    assert_eq!(cache.find(&dictionary, "tica", &default), vec!["at", "tic"]);
+    assert_eq!(cache.hits(), 1);
+}